@@ -18,7 +18,7 @@ use crate::{
     errors::MerkleError,
     traits::{CommitmentScheme, CRH},
 };
-use snarkvm_utilities::{to_bytes, FromBytes, ToBytes};
+use snarkvm_utilities::{to_bytes, variable_length_integer::*, FromBytes, ToBytes};
 use std::io::{Read, Result as IoResult, Write};
 
 #[derive(Derivative)]
@@ -29,7 +29,8 @@ use std::io::{Read, Result as IoResult, Write};
 )]
 pub struct CommitmentMerklePath<C: CommitmentScheme, H: CRH> {
     pub leaves: (<C as CommitmentScheme>::Output, <C as CommitmentScheme>::Output),
-    pub inner_hashes: (<H as CRH>::Output, <H as CRH>::Output),
+    /// The pairs of inner hashes from the level above the leaves up to the level below the root.
+    pub inner_hashes: Vec<(<H as CRH>::Output, <H as CRH>::Output)>,
 }
 
 impl<C: CommitmentScheme, H: CRH> CommitmentMerklePath<C, H> {
@@ -44,17 +45,19 @@ impl<C: CommitmentScheme, H: CRH> CommitmentMerklePath<C, H> {
             return Ok(false);
         };
 
-        // Check that the inner hash is included in the path
-        let inner_hash = hash_inner_node(parameters, &self.leaves.0, &self.leaves.1)?;
+        // Check that each inner hash is included in the path
+        let mut current_hash = hash_inner_node(parameters, &self.leaves.0, &self.leaves.1)?;
 
-        if inner_hash != self.inner_hashes.0 && inner_hash != self.inner_hashes.1 {
-            return Ok(false);
-        };
+        for (left_hash, right_hash) in &self.inner_hashes {
+            if &current_hash != left_hash && &current_hash != right_hash {
+                return Ok(false);
+            };
 
-        // Check that the root hash is valid.
-        let root = hash_inner_node(parameters, &self.inner_hashes.0, &self.inner_hashes.1)?;
+            current_hash = hash_inner_node(parameters, left_hash, right_hash)?;
+        }
 
-        if &root != root_hash {
+        // Check that the root hash is valid.
+        if &current_hash != root_hash {
             return Ok(false);
         }
 
@@ -73,8 +76,14 @@ impl<C: CommitmentScheme, H: CRH> ToBytes for CommitmentMerklePath<C, H> {
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
         self.leaves.0.write(&mut writer)?;
         self.leaves.1.write(&mut writer)?;
-        self.inner_hashes.0.write(&mut writer)?;
-        self.inner_hashes.1.write(&mut writer)
+
+        variable_length_integer(self.inner_hashes.len() as u64).write(&mut writer)?;
+        for (left_hash, right_hash) in &self.inner_hashes {
+            left_hash.write(&mut writer)?;
+            right_hash.write(&mut writer)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -82,7 +91,12 @@ impl<C: CommitmentScheme, H: CRH> FromBytes for CommitmentMerklePath<C, H> {
     #[inline]
     fn read<R: Read>(mut reader: R) -> IoResult<Self> {
         let leaves = (C::Output::read(&mut reader)?, C::Output::read(&mut reader)?);
-        let inner_hashes = (H::Output::read(&mut reader)?, H::Output::read(&mut reader)?);
+
+        let num_inner_hashes = read_variable_length_integer(&mut reader)?;
+        let mut inner_hashes = Vec::with_capacity(num_inner_hashes);
+        for _ in 0..num_inner_hashes {
+            inner_hashes.push((H::Output::read(&mut reader)?, H::Output::read(&mut reader)?));
+        }
 
         Ok(Self { leaves, inner_hashes })
     }
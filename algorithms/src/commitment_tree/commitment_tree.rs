@@ -19,7 +19,7 @@ use crate::{
     errors::MerkleError,
     traits::{CommitmentScheme, CRH},
 };
-use snarkvm_utilities::{to_bytes, FromBytes, ToBytes};
+use snarkvm_utilities::{to_bytes, variable_length_integer::*, FromBytes, ToBytes};
 
 use std::io::{Read, Result as IoResult, Write};
 
@@ -34,11 +34,12 @@ pub struct CommitmentMerkleTree<C: CommitmentScheme, H: CRH> {
     /// The computed root of the full Merkle tree.
     root: <H as CRH>::Output,
 
-    /// The internal hashes of the commitment Merkle tree
-    inner_hashes: (<H as CRH>::Output, <H as CRH>::Output),
+    /// The internal hashes of the commitment Merkle tree, from the level above the leaves
+    /// up to the level below the root
+    inner_hashes: Vec<Vec<<H as CRH>::Output>>,
 
     /// The leaves of the commitment Merkle tree
-    leaves: [<C as CommitmentScheme>::Output; 4],
+    leaves: Vec<<C as CommitmentScheme>::Output>,
 
     /// The CRH parameters used to construct the Merkle tree
     #[derivative(PartialEq = "ignore", Debug = "ignore")]
@@ -47,19 +48,41 @@ pub struct CommitmentMerkleTree<C: CommitmentScheme, H: CRH> {
 
 impl<C: CommitmentScheme, H: CRH> CommitmentMerkleTree<C, H> {
     /// Construct a new commitment Merkle tree.
-    pub fn new(parameters: H, leaves: &[<C as CommitmentScheme>::Output; 4]) -> Result<Self, MerkleError> {
-        let input_1 = to_bytes![leaves[0], leaves[1]]?;
-        let inner_hash1 = H::hash(&parameters, &input_1)?;
-
-        let input_2 = to_bytes![leaves[2], leaves[3]]?;
-        let inner_hash2 = H::hash(&parameters, &input_2)?;
-
-        let root = H::hash(&parameters, &to_bytes![inner_hash1, inner_hash2]?)?;
+    ///
+    /// The leaves are padded with default commitments up to the next power of two (and at least two),
+    /// so that every level of the tree is full.
+    pub fn new(parameters: H, leaves: &[<C as CommitmentScheme>::Output]) -> Result<Self, MerkleError> {
+        let mut level = leaves
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => hash_inner_node(&parameters, left, right),
+                [left] => hash_inner_node(&parameters, left, &Default::default()),
+                _ => unreachable!(),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Pad the first level with the hash of two default leaves.
+        let default_leaf = <C as CommitmentScheme>::Output::default();
+        level.resize(
+            padded_num_leaves(leaves.len()) / 2,
+            hash_inner_node(&parameters, &default_leaf, &default_leaf)?,
+        );
+
+        let mut inner_hashes = vec![];
+        while level.len() > 1 {
+            let next_level = level
+                .chunks(2)
+                .map(|pair| hash_inner_node(&parameters, &pair[0], &pair[1]))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            inner_hashes.push(level);
+            level = next_level;
+        }
 
         Ok(Self {
-            root,
-            inner_hashes: (inner_hash1, inner_hash2),
-            leaves: leaves.clone(),
+            root: level[0].clone(),
+            inner_hashes,
+            leaves: leaves.to_vec(),
             parameters,
         })
     }
@@ -70,12 +93,12 @@ impl<C: CommitmentScheme, H: CRH> CommitmentMerkleTree<C, H> {
     }
 
     #[inline]
-    pub fn inner_hashes(&self) -> (<H as CRH>::Output, <H as CRH>::Output) {
+    pub fn inner_hashes(&self) -> Vec<Vec<<H as CRH>::Output>> {
         self.inner_hashes.clone()
     }
 
     #[inline]
-    pub fn leaves(&self) -> [<C as CommitmentScheme>::Output; 4] {
+    pub fn leaves(&self) -> Vec<<C as CommitmentScheme>::Output> {
         self.leaves.clone()
     }
 
@@ -83,22 +106,31 @@ impl<C: CommitmentScheme, H: CRH> CommitmentMerkleTree<C, H> {
         &self,
         leaf: &<C as CommitmentScheme>::Output,
     ) -> Result<CommitmentMerklePath<C, H>, MerkleError> {
-        let leaf_index = match self.leaves.iter().position(|l| l == leaf) {
+        let mut index = match self.leaves.iter().position(|l| l == leaf) {
             Some(index) => index,
             _ => return Err(MerkleError::InvalidLeaf),
         };
 
-        let sibling_index = sibling(leaf_index);
-
         let leaf = leaf.clone();
-        let sibling = self.leaves[sibling_index].clone();
+        let sibling_leaf = self.leaves.get(sibling(index)).cloned().unwrap_or_default();
 
-        let leaves = match is_left_child(leaf_index) {
-            true => (leaf, sibling),
-            false => (sibling, leaf),
+        let leaves = match is_left_child(index) {
+            true => (leaf, sibling_leaf),
+            false => (sibling_leaf, leaf),
         };
 
-        let inner_hashes = self.inner_hashes.clone();
+        let mut inner_hashes = Vec::with_capacity(self.inner_hashes.len());
+        for level in &self.inner_hashes {
+            index /= 2;
+
+            let node = level[index].clone();
+            let sibling_node = level[sibling(index)].clone();
+
+            inner_hashes.push(match is_left_child(index) {
+                true => (node, sibling_node),
+                false => (sibling_node, node),
+            });
+        }
 
         Ok(CommitmentMerklePath { leaves, inner_hashes })
     }
@@ -106,25 +138,22 @@ impl<C: CommitmentScheme, H: CRH> CommitmentMerkleTree<C, H> {
     pub fn from_bytes<R: Read>(mut reader: R, parameters: H) -> IoResult<Self> {
         let root = <H as CRH>::Output::read(&mut reader)?;
 
-        let left_inner_hash = <H as CRH>::Output::read(&mut reader)?;
-        let right_inner_hash = <H as CRH>::Output::read(&mut reader)?;
-
-        let inner_hashes = (left_inner_hash, right_inner_hash);
-
-        let mut leaves = vec![];
-        for _ in 0..4 {
-            let leaf = <C as CommitmentScheme>::Output::read(&mut reader)?;
-            leaves.push(leaf);
+        let num_levels = read_variable_length_integer(&mut reader)?;
+        let mut inner_hashes = Vec::with_capacity(num_levels);
+        for _ in 0..num_levels {
+            let num_hashes = read_variable_length_integer(&mut reader)?;
+            let mut level = Vec::with_capacity(num_hashes);
+            for _ in 0..num_hashes {
+                level.push(<H as CRH>::Output::read(&mut reader)?);
+            }
+            inner_hashes.push(level);
         }
 
-        assert_eq!(leaves.len(), 4);
-
-        let leaves = [
-            leaves[0].clone(),
-            leaves[1].clone(),
-            leaves[2].clone(),
-            leaves[3].clone(),
-        ];
+        let num_leaves = read_variable_length_integer(&mut reader)?;
+        let mut leaves = Vec::with_capacity(num_leaves);
+        for _ in 0..num_leaves {
+            leaves.push(<C as CommitmentScheme>::Output::read(&mut reader)?);
+        }
 
         Ok(Self {
             root,
@@ -139,8 +168,16 @@ impl<C: CommitmentScheme, H: CRH> ToBytes for CommitmentMerkleTree<C, H> {
     #[inline]
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
         self.root.write(&mut writer)?;
-        self.inner_hashes.0.write(&mut writer)?;
-        self.inner_hashes.1.write(&mut writer)?;
+
+        variable_length_integer(self.inner_hashes.len() as u64).write(&mut writer)?;
+        for level in &self.inner_hashes {
+            variable_length_integer(level.len() as u64).write(&mut writer)?;
+            for inner_hash in level {
+                inner_hash.write(&mut writer)?;
+            }
+        }
+
+        variable_length_integer(self.leaves.len() as u64).write(&mut writer)?;
         for leaf in &self.leaves {
             leaf.write(&mut writer)?;
         }
@@ -149,20 +186,25 @@ impl<C: CommitmentScheme, H: CRH> ToBytes for CommitmentMerkleTree<C, H> {
     }
 }
 
-/// Returns the index of the sibling leaf, given an index.
+/// Returns the number of leaves in the padded tree.
+#[inline]
+pub fn padded_num_leaves(num_leaves: usize) -> usize {
+    num_leaves.next_power_of_two().max(2)
+}
+
+/// Returns the output hash, given a left and right hash value.
+fn hash_inner_node<H: CRH, L: ToBytes>(crh: &H, left: &L, right: &L) -> Result<<H as CRH>::Output, MerkleError> {
+    let input = to_bytes![left, right]?;
+    Ok(crh.hash(&input)?)
+}
+
+/// Returns the index of the sibling node, given the index of a node within its level.
 #[inline]
 fn sibling(index: usize) -> usize {
-    assert!(index < 4);
-    match index {
-        0 => 1,
-        1 => 0,
-        2 => 3,
-        3 => 2,
-        _ => unreachable!(),
-    }
+    index ^ 1
 }
 
-/// Returns true iff the given index represents a left child.
+/// Returns true iff the given index within a level represents a left child.
 #[inline]
 fn is_left_child(index: usize) -> bool {
     index % 2 == 0
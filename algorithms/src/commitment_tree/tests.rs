@@ -54,16 +54,16 @@ pub type CM = CommitmentMerklePath<C, H>;
 fn generate_merkle_tree<C: CommitmentScheme, H: CRH, R: Rng>(
     commitment: &C,
     crh: &H,
+    num_leaves: usize,
     rng: &mut R,
 ) -> CommitmentMerkleTree<C, H> {
-    let default = <C as CommitmentScheme>::Output::default();
-    let mut leaves = [default.clone(), default.clone(), default.clone(), default];
+    let mut leaves = Vec::with_capacity(num_leaves);
 
-    for leaf in &mut leaves {
+    for _ in 0..num_leaves {
         let leaf_input: [u8; 32] = rng.gen();
         let randomness = <C as CommitmentScheme>::Randomness::rand(rng);
 
-        *leaf = commitment.commit(&leaf_input, &randomness).unwrap();
+        leaves.push(commitment.commit(&leaf_input, &randomness).unwrap());
     }
 
     CommitmentMerkleTree::new(crh.clone(), &leaves).unwrap()
@@ -76,11 +76,13 @@ fn commitment_tree_good_root_test() {
     let commitment = C::setup(rng);
     let crh = H::setup(rng);
 
-    let merkle_tree = generate_merkle_tree(&commitment, &crh, rng);
+    for num_leaves in 1..=8 {
+        let merkle_tree = generate_merkle_tree(&commitment, &crh, num_leaves, rng);
 
-    for leaf in merkle_tree.leaves().iter() {
-        let proof = merkle_tree.generate_proof(&leaf).unwrap();
-        assert!(proof.verify(&crh, &merkle_tree.root(), &leaf).unwrap());
+        for leaf in merkle_tree.leaves().iter() {
+            let proof = merkle_tree.generate_proof(leaf).unwrap();
+            assert!(proof.verify(&crh, &merkle_tree.root(), leaf).unwrap());
+        }
     }
 }
 
@@ -92,14 +94,33 @@ fn commitment_tree_bad_root_test() {
     let commitment = C::setup(rng);
     let crh = H::setup(rng);
 
-    let merkle_tree = generate_merkle_tree(&commitment, &crh, rng);
+    let merkle_tree = generate_merkle_tree(&commitment, &crh, 4, rng);
 
     for leaf in merkle_tree.leaves().iter() {
-        let proof = merkle_tree.generate_proof(&leaf).unwrap();
-        assert!(proof.verify(&crh, &<H as CRH>::Output::default(), &leaf).unwrap());
+        let proof = merkle_tree.generate_proof(leaf).unwrap();
+        assert!(proof.verify(&crh, &<H as CRH>::Output::default(), leaf).unwrap());
     }
 }
 
+#[test]
+fn commitment_tree_padding_test() {
+    let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+
+    let commitment = C::setup(rng);
+    let crh = H::setup(rng);
+
+    let merkle_tree = generate_merkle_tree(&commitment, &crh, 6, rng);
+    assert_eq!(merkle_tree.leaves().len(), 6);
+    assert_eq!(merkle_tree.inner_hashes().len(), 2);
+
+    // The tree is padded with default leaves up to the next power of two.
+    let mut padded_leaves = merkle_tree.leaves();
+    padded_leaves.resize(8, <C as CommitmentScheme>::Output::default());
+
+    let padded_merkle_tree = CommitmentMerkleTree::<C, H>::new(crh, &padded_leaves).unwrap();
+    assert_eq!(merkle_tree.root(), padded_merkle_tree.root());
+}
+
 #[test]
 fn test_serialize_commitment_merkle_tree() {
     let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
@@ -107,12 +128,15 @@ fn test_serialize_commitment_merkle_tree() {
     let commitment = C::setup(rng);
     let crh = H::setup(rng);
 
-    let merkle_tree = generate_merkle_tree(&commitment, &crh, rng);
+    for num_leaves in 1..=8 {
+        let merkle_tree = generate_merkle_tree(&commitment, &crh, num_leaves, rng);
 
-    let merkle_tree_bytes = to_bytes![merkle_tree].unwrap();
-    let recovered_merkle_tree = CommitmentMerkleTree::<C, H>::from_bytes(&merkle_tree_bytes[..], crh).unwrap();
+        let merkle_tree_bytes = to_bytes![merkle_tree].unwrap();
+        let recovered_merkle_tree =
+            CommitmentMerkleTree::<C, H>::from_bytes(&merkle_tree_bytes[..], crh.clone()).unwrap();
 
-    assert!(merkle_tree == recovered_merkle_tree);
+        assert!(merkle_tree == recovered_merkle_tree);
+    }
 }
 
 #[test]
@@ -122,16 +146,18 @@ fn test_serialize_commitment_path() {
     let commitment = C::setup(rng);
     let crh = H::setup(rng);
 
-    let merkle_tree = generate_merkle_tree(&commitment, &crh, rng);
+    for num_leaves in 1..=8 {
+        let merkle_tree = generate_merkle_tree(&commitment, &crh, num_leaves, rng);
 
-    for leaf in merkle_tree.leaves().iter() {
-        let proof = merkle_tree.generate_proof(&leaf).unwrap();
+        for leaf in merkle_tree.leaves().iter() {
+            let proof = merkle_tree.generate_proof(leaf).unwrap();
 
-        let proof_bytes = to_bytes![proof].unwrap();
-        let recovered_proof = CM::read(&proof_bytes[..]).unwrap();
+            let proof_bytes = to_bytes![proof].unwrap();
+            let recovered_proof = CM::read(&proof_bytes[..]).unwrap();
 
-        assert!(proof == recovered_proof);
+            assert!(proof == recovered_proof);
 
-        assert!(recovered_proof.verify(&crh, &merkle_tree.root(), &leaf).unwrap());
+            assert!(recovered_proof.verify(&crh, &merkle_tree.root(), leaf).unwrap());
+        }
     }
 }
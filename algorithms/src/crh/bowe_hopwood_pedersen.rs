@@ -131,17 +131,15 @@ impl<G: Group, S: PedersenSize> CRH for BoweHopwoodPedersenCRH<G, S> {
 
         assert_eq!(padded_input.len() % BOWE_HOPWOOD_CHUNK_SIZE, 0);
 
-        assert_eq!(
-            self.parameters.bases.len(),
-            S::NUM_WINDOWS,
-            "Incorrect pp of size {:?} for window params {:?}x{:?}x{}",
-            self.parameters.bases.len(),
-            S::WINDOW_SIZE,
-            S::NUM_WINDOWS,
-            BOWE_HOPWOOD_CHUNK_SIZE,
-        );
-        for bases in self.parameters.bases.iter() {
-            assert_eq!(bases.len(), S::WINDOW_SIZE);
+        if self.parameters.bases.len() != S::NUM_WINDOWS
+            || self.parameters.bases.iter().any(|bases| bases.len() != S::WINDOW_SIZE)
+        {
+            return Err(CRHError::IncorrectParameterSize(
+                self.parameters.bases.first().map_or(0, |bases| bases.len()),
+                self.parameters.bases.len(),
+                S::WINDOW_SIZE,
+                S::NUM_WINDOWS,
+            ));
         }
         assert_eq!(BOWE_HOPWOOD_CHUNK_SIZE, 3);
 
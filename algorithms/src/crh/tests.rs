@@ -16,6 +16,7 @@
 
 use crate::{
     crh::{BoweHopwoodPedersenCRH, BoweHopwoodPedersenCompressedCRH, PedersenCRH, PedersenCompressedCRH, PedersenSize},
+    errors::CRHError,
     traits::CRH,
 };
 use snarkvm_curves::edwards_bls12::EdwardsProjective;
@@ -85,3 +86,27 @@ fn simple_bowe_hopwood_crh() {
 
     BoweHopwoodCRH::hash(&parameters, &[1, 2, 3]).unwrap();
 }
+
+#[test]
+fn bowe_hopwood_crh_rejects_parameters_of_other_windows() {
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    struct LargerBoweHopwoodSize;
+
+    impl PedersenSize for LargerBoweHopwoodSize {
+        const NUM_WINDOWS: usize = 16;
+        const WINDOW_SIZE: usize = 63;
+    }
+
+    let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+
+    let parameters = BoweHopwoodPedersenCRH::<EdwardsProjective, BoweHopwoodSize>::setup(rng);
+    let parameters_bytes = to_bytes![parameters.parameters()].unwrap();
+    type LargerBoweHopwoodCRH = BoweHopwoodPedersenCRH<EdwardsProjective, LargerBoweHopwoodSize>;
+    let larger_parameters: <LargerBoweHopwoodCRH as CRH>::Parameters = FromBytes::read(&parameters_bytes[..]).unwrap();
+    let larger_crh = LargerBoweHopwoodCRH::from(larger_parameters);
+
+    assert!(matches!(
+        larger_crh.hash(&[1, 2, 3]),
+        Err(CRHError::IncorrectParameterSize(63, 8, 63, 16))
+    ));
+}
@@ -219,15 +219,19 @@ impl<P: MontgomeryModelParameters + TEModelParameters, G: Group + ProjectiveCurv
             assert!(u.legendre().is_qnr());
 
             // Verify that x != -A.
-            assert_ne!(x, -a);
+            if x == -a {
+                return Err(EncodingError::InvalidGroupElement);
+            }
 
             // Verify that if y is 0, then x is 0.
-            if y.is_zero() {
-                assert!(x.is_zero());
+            if y.is_zero() && !x.is_zero() {
+                return Err(EncodingError::InvalidGroupElement);
             }
 
-            // Verify -ux(x + A) is a residue.
-            assert_eq!((-(u * &x) * &(x + &a)).legendre(), LegendreSymbol::QuadraticResidue);
+            // Verify -ux(x + A) is a residue, otherwise the group element is not an encoding.
+            if (-(u * &x) * &(x + &a)).legendre() != LegendreSymbol::QuadraticResidue {
+                return Err(EncodingError::InvalidGroupElement);
+            }
         }
 
         let exists_in_sqrt_fq2 = v_reconstructed.square().sqrt().unwrap() == v_reconstructed;
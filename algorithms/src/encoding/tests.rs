@@ -15,7 +15,7 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::encoding::Elligator2;
-use snarkvm_curves::edwards_bls12::*;
+use snarkvm_curves::{edwards_bls12::*, traits::ProjectiveCurve};
 use snarkvm_fields::Zero;
 use snarkvm_utilities::rand::UniformRand;
 
@@ -46,3 +46,24 @@ fn test_elligator2_zero() {
     let decode = Elligator2::<EdwardsParameters, EdwardsProjective>::decode(&EdwardsAffine::zero(), false);
     assert!(decode.is_err());
 }
+
+#[test]
+fn test_elligator2_decode_invalid() {
+    let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+
+    // Roughly half of the group elements are not in the image of the encoding.
+    let mut num_invalid = 0;
+    for _ in 0..100 {
+        let group_element = EdwardsProjective::rand(rng).into_affine();
+
+        match Elligator2::<EdwardsParameters, EdwardsProjective>::decode(&group_element, false) {
+            Ok(decoded) => {
+                let (encoded, _) = Elligator2::<EdwardsParameters, EdwardsProjective>::encode(&decoded).unwrap();
+                assert_eq!(group_element, encoded);
+            }
+            Err(_) => num_invalid += 1,
+        }
+    }
+
+    assert!(num_invalid > 0);
+}
@@ -31,6 +31,9 @@ pub enum MerkleError {
     #[error("Invalid leaf")]
     InvalidLeaf,
 
+    #[error("Invalid number of leaves: {}. Must be less than or equal to: {}", _0, _1)]
+    InvalidNumLeaves(usize, usize),
+
     #[error("Invalid path length: {}. Must be less than or equal to: {}", _0, _1)]
     InvalidPathLength(usize, usize),
 
@@ -0,0 +1,178 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    errors::MerkleError,
+    merkle_tree::MerkleTreeDigest,
+    traits::{MerkleParameters, CRH},
+};
+use snarkvm_utilities::{FromBytes, ToBytes};
+
+use std::io::{Read, Result as IoResult, Write};
+
+/// The frontier of an append-only Merkle tree.
+///
+/// For a tree of `num_leaves` leaves, the frontier holds the root of the complete subtree
+/// of height `h` for every bit `h` that is set in `num_leaves`. This suffices to append new leaves
+/// and to compute the root of the tree, in `O(depth)` hashes per leaf and without the full tree.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "P: MerkleParameters"),
+    Debug(bound = "P: MerkleParameters"),
+    Default(bound = "P: MerkleParameters"),
+    PartialEq(bound = "P: MerkleParameters"),
+    Eq(bound = "P: MerkleParameters")
+)]
+pub struct MerkleFrontier<P: MerkleParameters> {
+    /// The number of leaves appended to the tree.
+    num_leaves: usize,
+
+    /// The roots of the complete subtrees on the right-most edge of the tree, indexed by height.
+    nodes: Vec<Option<MerkleTreeDigest<P>>>,
+}
+
+impl<P: MerkleParameters> MerkleFrontier<P> {
+    /// Returns the frontier of a tree with the given number of leaves and complete subtree roots.
+    pub(crate) fn from_nodes(num_leaves: usize, nodes: Vec<Option<MerkleTreeDigest<P>>>) -> Self {
+        Self { num_leaves, nodes }
+    }
+
+    /// Returns the number of leaves in the tree.
+    #[inline]
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    /// Returns the root of the complete subtree of the given height on the right-most edge of the tree, if any.
+    #[inline]
+    pub fn node(&self, height: usize) -> Option<&MerkleTreeDigest<P>> {
+        self.nodes.get(height).and_then(|node| node.as_ref())
+    }
+
+    /// Appends the given leaves to the frontier.
+    pub fn extend<L: ToBytes>(&mut self, parameters: &P, new_leaves: &[L]) -> Result<(), MerkleError> {
+        let num_leaves = self.num_leaves + new_leaves.len();
+        let tree_depth = tree_depth(num_leaves);
+        if tree_depth > P::DEPTH {
+            return Err(MerkleError::InvalidTreeDepth(tree_depth, P::DEPTH));
+        }
+
+        let hash_input_size_in_bytes = (P::H::INPUT_SIZE_BITS / 8) * 2;
+        let mut leaf_buffer = vec![0u8; hash_input_size_in_bytes];
+        let mut buffer = vec![0u8; hash_input_size_in_bytes];
+
+        for leaf in new_leaves {
+            let mut current_hash = parameters.hash_leaf(leaf, &mut leaf_buffer)?;
+
+            // Merge the complete subtrees that the new leaf completes, starting from the bottom.
+            let mut height = 0;
+            while (self.num_leaves >> height) & 1 == 1 {
+                let left = self.nodes[height].take().expect("the frontier node exists");
+                current_hash = parameters.hash_inner_node(&left, &current_hash, &mut buffer)?;
+                height += 1;
+            }
+
+            if self.nodes.len() <= height {
+                self.nodes.resize(height + 1, None);
+            }
+            self.nodes[height] = Some(current_hash);
+            self.num_leaves += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the root of the tree, padded to `P::DEPTH` in the same way as `MerkleTree::root`.
+    pub fn root(&self, parameters: &P) -> Result<MerkleTreeDigest<P>, MerkleError> {
+        let hash_input_size_in_bytes = (P::H::INPUT_SIZE_BITS / 8) * 2;
+        let mut buffer = vec![0u8; hash_input_size_in_bytes];
+
+        let empty_hash = parameters.hash_empty()?;
+        let tree_depth = tree_depth(self.num_leaves);
+
+        // Fold the frontier from the bottom up, where `None` denotes an empty subtree of the current height.
+        let tree_root = match self.node(tree_depth) {
+            // The tree is complete.
+            Some(root) => root.clone(),
+            None => {
+                let mut empty_subtree_hash = empty_hash.clone();
+                let mut current_hash: Option<MerkleTreeDigest<P>> = None;
+                for height in 0..tree_depth {
+                    current_hash = match (self.node(height), current_hash) {
+                        (Some(left), Some(right)) => Some(parameters.hash_inner_node(left, &right, &mut buffer)?),
+                        (Some(left), None) => {
+                            Some(parameters.hash_inner_node(left, &empty_subtree_hash, &mut buffer)?)
+                        }
+                        (None, Some(left)) => {
+                            Some(parameters.hash_inner_node(&left, &empty_subtree_hash, &mut buffer)?)
+                        }
+                        (None, None) => None,
+                    };
+                    empty_subtree_hash =
+                        parameters.hash_inner_node(&empty_subtree_hash, &empty_subtree_hash, &mut buffer)?;
+                }
+                current_hash.unwrap_or(empty_subtree_hash)
+            }
+        };
+
+        // Pad the root until we hit our DEPTH goal.
+        let mut current_hash = tree_root;
+        for _ in tree_depth..P::DEPTH {
+            current_hash = parameters.hash_inner_node(&current_hash, &empty_hash, &mut buffer)?;
+        }
+
+        Ok(current_hash)
+    }
+}
+
+impl<P: MerkleParameters> ToBytes for MerkleFrontier<P> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        (self.num_leaves as u64).write(&mut writer)?;
+
+        // The heights of the nodes are implied by the number of leaves.
+        for node in self.nodes.iter().flatten() {
+            node.write(&mut writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<P: MerkleParameters> FromBytes for MerkleFrontier<P> {
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let num_leaves = u64::read(&mut reader)? as usize;
+
+        let mut nodes = vec![];
+        let mut remaining_leaves = num_leaves;
+        while remaining_leaves > 0 {
+            match remaining_leaves & 1 {
+                1 => nodes.push(Some(MerkleTreeDigest::<P>::read(&mut reader)?)),
+                _ => nodes.push(None),
+            }
+            remaining_leaves >>= 1;
+        }
+
+        Ok(Self { num_leaves, nodes })
+    }
+}
+
+/// Returns the depth of the smallest tree of a power-of-two width that fits the given number of leaves.
+#[inline]
+fn tree_depth(num_leaves: usize) -> usize {
+    num_leaves.next_power_of_two().trailing_zeros() as usize
+}
@@ -16,12 +16,12 @@
 
 use crate::{
     errors::MerkleError,
-    merkle_tree::{MerklePath, MerkleTreeDigest},
+    merkle_tree::{MerkleFrontier, MerklePath, MerkleTreeDigest},
     traits::{MerkleParameters, CRH},
 };
 use snarkvm_utilities::ToBytes;
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct MerkleTree<P: MerkleParameters> {
     /// The computed root of the full Merkle tree.
     root: Option<MerkleTreeDigest<P>>,
@@ -32,6 +32,9 @@ pub struct MerkleTree<P: MerkleParameters> {
     /// The hash of each non-empty leaf in the Merkle tree.
     hashed_leaves: Vec<MerkleTreeDigest<P>>,
 
+    /// The number of leaves inserted into the Merkle tree.
+    num_leaves: usize,
+
     /// For each level after a full tree has been built from the leaves,
     /// keeps both the roots the siblings that are used to get to the desired depth.
     padding_tree: Vec<(MerkleTreeDigest<P>, MerkleTreeDigest<P>)>,
@@ -92,19 +95,7 @@ impl<P: MerkleParameters> MerkleTree<P> {
 
         // Finished computing actual tree.
         // Now, we compute the dummy nodes until we hit our DEPTH goal.
-        let mut current_depth = tree_depth;
-        let mut padding_tree = Vec::with_capacity((Self::DEPTH as usize).saturating_sub(current_depth + 1));
-        let mut current_hash = tree[0].clone();
-        while current_depth < Self::DEPTH as usize {
-            current_hash = parameters.hash_inner_node(&current_hash, &empty_hash, &mut buffer)?;
-
-            // do not pad at the top-level of the tree
-            if current_depth < Self::DEPTH as usize - 1 {
-                padding_tree.push((current_hash.clone(), empty_hash.clone()));
-            }
-            current_depth += 1;
-        }
-        let root_hash = current_hash;
+        let (padding_tree, root_hash) = Self::compute_padding(&parameters, &tree[0], tree_depth, &mut buffer)?;
 
         end_timer!(new_time);
 
@@ -113,11 +104,149 @@ impl<P: MerkleParameters> MerkleTree<P> {
             tree,
             padding_tree,
             hashed_leaves,
+            num_leaves: leaves.len(),
             parameters,
             root: Some(root_hash),
         })
     }
 
+    /// Appends the given leaves to the Merkle tree. Only the nodes on the paths from
+    /// the new leaves to the root are rehashed, and the tree is grown to the next
+    /// power of two (by placing the existing tree in its left-most subtree) when needed.
+    pub fn extend<L: ToBytes>(&mut self, new_leaves: &[L]) -> Result<(), MerkleError> {
+        if new_leaves.is_empty() {
+            return Ok(());
+        }
+
+        let extend_time = start_timer!(|| "MerkleTree::extend");
+
+        let old_tree_depth = tree_depth(self.tree.len());
+        let start_index = self.num_leaves;
+        let num_leaves = start_index + new_leaves.len();
+
+        let last_level_size = num_leaves.next_power_of_two();
+        let tree_size = 2 * last_level_size - 1;
+        let tree_depth = tree_depth(tree_size);
+
+        if tree_depth > Self::DEPTH as usize {
+            return Err(MerkleError::InvalidTreeDepth(tree_depth, Self::DEPTH as usize));
+        }
+
+        let hash_input_size_in_bytes = (P::H::INPUT_SIZE_BITS / 8) * 2;
+        let mut buffer = vec![0u8; hash_input_size_in_bytes];
+        let empty_hash = self.parameters.hash_empty()?;
+
+        // Grow the tree, if the new leaves do not fit in its last level.
+        if tree_depth > old_tree_depth {
+            // Compute the hash of an empty subtree for each height.
+            let mut empty_hashes = Vec::with_capacity(tree_depth + 1);
+            empty_hashes.push(empty_hash.clone());
+            for height in 0..tree_depth {
+                let empty_subtree_hash =
+                    self.parameters
+                        .hash_inner_node(&empty_hashes[height], &empty_hashes[height], &mut buffer)?;
+                empty_hashes.push(empty_subtree_hash);
+            }
+
+            // The old tree becomes the left-most subtree of the new tree.
+            let depth_difference = tree_depth - old_tree_depth;
+            let mut tree = Vec::with_capacity(tree_size);
+            for level in 0..=tree_depth {
+                let level_size = 1 << level;
+                let empty_subtree_hash = &empty_hashes[tree_depth - level];
+
+                let old_level_size = match level >= depth_difference {
+                    true => {
+                        let old_level_size = 1 << (level - depth_difference);
+                        let old_start_index = old_level_size - 1;
+                        tree.extend_from_slice(&self.tree[old_start_index..old_start_index + old_level_size]);
+                        old_level_size
+                    }
+                    false => 0,
+                };
+                tree.resize(tree.len() + level_size - old_level_size, empty_subtree_hash.clone());
+            }
+
+            self.tree = tree;
+        }
+
+        // Compute and store the hash values for each new leaf.
+        let last_level_index = last_level_size - 1;
+        let mut leaf_buffer = vec![0u8; hash_input_size_in_bytes];
+        for (i, leaf) in new_leaves.iter().enumerate() {
+            self.tree[last_level_index + start_index + i] = self.parameters.hash_leaf(leaf, &mut leaf_buffer)?;
+        }
+
+        // Recompute the hash values for the ancestors of the new leaves, level by level.
+        let mut start = last_level_index + start_index;
+        let mut end = last_level_index + num_leaves - 1;
+        while !is_root(start) {
+            start = parent(start).unwrap();
+            end = parent(end).unwrap();
+
+            for current_index in start..=end {
+                let left_index = left_child(current_index);
+                let right_index = right_child(current_index);
+
+                // Compute Hash(left || right).
+                self.tree[current_index] =
+                    self.parameters
+                        .hash_inner_node(&self.tree[left_index], &self.tree[right_index], &mut buffer)?;
+            }
+        }
+
+        // Recompute the dummy nodes until we hit our DEPTH goal.
+        let (padding_tree, root_hash) =
+            Self::compute_padding(&self.parameters, &self.tree[0], tree_depth, &mut buffer)?;
+
+        self.hashed_leaves.resize(last_level_size, empty_hash);
+        self.hashed_leaves[start_index..num_leaves]
+            .clone_from_slice(&self.tree[last_level_index + start_index..last_level_index + num_leaves]);
+        self.padding_tree = padding_tree;
+        self.num_leaves = num_leaves;
+        self.root = Some(root_hash);
+
+        end_timer!(extend_time);
+
+        Ok(())
+    }
+
+    /// Returns the frontier of the Merkle tree, which is sufficient to append
+    /// new leaves and compute the resulting root without the full tree.
+    pub fn frontier(&self) -> MerkleFrontier<P> {
+        self.frontier_at(self.num_leaves)
+            .expect("the frontier of the Merkle tree exists")
+    }
+
+    /// Returns the frontier of the historical state of the Merkle tree,
+    /// when it consisted of only its first `num_leaves` leaves.
+    pub fn frontier_at(&self, num_leaves: usize) -> Result<MerkleFrontier<P>, MerkleError> {
+        if num_leaves > self.num_leaves {
+            return Err(MerkleError::InvalidNumLeaves(num_leaves, self.num_leaves));
+        }
+
+        let tree_depth = tree_depth(self.tree.len());
+
+        let mut nodes = vec![];
+        let mut remaining_leaves = num_leaves;
+        let mut height = 0;
+        while remaining_leaves > 0 {
+            match remaining_leaves & 1 {
+                1 => {
+                    // The complete subtree left of the remaining leaves at this height.
+                    let level = tree_depth - height;
+                    let tree_index = (1 << level) - 1 + (num_leaves >> height) - 1;
+                    nodes.push(Some(self.tree[tree_index].clone()));
+                }
+                _ => nodes.push(None),
+            }
+            remaining_leaves >>= 1;
+            height += 1;
+        }
+
+        Ok(MerkleFrontier::from_nodes(num_leaves, nodes))
+    }
+
     #[inline]
     pub fn root(&self) -> <P::H as CRH>::Output {
         self.root.clone().unwrap()
@@ -128,6 +257,11 @@ impl<P: MerkleParameters> MerkleTree<P> {
         self.hashed_leaves.clone()
     }
 
+    #[inline]
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
     pub fn generate_proof<L: ToBytes>(&self, index: usize, leaf: &L) -> Result<MerklePath<P>, MerkleError> {
         let prove_time = start_timer!(|| "MerkleTree::generate_proof");
         let mut path = vec![];
@@ -182,6 +316,138 @@ impl<P: MerkleParameters> MerkleTree<P> {
             })
         }
     }
+
+    /// Returns the Merkle path for the given leaf in the historical state of the Merkle tree,
+    /// when it consisted of only its first `num_leaves` leaves. The path is valid for the root
+    /// that the Merkle tree had at the time, and is derived without rebuilding that tree.
+    pub fn generate_proof_at<L: ToBytes>(
+        &self,
+        num_leaves: usize,
+        index: usize,
+        leaf: &L,
+    ) -> Result<MerklePath<P>, MerkleError> {
+        let prove_time = start_timer!(|| "MerkleTree::generate_proof_at");
+
+        if num_leaves > self.num_leaves {
+            return Err(MerkleError::InvalidNumLeaves(num_leaves, self.num_leaves));
+        }
+        if index >= num_leaves {
+            return Err(MerkleError::IncorrectLeafIndex(index));
+        }
+
+        let hash_input_size_in_bytes = (P::H::INPUT_SIZE_BITS / 8) * 2;
+        let mut buffer = vec![0u8; hash_input_size_in_bytes];
+
+        let leaf_hash = self.parameters.hash_leaf(leaf, &mut buffer)?;
+
+        let tree_depth = tree_depth(self.tree.len());
+        let tree_index = convert_index_to_last_level(index, tree_depth);
+
+        // Check that the given index corresponds to the correct leaf.
+        if leaf_hash != self.tree[tree_index] {
+            return Err(MerkleError::IncorrectLeafIndex(tree_index));
+        }
+
+        // Compute the hash of an empty subtree for each height of the historical tree.
+        let historical_tree_depth = num_leaves.next_power_of_two().trailing_zeros() as usize;
+        let mut empty_hashes = Vec::with_capacity(historical_tree_depth + 1);
+        empty_hashes.push(self.parameters.hash_empty()?);
+        for height in 0..historical_tree_depth {
+            let empty_subtree_hash =
+                self.parameters
+                    .hash_inner_node(&empty_hashes[height], &empty_hashes[height], &mut buffer)?;
+            empty_hashes.push(empty_subtree_hash);
+        }
+
+        // Iterate from the leaf up to the root of the historical tree, storing all intermediate hash values.
+        let mut path = Vec::with_capacity(Self::DEPTH as usize);
+        for height in 0..historical_tree_depth {
+            let offset = index >> height;
+            let hash = self.historical_node(num_leaves, height, offset, &empty_hashes, &mut buffer)?;
+            let sibling_hash = self.historical_node(num_leaves, height, offset ^ 1, &empty_hashes, &mut buffer)?;
+            if offset % 2 == 0 {
+                path.push((hash, sibling_hash));
+            } else {
+                path.push((sibling_hash, hash));
+            }
+        }
+
+        // Store the root node and the padding of the historical tree.
+        if path.len() != Self::DEPTH as usize {
+            let historical_root =
+                self.historical_node(num_leaves, historical_tree_depth, 0, &empty_hashes, &mut buffer)?;
+            let (padding_tree, _) =
+                Self::compute_padding(&self.parameters, &historical_root, historical_tree_depth, &mut buffer)?;
+
+            path.push((historical_root, empty_hashes[0].clone()));
+            path.extend(padding_tree);
+        }
+        end_timer!(prove_time);
+
+        if path.len() != Self::DEPTH as usize {
+            Err(MerkleError::IncorrectPathLength(path.len()))
+        } else {
+            Ok(MerklePath {
+                parameters: self.parameters.clone(),
+                path,
+            })
+        }
+    }
+
+    /// Returns the node at the given height and offset of the historical state of the Merkle tree,
+    /// when it consisted of only its first `num_leaves` leaves. Nodes of complete subtrees are read
+    /// from the Merkle tree, and only the partial subtree on the right-most edge is rehashed.
+    fn historical_node(
+        &self,
+        num_leaves: usize,
+        height: usize,
+        offset: usize,
+        empty_hashes: &[MerkleTreeDigest<P>],
+        buffer: &mut [u8],
+    ) -> Result<MerkleTreeDigest<P>, MerkleError> {
+        let first_leaf = offset << height;
+        let last_leaf = (offset + 1) << height;
+
+        if last_leaf <= num_leaves {
+            // The subtree is complete, and thus unchanged since.
+            let level = tree_depth(self.tree.len()) - height;
+            Ok(self.tree[(1 << level) - 1 + offset].clone())
+        } else if first_leaf >= num_leaves {
+            // The subtree is empty.
+            Ok(empty_hashes[height].clone())
+        } else {
+            let left = self.historical_node(num_leaves, height - 1, 2 * offset, empty_hashes, buffer)?;
+            let right = self.historical_node(num_leaves, height - 1, 2 * offset + 1, empty_hashes, buffer)?;
+            Ok(self.parameters.hash_inner_node(&left, &right, buffer)?)
+        }
+    }
+
+    /// Returns the padding tree and the root, by hashing the given root of
+    /// the full tree with empty siblings until we hit our DEPTH goal.
+    #[allow(clippy::type_complexity)]
+    fn compute_padding(
+        parameters: &P,
+        tree_root: &MerkleTreeDigest<P>,
+        tree_depth: usize,
+        buffer: &mut [u8],
+    ) -> Result<(Vec<(MerkleTreeDigest<P>, MerkleTreeDigest<P>)>, MerkleTreeDigest<P>), MerkleError> {
+        let empty_hash = parameters.hash_empty()?;
+
+        let mut current_depth = tree_depth;
+        let mut padding_tree = Vec::with_capacity((Self::DEPTH as usize).saturating_sub(current_depth + 1));
+        let mut current_hash = tree_root.clone();
+        while current_depth < Self::DEPTH as usize {
+            current_hash = parameters.hash_inner_node(&current_hash, &empty_hash, buffer)?;
+
+            // do not pad at the top-level of the tree
+            if current_depth < Self::DEPTH as usize - 1 {
+                padding_tree.push((current_hash.clone(), empty_hash.clone()));
+            }
+            current_depth += 1;
+        }
+
+        Ok((padding_tree, current_hash))
+    }
 }
 
 /// Returns the depth of the tree, given the size of the tree.
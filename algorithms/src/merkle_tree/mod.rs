@@ -16,6 +16,9 @@
 
 #![allow(clippy::module_inception)]
 
+pub mod merkle_frontier;
+pub use merkle_frontier::*;
+
 pub mod merkle_path;
 pub use merkle_path::*;
 
@@ -17,10 +17,10 @@
 use crate::{
     crh::{PedersenCRH, PedersenCompressedCRH, PedersenSize},
     define_merkle_tree_parameters,
-    merkle_tree::MerkleTree,
+    merkle_tree::{MerkleFrontier, MerkleTree},
     traits::{crh::CRH, merkle_tree::LoadableMerkleParameters},
 };
-use snarkvm_utilities::{to_bytes, ToBytes};
+use snarkvm_utilities::{to_bytes, FromBytes, ToBytes};
 
 /// Generates a valid Merkle tree and verifies the Merkle path witness for each leaf.
 fn generate_merkle_tree<P: LoadableMerkleParameters, L: ToBytes + Clone + Eq>(
@@ -164,6 +164,99 @@ fn run_padded_merkle_tree_matches_hashing_test<P: LoadableMerkleParameters>() {
     assert_eq!(merkle_tree_root, expected_root);
 }
 
+fn run_extend_merkle_tree_test<P: LoadableMerkleParameters>() {
+    let parameters = &P::default();
+
+    let mut leaves = vec![];
+    for i in 0..17u8 {
+        leaves.push([i, i, i, i, i, i, i, i]);
+    }
+
+    // Extend the tree in batches of varying sizes, growing its depth along the way.
+    let mut tree = MerkleTree::<P>::new(parameters.clone(), &leaves[..1]).unwrap();
+    let mut num_leaves = 1;
+    for batch_size in &[1, 2, 3, 5, 5] {
+        let new_leaves = &leaves[num_leaves..num_leaves + batch_size];
+        tree.extend(new_leaves).unwrap();
+        num_leaves += batch_size;
+
+        let expected_tree = generate_merkle_tree::<P, _>(&leaves[..num_leaves], parameters);
+        assert_eq!(expected_tree.root(), tree.root());
+        assert_eq!(expected_tree.hashed_leaves(), tree.hashed_leaves());
+        assert_eq!(num_leaves, tree.num_leaves());
+
+        for (i, leaf) in leaves[..num_leaves].iter().enumerate() {
+            let proof = tree.generate_proof(i, &leaf).unwrap();
+            assert_eq!(P::DEPTH, proof.path.len());
+            assert!(proof.verify(&tree.root(), &leaf).unwrap());
+        }
+    }
+
+    // Extending an empty tree matches building it from scratch.
+    let mut tree = MerkleTree::<P>::new(parameters.clone(), &Vec::<[u8; 8]>::new()).unwrap();
+    tree.extend(&leaves).unwrap();
+    assert_eq!(generate_merkle_tree::<P, _>(&leaves, parameters).root(), tree.root());
+}
+
+fn run_merkle_frontier_test<P: LoadableMerkleParameters>() {
+    let parameters = &P::default();
+
+    let mut leaves = vec![];
+    for i in 0..17u8 {
+        leaves.push([i, i, i, i, i, i, i, i]);
+    }
+
+    let mut frontier = MerkleFrontier::<P>::default();
+    assert_eq!(
+        generate_merkle_tree::<P, [u8; 8]>(&[], parameters).root(),
+        frontier.root(parameters).unwrap()
+    );
+
+    for num_leaves in 1..=leaves.len() {
+        frontier
+            .extend(parameters, &leaves[num_leaves - 1..num_leaves])
+            .unwrap();
+
+        let tree = generate_merkle_tree::<P, _>(&leaves[..num_leaves], parameters);
+        assert_eq!(tree.frontier(), frontier);
+        assert_eq!(tree.root(), frontier.root(parameters).unwrap());
+
+        let frontier_bytes = to_bytes![frontier].unwrap();
+        assert_eq!(frontier, MerkleFrontier::<P>::read(&frontier_bytes[..]).unwrap());
+    }
+}
+
+fn run_historical_merkle_path_test<P: LoadableMerkleParameters>() {
+    let parameters = &P::default();
+
+    let mut leaves = vec![];
+    for i in 0..9u8 {
+        leaves.push([i, i, i, i, i, i, i, i]);
+    }
+    let tree = MerkleTree::<P>::new(parameters.clone(), &leaves).unwrap();
+
+    for num_leaves in 1..=leaves.len() {
+        let historical_tree = MerkleTree::<P>::new(parameters.clone(), &leaves[..num_leaves]).unwrap();
+
+        assert_eq!(historical_tree.frontier(), tree.frontier_at(num_leaves).unwrap());
+
+        for (i, leaf) in leaves[..num_leaves].iter().enumerate() {
+            let proof = tree.generate_proof_at(num_leaves, i, &leaf).unwrap();
+            assert_eq!(historical_tree.generate_proof(i, &leaf).unwrap().path, proof.path);
+            assert!(proof.verify(&historical_tree.root(), &leaf).unwrap());
+        }
+
+        // The leaves appended later are not part of the historical tree.
+        if num_leaves < leaves.len() {
+            assert!(tree
+                .generate_proof_at(num_leaves, num_leaves, &leaves[num_leaves])
+                .is_err());
+        }
+    }
+
+    assert!(tree.generate_proof_at(leaves.len() + 1, 0, &leaves[0]).is_err());
+}
+
 mod pedersen_crh_on_affine {
     use super::*;
     use snarkvm_curves::edwards_bls12::EdwardsAffine as Edwards;
@@ -194,6 +287,24 @@ mod pedersen_crh_on_affine {
         run_bad_root_test::<MTParameters>();
     }
 
+    #[test]
+    fn extend_merkle_tree_test() {
+        define_merkle_tree_parameters!(MTParameters, PedersenCRH<Edwards, Size>, 32);
+        run_extend_merkle_tree_test::<MTParameters>();
+    }
+
+    #[test]
+    fn merkle_frontier_test() {
+        define_merkle_tree_parameters!(MTParameters, PedersenCRH<Edwards, Size>, 32);
+        run_merkle_frontier_test::<MTParameters>();
+    }
+
+    #[test]
+    fn historical_merkle_path_test() {
+        define_merkle_tree_parameters!(MTParameters, PedersenCRH<Edwards, Size>, 32);
+        run_historical_merkle_path_test::<MTParameters>();
+    }
+
     #[test]
     fn depth2_merkle_tree_matches_hashing_test() {
         define_merkle_tree_parameters!(MTParameters, PedersenCRH<Edwards, Size>, 2);
@@ -237,6 +348,24 @@ mod pedersen_crh_on_projective {
         run_bad_root_test::<MTParameters>();
     }
 
+    #[test]
+    fn extend_merkle_tree_test() {
+        define_merkle_tree_parameters!(MTParameters, PedersenCRH<Edwards, Size>, 32);
+        run_extend_merkle_tree_test::<MTParameters>();
+    }
+
+    #[test]
+    fn merkle_frontier_test() {
+        define_merkle_tree_parameters!(MTParameters, PedersenCRH<Edwards, Size>, 32);
+        run_merkle_frontier_test::<MTParameters>();
+    }
+
+    #[test]
+    fn historical_merkle_path_test() {
+        define_merkle_tree_parameters!(MTParameters, PedersenCRH<Edwards, Size>, 32);
+        run_historical_merkle_path_test::<MTParameters>();
+    }
+
     // TODO (howardwu): Debug why PedersenCRH fails and make this test pass.
     #[ignore]
     #[test]
@@ -284,6 +413,24 @@ mod pedersen_compressed_crh_on_projective {
         run_bad_root_test::<MTParameters>();
     }
 
+    #[test]
+    fn extend_merkle_tree_test() {
+        define_merkle_tree_parameters!(MTParameters, PedersenCompressedCRH<Edwards, Size>, 32);
+        run_extend_merkle_tree_test::<MTParameters>();
+    }
+
+    #[test]
+    fn merkle_frontier_test() {
+        define_merkle_tree_parameters!(MTParameters, PedersenCompressedCRH<Edwards, Size>, 32);
+        run_merkle_frontier_test::<MTParameters>();
+    }
+
+    #[test]
+    fn historical_merkle_path_test() {
+        define_merkle_tree_parameters!(MTParameters, PedersenCompressedCRH<Edwards, Size>, 32);
+        run_historical_merkle_path_test::<MTParameters>();
+    }
+
     #[test]
     fn depth2_merkle_tree_matches_hashing_test() {
         define_merkle_tree_parameters!(MTParameters, PedersenCompressedCRH<Edwards, Size>, 2);
@@ -19,6 +19,7 @@ use super::{
     generate_random_parameters,
     prepare_verifying_key,
     verify_proof,
+    verify_proofs_batch,
     Parameters,
     PreparedVerifyingKey,
     Proof,
@@ -89,4 +90,26 @@ impl<E: PairingEngine, C: ConstraintSynthesizer<E::Fr>, V: ToConstraintField<E::
         end_timer!(verify_time);
         Ok(result)
     }
+
+    fn verify_batch<R: Rng>(
+        vk: &Self::PreparedVerificationParameters,
+        inputs_and_proofs: &[(&Self::VerifierInput, &Self::Proof)],
+        rng: &mut R,
+    ) -> Result<bool, SNARKError> {
+        let verify_time = start_timer!(|| format!("{{Groth 2016}}::VerifyBatch ({} proofs)", inputs_and_proofs.len()));
+        let conversion_time = start_timer!(|| "Convert inputs to E::Fr");
+        let inputs = inputs_and_proofs
+            .iter()
+            .map(|(input, _)| input.to_field_elements())
+            .collect::<Result<Vec<_>, _>>()?;
+        end_timer!(conversion_time);
+        let proofs_and_inputs = inputs_and_proofs
+            .iter()
+            .zip(inputs.iter())
+            .map(|((_, proof), input)| (*proof, &input[..]))
+            .collect::<Vec<_>>();
+        let result = verify_proofs_batch(vk, &proofs_and_inputs, rng)?;
+        end_timer!(verify_time);
+        Ok(result)
+    }
 }
@@ -50,7 +50,13 @@ impl<ConstraintF: Field> ConstraintSynthesizer<ConstraintF> for MySillyCircuit<C
 
 mod bls12_377 {
     use super::*;
-    use crate::snark::groth16::{create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof};
+    use crate::snark::groth16::{
+        create_random_proof,
+        generate_random_parameters,
+        prepare_verifying_key,
+        verify_proof,
+        verify_proofs_batch,
+    };
     use core::ops::MulAssign;
     use snarkvm_curves::bls12_377::{Bls12_377, Fr};
     use snarkvm_utilities::rand::{test_rng, UniformRand};
@@ -75,6 +81,54 @@ mod bls12_377 {
             assert!(!verify_proof(&pvk, &proof, &[a]).unwrap());
         }
     }
+
+    #[test]
+    fn prove_and_verify_batch() {
+        let rng = &mut test_rng();
+
+        let parameters =
+            generate_random_parameters::<Bls12_377, _, _>(&MySillyCircuit { a: None, b: None }, rng).unwrap();
+        let pvk = prepare_verifying_key::<Bls12_377>(parameters.vk.clone());
+
+        let mut proofs = vec![];
+        let mut inputs = vec![];
+        for _ in 0..10 {
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+            let mut c = a;
+            c.mul_assign(&b);
+
+            proofs.push(create_random_proof(&MySillyCircuit { a: Some(a), b: Some(b) }, &parameters, rng).unwrap());
+            inputs.push(vec![c]);
+        }
+
+        let batch = proofs
+            .iter()
+            .zip(inputs.iter())
+            .map(|(proof, input)| (proof, &input[..]))
+            .collect::<Vec<_>>();
+
+        assert!(verify_proofs_batch(&pvk, &[], rng).unwrap());
+        assert!(verify_proofs_batch(&pvk, &batch[..1], rng).unwrap());
+        assert!(verify_proofs_batch(&pvk, &batch, rng).unwrap());
+
+        // A single invalid input invalidates the whole batch.
+        let wrong_input = vec![Fr::rand(rng)];
+        let mut invalid_batch = batch.clone();
+        invalid_batch[7].1 = &wrong_input[..];
+        assert!(!verify_proofs_batch(&pvk, &invalid_batch, rng).unwrap());
+
+        // Swapping the inputs of two proofs invalidates the batch, even though the multiset is unchanged.
+        let mut swapped_batch = batch.clone();
+        swapped_batch[2].1 = batch[3].1;
+        swapped_batch[3].1 = batch[2].1;
+        assert!(!verify_proofs_batch(&pvk, &swapped_batch, rng).unwrap());
+
+        // Malformed inputs are still reported as errors.
+        let mut malformed_batch = batch.clone();
+        malformed_batch[0].1 = &[];
+        assert!(verify_proofs_batch(&pvk, &malformed_batch, rng).is_err());
+    }
 }
 
 mod bw6_761 {
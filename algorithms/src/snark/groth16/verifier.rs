@@ -15,11 +15,13 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 use super::{PreparedVerifyingKey, Proof, VerifyingKey};
-use snarkvm_curves::traits::{AffineCurve, PairingCurve, PairingEngine, ProjectiveCurve};
-use snarkvm_fields::PrimeField;
+use snarkvm_curves::traits::{AffineCurve, Group, PairingCurve, PairingEngine, ProjectiveCurve};
+use snarkvm_fields::{Field, PrimeField, Zero};
 use snarkvm_r1cs::errors::SynthesisError;
+use snarkvm_utilities::rand::UniformRand;
 
 use core::ops::{AddAssign, Neg};
+use rand::Rng;
 
 pub fn prepare_verifying_key<E: PairingEngine>(vk: VerifyingKey<E>) -> PreparedVerifyingKey<E> {
     let alpha_g1_beta_g2 = E::pairing(vk.alpha_g1, vk.beta_g2);
@@ -34,11 +36,11 @@ pub fn prepare_verifying_key<E: PairingEngine>(vk: VerifyingKey<E>) -> PreparedV
     }
 }
 
-pub fn verify_proof<E: PairingEngine>(
+/// Computes the public input commitment `gamma_abc_g1[0] + sum_i public_inputs[i] * gamma_abc_g1[i + 1]`.
+fn compute_input_commitment<E: PairingEngine>(
     pvk: &PreparedVerifyingKey<E>,
-    proof: &Proof<E>,
     public_inputs: &[E::Fr],
-) -> Result<bool, SynthesisError> {
+) -> Result<E::G1Projective, SynthesisError> {
     if (public_inputs.len() + 1) != pvk.gamma_abc_g1().len() {
         return Err(SynthesisError::MalformedVerifyingKey);
     }
@@ -48,6 +50,16 @@ pub fn verify_proof<E: PairingEngine>(
         g_ic.add_assign(&b.mul(i.into_repr()));
     }
 
+    Ok(g_ic)
+}
+
+pub fn verify_proof<E: PairingEngine>(
+    pvk: &PreparedVerifyingKey<E>,
+    proof: &Proof<E>,
+    public_inputs: &[E::Fr],
+) -> Result<bool, SynthesisError> {
+    let g_ic = compute_input_commitment(pvk, public_inputs)?;
+
     let qap = E::miller_loop(
         [
             (&proof.a.prepare(), &proof.b.prepare()),
@@ -62,3 +74,58 @@ pub fn verify_proof<E: PairingEngine>(
 
     Ok(test == pvk.alpha_g1_beta_g2)
 }
+
+/// Verifies a batch of proofs against the same verifying key using a random linear combination.
+///
+/// Each proof is scaled by an independent random challenge `r_i`, so that all the proofs
+/// share a single multi-Miller loop and a single final exponentiation:
+///
+/// `prod_i e(r_i * A_i, B_i) * e(sum_i r_i * IC_i, -gamma) * e(sum_i r_i * C_i, -delta) == e(alpha, beta)^(sum_i r_i)`
+///
+/// The check succeeds if and only if every proof in the batch is valid, except with negligible
+/// probability over the choice of challenges. It does not identify which proof is invalid.
+pub fn verify_proofs_batch<E: PairingEngine, R: Rng>(
+    pvk: &PreparedVerifyingKey<E>,
+    proofs_and_inputs: &[(&Proof<E>, &[E::Fr])],
+    rng: &mut R,
+) -> Result<bool, SynthesisError> {
+    if proofs_and_inputs.is_empty() {
+        return Ok(true);
+    }
+
+    let mut a_prepared = Vec::with_capacity(proofs_and_inputs.len());
+    let mut b_prepared = Vec::with_capacity(proofs_and_inputs.len());
+    let mut combined_g_ic = E::G1Projective::zero();
+    let mut combined_c = E::G1Projective::zero();
+    let mut challenge_sum = E::Fr::zero();
+
+    for (proof, public_inputs) in proofs_and_inputs {
+        let challenge = E::Fr::rand(rng);
+
+        let g_ic = compute_input_commitment(pvk, public_inputs)?;
+        combined_g_ic.add_assign(&g_ic.mul(&challenge));
+        combined_c.add_assign(&proof.c.mul(challenge.into_repr()));
+        challenge_sum.add_assign(&challenge);
+
+        a_prepared.push(proof.a.mul(challenge.into_repr()).into_affine().prepare());
+        b_prepared.push(proof.b.prepare());
+    }
+
+    let combined_g_ic = combined_g_ic.into_affine().prepare();
+    let combined_c = combined_c.into_affine().prepare();
+
+    let qap = E::miller_loop(
+        a_prepared.iter().zip(b_prepared.iter()).chain(
+            [
+                (&combined_g_ic, &pvk.gamma_g2_neg_pc),
+                (&combined_c, &pvk.delta_g2_neg_pc),
+            ]
+            .iter()
+            .copied(),
+        ),
+    );
+
+    let test = E::final_exponentiation(&qap).ok_or(SynthesisError::UnexpectedIdentity)?;
+
+    Ok(test == pvk.alpha_g1_beta_g2.pow(challenge_sum.into_repr()))
+}
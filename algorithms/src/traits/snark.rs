@@ -49,4 +49,21 @@ pub trait SNARK {
         input: &Self::VerifierInput,
         proof: &Self::Proof,
     ) -> Result<bool, SNARKError>;
+
+    /// Verifies a batch of proofs against the same verification parameters.
+    ///
+    /// Returns `true` if and only if every proof in the batch is valid. SNARKs that support
+    /// randomized batch verification override this; the default verifies each proof in turn.
+    fn verify_batch<R: Rng>(
+        verifier_key: &Self::PreparedVerificationParameters,
+        inputs_and_proofs: &[(&Self::VerifierInput, &Self::Proof)],
+        _rng: &mut R,
+    ) -> Result<bool, SNARKError> {
+        for (input, proof) in inputs_and_proofs {
+            if !Self::verify(verifier_key, input, proof)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
 }
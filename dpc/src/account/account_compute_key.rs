@@ -0,0 +1,139 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{traits::DPCComponents, AccountError, AccountPrivateKey};
+use snarkvm_algorithms::traits::{CommitmentScheme, EncryptionScheme, SignatureScheme, PRF};
+use snarkvm_utilities::{bytes_to_bits, to_bytes, FromBytes, ToBytes};
+
+use std::io::{Read, Result as IoResult, Write};
+
+/// The parts of an account private key that are needed to prove a transaction.
+///
+/// An account compute key derives the account view key and the serial numbers of the account records,
+/// but it can't sign transactions, so it can be given to an untrusted prover.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "C: DPCComponents"),
+    Debug(bound = "C: DPCComponents"),
+    Default(bound = "C: DPCComponents"),
+    PartialEq(bound = "C: DPCComponents"),
+    Eq(bound = "C: DPCComponents")
+)]
+pub struct AccountComputeKey<C: DPCComponents> {
+    pub pk_sig: <C::AccountSignature as SignatureScheme>::PublicKey,
+    pub sk_prf: <C::PRF as PRF>::Seed,
+    pub r_pk: <C::AccountCommitment as CommitmentScheme>::Randomness,
+    // This dummy flag is set to true for use in the `inner_snark` setup.
+    #[derivative(Default(value = "true"))]
+    pub is_dummy: bool,
+}
+
+impl<C: DPCComponents> AccountComputeKey<C> {
+    /// Derives the account compute key from an account private key.
+    pub fn from_private_key(
+        signature_parameters: &C::AccountSignature,
+        private_key: &AccountPrivateKey<C>,
+    ) -> Result<Self, AccountError> {
+        Ok(Self {
+            pk_sig: private_key.pk_sig(signature_parameters)?,
+            sk_prf: private_key.sk_prf.clone(),
+            r_pk: private_key.r_pk.clone(),
+            is_dummy: private_key.is_dummy,
+        })
+    }
+
+    /// Returns the decryption key for the account view key.
+    pub fn to_decryption_key(
+        &self,
+        commitment_parameters: &C::AccountCommitment,
+    ) -> Result<<C::AccountEncryption as EncryptionScheme>::PrivateKey, AccountError> {
+        let commitment = self.commit(commitment_parameters)?;
+        let decryption_key_bytes = to_bytes![commitment]?;
+
+        // This operation implicitly enforces that the unused MSB bits
+        // for the scalar field representation are correctly set to 0.
+        let decryption_key = match self.is_dummy {
+            true => <C::AccountEncryption as EncryptionScheme>::PrivateKey::default(),
+            false => <C::AccountEncryption as EncryptionScheme>::PrivateKey::read(&decryption_key_bytes[..])?,
+        };
+
+        // This operation explicitly enforces that the unused MSB bits
+        // for the scalar field representation are correctly set to 0.
+        //
+        // To simplify verification of this isomorphism from the base field
+        // to the scalar field in the `inner_snark`, we additionally enforce
+        // that the MSB bit of the scalar field is also set to 0.
+        if !self.is_dummy {
+            let account_decryption_key_bits = bytes_to_bits(&decryption_key_bytes[..]).collect::<Vec<_>>();
+            let account_decryption_key_length = account_decryption_key_bits.len();
+
+            let decryption_private_key_length = C::AccountEncryption::private_key_size_in_bits();
+            assert!(decryption_private_key_length > 0);
+            assert!(decryption_private_key_length <= account_decryption_key_length);
+
+            for i in (decryption_private_key_length - 1)..account_decryption_key_length {
+                let bit_index = account_decryption_key_length - i - 1;
+                if account_decryption_key_bits[bit_index] {
+                    return Err(AccountError::InvalidAccountCommitment);
+                }
+            }
+        }
+
+        Ok(decryption_key)
+    }
+
+    /// Returns the commitment output of the compute key.
+    fn commit(
+        &self,
+        commitment_parameters: &C::AccountCommitment,
+    ) -> Result<<C::AccountCommitment as CommitmentScheme>::Output, AccountError> {
+        // Construct the commitment input for the account address.
+        let commit_input = to_bytes![self.pk_sig, self.sk_prf]?;
+
+        Ok(C::AccountCommitment::commit(
+            commitment_parameters,
+            &commit_input,
+            &self.r_pk,
+        )?)
+    }
+}
+
+impl<C: DPCComponents> ToBytes for AccountComputeKey<C> {
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.pk_sig.write(&mut writer)?;
+        self.sk_prf.write(&mut writer)?;
+        self.r_pk.write(&mut writer)?;
+        self.is_dummy.write(&mut writer)
+    }
+}
+
+impl<C: DPCComponents> FromBytes for AccountComputeKey<C> {
+    /// Reads in an account compute key buffer.
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let pk_sig: <C::AccountSignature as SignatureScheme>::PublicKey = FromBytes::read(&mut reader)?;
+        let sk_prf: <C::PRF as PRF>::Seed = FromBytes::read(&mut reader)?;
+        let r_pk: <C::AccountCommitment as CommitmentScheme>::Randomness = FromBytes::read(&mut reader)?;
+        let is_dummy: bool = FromBytes::read(&mut reader)?;
+
+        Ok(Self {
+            pk_sig,
+            sk_prf,
+            r_pk,
+            is_dummy,
+        })
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{traits::DPCComponents, AccountAddress, AccountError, AccountPrivateKey, AccountViewKey};
+
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha512;
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// The offset of the hardened child indices.
+pub const HARDENED_OFFSET: u32 = 1 << 31;
+
+/// An account private key in a BIP32-style derivation tree.
+///
+/// Account keys are derived from a 32-byte seed rather than a curve point, so only hardened child
+/// derivation is supported. A child seed is derived from its parent seed and chain code as
+/// `HMAC-SHA512(chain_code, 0x00 || seed || index)`, where the left half is the child seed and the
+/// right half is the child chain code.
+///
+/// As in SLIP-0010, a seed which does not derive a valid account private key is never returned.
+/// Instead, the derivation is repeated with `HMAC-SHA512(chain_code, 0x01 || right || index)` for a child,
+/// or with `HMAC-SHA512("Aleo seed", left || right)` for the master key, until the seed is valid.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "C: DPCComponents"),
+    PartialEq(bound = "C: DPCComponents"),
+    Eq(bound = "C: DPCComponents")
+)]
+pub struct AccountExtendedPrivateKey<C: DPCComponents> {
+    /// The number of derivations from the master key.
+    pub depth: u8,
+    /// The index of this key in its parent, including the hardened offset. Zero for the master key.
+    pub child_index: u32,
+    /// The seed of the account private key.
+    pub seed: [u8; 32],
+    /// The chain code used to derive child keys.
+    pub chain_code: [u8; 32],
+    _components: PhantomData<C>,
+}
+
+impl<C: DPCComponents> AccountExtendedPrivateKey<C> {
+    /// The HMAC key used to derive the master key.
+    const MASTER_KEY: &'static [u8] = b"Aleo seed";
+
+    /// Derives the master extended private key from a master seed of 16 to 64 bytes.
+    pub fn new_master(master_seed: &[u8]) -> Result<Self, AccountError> {
+        if master_seed.len() < 16 || master_seed.len() > 64 {
+            return Err(AccountError::InvalidByteLength(master_seed.len()));
+        }
+
+        let (mut seed, mut chain_code) = hmac_sha512(Self::MASTER_KEY, &[master_seed]);
+        while !AccountPrivateKey::<C>::is_valid_seed(&seed) {
+            let (next_seed, next_chain_code) = hmac_sha512(Self::MASTER_KEY, &[&seed, &chain_code]);
+            seed = next_seed;
+            chain_code = next_chain_code;
+        }
+
+        Ok(Self {
+            depth: 0,
+            child_index: 0,
+            seed,
+            chain_code,
+            _components: PhantomData,
+        })
+    }
+
+    /// Derives the hardened child at the given index, which must include the hardened offset.
+    pub fn derive_child(&self, index: u32) -> Result<Self, AccountError> {
+        if index < HARDENED_OFFSET {
+            return Err(AccountError::NonHardenedDerivationIndex(index));
+        }
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or_else(|| AccountError::InvalidDerivationPath("maximum depth exceeded".into()))?;
+
+        let (mut seed, mut chain_code) = hmac_sha512(&self.chain_code, &[&[0u8], &self.seed, &index.to_be_bytes()]);
+        while !AccountPrivateKey::<C>::is_valid_seed(&seed) {
+            let (next_seed, next_chain_code) =
+                hmac_sha512(&self.chain_code, &[&[1u8], &chain_code, &index.to_be_bytes()]);
+            seed = next_seed;
+            chain_code = next_chain_code;
+        }
+
+        Ok(Self {
+            depth,
+            child_index: index,
+            seed,
+            chain_code,
+            _components: PhantomData,
+        })
+    }
+
+    /// Derives the descendant at the given path, relative to this key.
+    pub fn derive_path(&self, path: &AccountDerivationPath) -> Result<Self, AccountError> {
+        let mut key = self.clone();
+        for index in &path.0 {
+            key = key.derive_child(*index)?;
+        }
+        Ok(key)
+    }
+
+    /// Returns the account private key of this node.
+    pub fn to_private_key(
+        &self,
+        signature_parameters: &C::AccountSignature,
+        commitment_parameters: &C::AccountCommitment,
+    ) -> Result<AccountPrivateKey<C>, AccountError> {
+        AccountPrivateKey::from_seed(signature_parameters, commitment_parameters, &self.seed)
+    }
+
+    /// Returns the account view key of this node.
+    pub fn to_view_key(
+        &self,
+        signature_parameters: &C::AccountSignature,
+        commitment_parameters: &C::AccountCommitment,
+    ) -> Result<AccountViewKey<C>, AccountError> {
+        let private_key = self.to_private_key(signature_parameters, commitment_parameters)?;
+        AccountViewKey::from_private_key(signature_parameters, commitment_parameters, &private_key)
+    }
+
+    /// Returns the account address of this node.
+    pub fn to_address(
+        &self,
+        signature_parameters: &C::AccountSignature,
+        commitment_parameters: &C::AccountCommitment,
+        encryption_parameters: &C::AccountEncryption,
+    ) -> Result<AccountAddress<C>, AccountError> {
+        let private_key = self.to_private_key(signature_parameters, commitment_parameters)?;
+        AccountAddress::from_private_key(
+            signature_parameters,
+            commitment_parameters,
+            encryption_parameters,
+            &private_key,
+        )
+    }
+}
+
+impl<C: DPCComponents> fmt::Debug for AccountExtendedPrivateKey<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AccountExtendedPrivateKey {{ depth: {:?}, child_index: {:?} }}",
+            self.depth, self.child_index
+        )
+    }
+}
+
+/// A path of hardened child indices, such as `m/0'/5'`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountDerivationPath(Vec<u32>);
+
+impl AccountDerivationPath {
+    /// Returns the child indices of the path, each offset by `HARDENED_OFFSET`.
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl FromStr for AccountDerivationPath {
+    type Err = AccountError;
+
+    /// Reads in a derivation path string, where every index must be hardened with `'` or `h`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut components = s.split('/');
+        if components.next() != Some("m") {
+            return Err(AccountError::InvalidDerivationPath(s.into()));
+        }
+
+        let mut indices = vec![];
+        for component in components {
+            let index = match component.strip_suffix('\'').or_else(|| component.strip_suffix('h')) {
+                Some(index) => index,
+                None => match component.parse::<u32>() {
+                    Ok(index) => return Err(AccountError::NonHardenedDerivationIndex(index)),
+                    Err(_) => return Err(AccountError::InvalidDerivationPath(s.into())),
+                },
+            };
+
+            match index.parse::<u32>() {
+                Ok(index) if index < HARDENED_OFFSET => indices.push(index + HARDENED_OFFSET),
+                _ => return Err(AccountError::InvalidDerivationPath(s.into())),
+            }
+        }
+
+        Ok(Self(indices))
+    }
+}
+
+impl fmt::Display for AccountDerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            write!(f, "/{}'", index - HARDENED_OFFSET)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the left and right halves of the HMAC-SHA512 of the concatenated inputs.
+fn hmac_sha512(key: &[u8], inputs: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = HmacSha512::new_varkey(key).expect("HMAC accepts keys of any length");
+    for input in inputs {
+        mac.update(input);
+    }
+    let output = mac.finalize().into_bytes();
+
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    (left, right)
+}
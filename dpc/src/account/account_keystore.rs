@@ -0,0 +1,233 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{traits::DPCComponents, AccountAddress, AccountError, AccountPrivateKey};
+
+use chacha20poly1305::{
+    aead::{Aead, NewAead, Payload},
+    ChaCha20Poly1305,
+    Key,
+    Nonce,
+};
+use rand::Rng;
+use scrypt::{scrypt, ScryptParams};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// The version of the keystore format.
+pub const KEYSTORE_VERSION: u32 = 1;
+
+/// The key derivation function of the keystore format.
+const KEYSTORE_KDF: &str = "scrypt";
+
+/// The cipher of the keystore format.
+const KEYSTORE_CIPHER: &str = "chacha20poly1305";
+
+/// The default scrypt cost parameters (32 MiB of memory).
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// The largest scrypt cost parameters accepted from a keystore (2 GiB of memory),
+/// so that a malicious keystore cannot exhaust the memory or time of the reader.
+const MAX_SCRYPT_LOG_N: u8 = 20;
+const MAX_SCRYPT_R: u32 = 16;
+const MAX_SCRYPT_P: u32 = 4;
+
+/// The length of the scrypt salt in bytes.
+const SALT_LENGTH: usize = 32;
+
+/// A password-encrypted account private key, stored as versioned JSON.
+///
+/// The encryption key is derived from the password with scrypt, and the seed and r_pk counter of
+/// the private key are encrypted with ChaCha20-Poly1305. The address is stored in the clear, so a
+/// keystore can be identified without the password, and is authenticated as associated data.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountKeystore {
+    pub version: u32,
+    pub address: String,
+    pub crypto: KeystoreCrypto,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub kdf: String,
+    pub kdfparams: KeystoreKdfParams,
+    pub cipher: String,
+    pub cipherparams: KeystoreCipherParams,
+    /// The hex-encoded ciphertext, including the authentication tag.
+    pub ciphertext: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreKdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+    /// The hex-encoded 32-byte salt.
+    pub salt: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCipherParams {
+    /// The hex-encoded 12-byte nonce.
+    pub nonce: String,
+}
+
+impl AccountKeystore {
+    /// Reads in a keystore and checks that its version, key derivation function and cipher are supported.
+    pub fn from_bytes(keystore: &[u8]) -> Result<Self, AccountError> {
+        let keystore: Self = serde_json::from_slice(keystore)?;
+
+        if keystore.version != KEYSTORE_VERSION {
+            return Err(AccountError::UnsupportedKeystoreVersion(keystore.version));
+        }
+        if keystore.crypto.kdf != KEYSTORE_KDF {
+            return Err(AccountError::InvalidKeystore(format!(
+                "unsupported key derivation function {}",
+                keystore.crypto.kdf
+            )));
+        }
+        if keystore.crypto.cipher != KEYSTORE_CIPHER {
+            return Err(AccountError::InvalidKeystore(format!(
+                "unsupported cipher {}",
+                keystore.crypto.cipher
+            )));
+        }
+
+        Ok(keystore)
+    }
+
+    /// Returns the keystore as JSON bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, AccountError> {
+        Ok(serde_json::to_vec_pretty(self)?)
+    }
+}
+
+impl<C: DPCComponents> AccountPrivateKey<C> {
+    /// Encrypts the account private key with the given password, and returns the keystore as JSON bytes.
+    pub fn to_keystore<R: Rng>(
+        &self,
+        password: &str,
+        signature_parameters: &C::AccountSignature,
+        commitment_parameters: &C::AccountCommitment,
+        encryption_parameters: &C::AccountEncryption,
+        rng: &mut R,
+    ) -> Result<Vec<u8>, AccountError> {
+        let address =
+            AccountAddress::from_private_key(signature_parameters, commitment_parameters, encryption_parameters, self)?
+                .to_string();
+
+        let salt: [u8; SALT_LENGTH] = rng.gen();
+        let nonce: [u8; 12] = rng.gen();
+        let key = derive_key(password, &salt, SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)?;
+
+        let mut plaintext = Zeroizing::new(Vec::with_capacity(34));
+        plaintext.extend_from_slice(&self.seed);
+        plaintext.extend_from_slice(&self.r_pk_counter.to_le_bytes());
+
+        let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key[..]))
+            .encrypt(Nonce::from_slice(&nonce), Payload {
+                msg: &plaintext,
+                aad: address.as_bytes(),
+            })
+            .map_err(|_| AccountError::InvalidKeystore("encryption failed".into()))?;
+
+        AccountKeystore {
+            version: KEYSTORE_VERSION,
+            address,
+            crypto: KeystoreCrypto {
+                kdf: KEYSTORE_KDF.into(),
+                kdfparams: KeystoreKdfParams {
+                    log_n: SCRYPT_LOG_N,
+                    r: SCRYPT_R,
+                    p: SCRYPT_P,
+                    salt: hex::encode(salt),
+                },
+                cipher: KEYSTORE_CIPHER.into(),
+                cipherparams: KeystoreCipherParams {
+                    nonce: hex::encode(nonce),
+                },
+                ciphertext: hex::encode(ciphertext),
+            },
+        }
+        .to_bytes()
+    }
+
+    /// Decrypts the account private key from the given keystore JSON bytes with the given password.
+    pub fn from_keystore(keystore: &[u8], password: &str) -> Result<Self, AccountError> {
+        let keystore = AccountKeystore::from_bytes(keystore)?;
+        let crypto = &keystore.crypto;
+
+        let salt = hex::decode(&crypto.kdfparams.salt)?;
+        let nonce = hex::decode(&crypto.cipherparams.nonce)?;
+        let ciphertext = hex::decode(&crypto.ciphertext)?;
+        if nonce.len() != 12 {
+            return Err(AccountError::InvalidKeystore(format!(
+                "invalid nonce length {}",
+                nonce.len()
+            )));
+        }
+
+        let params = &crypto.kdfparams;
+        if params.log_n > MAX_SCRYPT_LOG_N {
+            return Err(AccountError::InvalidKeystoreScryptLogN(params.log_n, MAX_SCRYPT_LOG_N));
+        }
+        if params.r > MAX_SCRYPT_R {
+            return Err(AccountError::InvalidKeystoreScryptR(params.r, MAX_SCRYPT_R));
+        }
+        if params.p > MAX_SCRYPT_P {
+            return Err(AccountError::InvalidKeystoreScryptP(params.p, MAX_SCRYPT_P));
+        }
+        if salt.len() != SALT_LENGTH {
+            return Err(AccountError::InvalidKeystoreSaltLength(salt.len()));
+        }
+
+        let key = derive_key(password, &salt, params.log_n, params.r, params.p)?;
+
+        let plaintext = Zeroizing::new(
+            ChaCha20Poly1305::new(Key::from_slice(&key[..]))
+                .decrypt(Nonce::from_slice(&nonce), Payload {
+                    msg: &ciphertext,
+                    aad: keystore.address.as_bytes(),
+                })
+                .map_err(|_| AccountError::InvalidKeystorePassword)?,
+        );
+        if plaintext.len() != 34 {
+            return Err(AccountError::InvalidKeystore(format!(
+                "invalid plaintext length {}",
+                plaintext.len()
+            )));
+        }
+
+        let mut seed = Zeroizing::new([0u8; 32]);
+        seed.copy_from_slice(&plaintext[..32]);
+        let r_pk_counter = u16::from_le_bytes([plaintext[32], plaintext[33]]);
+
+        Self::from_seed_and_counter_unchecked(&seed, r_pk_counter)
+    }
+}
+
+/// Derives the 32-byte encryption key from the password with scrypt.
+fn derive_key(password: &str, salt: &[u8], log_n: u8, r: u32, p: u32) -> Result<Zeroizing<[u8; 32]>, AccountError> {
+    let params = ScryptParams::new(log_n, r, p)
+        .map_err(|_| AccountError::InvalidKeystore("invalid scrypt parameters".into()))?;
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt(password.as_bytes(), salt, &params, &mut key[..])
+        .map_err(|_| AccountError::InvalidKeystore("invalid scrypt output length".into()))?;
+    Ok(key)
+}
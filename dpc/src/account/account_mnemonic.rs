@@ -0,0 +1,169 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{traits::DPCComponents, AccountError, AccountExtendedPrivateKey, AccountPrivateKey};
+
+use hmac::Hmac;
+use rand::Rng;
+use sha2::{Digest, Sha256, Sha512};
+use std::{fmt, str::FromStr};
+use unicode_normalization::UnicodeNormalization;
+
+/// The BIP39 English wordlist.
+const ENGLISH_WORDLIST: &str = include_str!("wordlists/english.txt");
+
+/// The number of PBKDF2 rounds used to derive the seed from a mnemonic.
+const PBKDF2_ROUNDS: u32 = 2048;
+
+/// A BIP39 mnemonic phrase encoding 128 to 256 bits of entropy.
+///
+/// The mnemonic derives a 64-byte seed with an optional passphrase, which is used as the master seed of an
+/// account derivation tree. The seed of the master key is the seed consumed by `AccountPrivateKey::from_seed`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AccountMnemonic {
+    entropy: Vec<u8>,
+    words: Vec<&'static str>,
+}
+
+impl AccountMnemonic {
+    /// Samples a new mnemonic with the given number of words, which must be 12, 15, 18, 21 or 24.
+    pub fn new<R: Rng>(num_words: usize, rng: &mut R) -> Result<Self, AccountError> {
+        if !matches!(num_words, 12 | 15 | 18 | 21 | 24) {
+            return Err(AccountError::InvalidMnemonicWordCount(num_words));
+        }
+
+        let entropy: Vec<u8> = (0..num_words * 4 / 3).map(|_| rng.gen()).collect();
+        Self::from_entropy(&entropy)
+    }
+
+    /// Encodes the given entropy, which must be 16, 20, 24, 28 or 32 bytes, as a mnemonic.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, AccountError> {
+        if !matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) {
+            return Err(AccountError::InvalidByteLength(entropy.len()));
+        }
+
+        let wordlist = wordlist();
+        let bits = entropy_bits_with_checksum(entropy);
+        let words = bits
+            .chunks(11)
+            .map(|chunk| wordlist[chunk.iter().fold(0, |index, bit| (index << 1) | *bit as usize)])
+            .collect();
+
+        Ok(Self {
+            entropy: entropy.to_vec(),
+            words,
+        })
+    }
+
+    /// Returns the entropy encoded by the mnemonic.
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// Returns the 64-byte seed of the mnemonic with the given passphrase, which may be empty.
+    pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+        let phrase = self.to_string();
+        let salt = format!("mnemonic{}", passphrase).nfkd().collect::<String>();
+
+        let mut seed = [0u8; 64];
+        pbkdf2::pbkdf2::<Hmac<Sha512>>(phrase.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
+        seed
+    }
+
+    /// Returns the master key of the account derivation tree of the mnemonic with the given passphrase.
+    pub fn to_extended_private_key<C: DPCComponents>(
+        &self,
+        passphrase: &str,
+    ) -> Result<AccountExtendedPrivateKey<C>, AccountError> {
+        AccountExtendedPrivateKey::new_master(&self.to_seed(passphrase))
+    }
+
+    /// Returns the account private key of the master key of the mnemonic with the given passphrase.
+    pub fn to_private_key<C: DPCComponents>(
+        &self,
+        passphrase: &str,
+        signature_parameters: &C::AccountSignature,
+        commitment_parameters: &C::AccountCommitment,
+    ) -> Result<AccountPrivateKey<C>, AccountError> {
+        self.to_extended_private_key::<C>(passphrase)?
+            .to_private_key(signature_parameters, commitment_parameters)
+    }
+}
+
+impl FromStr for AccountMnemonic {
+    type Err = AccountError;
+
+    /// Reads in a mnemonic phrase and verifies its checksum.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let phrase = s.nfkd().collect::<String>();
+        let wordlist = wordlist();
+
+        let words = phrase.split_whitespace().collect::<Vec<_>>();
+        if !matches!(words.len(), 12 | 15 | 18 | 21 | 24) {
+            return Err(AccountError::InvalidMnemonicWordCount(words.len()));
+        }
+
+        let mut bits = Vec::with_capacity(words.len() * 11);
+        for word in &words {
+            let index = wordlist
+                .binary_search(word)
+                .map_err(|_| AccountError::InvalidMnemonicWord(word.to_string()))?;
+            bits.extend((0..11).rev().map(|i| (index >> i) & 1 == 1));
+        }
+
+        // The entropy is followed by one checksum bit for every 32 bits of entropy.
+        let entropy_length = bits.len() / 33 * 32;
+        let entropy = bits[..entropy_length]
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0u8, |byte, bit| (byte << 1) | *bit as u8))
+            .collect::<Vec<_>>();
+
+        if entropy_bits_with_checksum(&entropy) != bits {
+            return Err(AccountError::InvalidMnemonicChecksum);
+        }
+
+        Self::from_entropy(&entropy)
+    }
+}
+
+impl fmt::Display for AccountMnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.words.join(" "))
+    }
+}
+
+impl fmt::Debug for AccountMnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AccountMnemonic {{ num_words: {:?} }}", self.words.len())
+    }
+}
+
+/// Returns the sorted BIP39 English wordlist.
+fn wordlist() -> Vec<&'static str> {
+    ENGLISH_WORDLIST.lines().collect()
+}
+
+/// Returns the bits of the entropy, followed by the first `entropy.len() / 4` bits of its SHA-256 hash.
+fn entropy_bits_with_checksum(entropy: &[u8]) -> Vec<bool> {
+    let checksum = Sha256::digest(entropy);
+
+    let mut bits = Vec::with_capacity(entropy.len() * 8 + entropy.len() / 4);
+    for byte in entropy {
+        bits.extend((0..8).rev().map(|i| (byte >> i) & 1 == 1));
+    }
+    bits.extend((0..entropy.len() / 4).map(|i| (checksum[i / 8] >> (7 - i % 8)) & 1 == 1));
+    bits
+}
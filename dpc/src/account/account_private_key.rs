@@ -14,12 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{account_format, traits::DPCComponents, AccountError};
+use crate::{account_format, traits::DPCComponents, AccountComputeKey, AccountError};
 use snarkvm_algorithms::{
     prf::Blake2s,
     traits::{CommitmentScheme, EncryptionScheme, SignatureScheme, PRF},
 };
-use snarkvm_utilities::{bytes_to_bits, to_bytes, FromBytes, ToBytes};
+use snarkvm_utilities::{FromBytes, ToBytes};
 
 use base58::{FromBase58, ToBase58};
 use rand::Rng;
@@ -141,6 +141,14 @@ impl<C: DPCComponents> AccountPrivateKey<C> {
         })
     }
 
+    /// Returns `true` if the given seed derives a valid signature private key. Otherwise, returns `false`.
+    pub fn is_valid_seed(seed: &[u8; 32]) -> bool {
+        match Blake2s::evaluate(seed, &Self::INPUT_SK_SIG) {
+            Ok(sk_sig_bytes) => <C::AccountSignature as SignatureScheme>::PrivateKey::read(&sk_sig_bytes[..]).is_ok(),
+            Err(_) => false,
+        }
+    }
+
     /// Generate the randomness rpk for the commitment scheme from a given seed and counter
     fn derive_r_pk(
         seed: &[u8; 32],
@@ -172,39 +180,7 @@ impl<C: DPCComponents> AccountPrivateKey<C> {
         signature_parameters: &C::AccountSignature,
         commitment_parameters: &C::AccountCommitment,
     ) -> Result<<C::AccountEncryption as EncryptionScheme>::PrivateKey, AccountError> {
-        let commitment = self.commit(signature_parameters, commitment_parameters)?;
-        let decryption_key_bytes = to_bytes![commitment]?;
-
-        // This operation implicitly enforces that the unused MSB bits
-        // for the scalar field representation are correctly set to 0.
-        let decryption_key = match self.is_dummy {
-            true => <C::AccountEncryption as EncryptionScheme>::PrivateKey::default(),
-            false => <C::AccountEncryption as EncryptionScheme>::PrivateKey::read(&decryption_key_bytes[..])?,
-        };
-
-        // This operation explicitly enforces that the unused MSB bits
-        // for the scalar field representation are correctly set to 0.
-        //
-        // To simplify verification of this isomorphism from the base field
-        // to the scalar field in the `inner_snark`, we additionally enforce
-        // that the MSB bit of the scalar field is also set to 0.
-        if !self.is_dummy {
-            let account_decryption_key_bits = bytes_to_bits(&decryption_key_bytes[..]).collect::<Vec<_>>();
-            let account_decryption_key_length = account_decryption_key_bits.len();
-
-            let decryption_private_key_length = C::AccountEncryption::private_key_size_in_bits();
-            assert!(decryption_private_key_length > 0);
-            assert!(decryption_private_key_length <= account_decryption_key_length);
-
-            for i in (decryption_private_key_length - 1)..account_decryption_key_length {
-                let bit_index = account_decryption_key_length - i - 1;
-                if account_decryption_key_bits[bit_index] {
-                    return Err(AccountError::InvalidAccountCommitment);
-                }
-            }
-        }
-
-        Ok(decryption_key)
+        AccountComputeKey::from_private_key(signature_parameters, self)?.to_decryption_key(commitment_parameters)
     }
 
     /// Returns the signature public key for deriving the account view key.
@@ -217,23 +193,6 @@ impl<C: DPCComponents> AccountPrivateKey<C> {
             &self.sk_sig,
         )?)
     }
-
-    /// Returns the commitment output of the private key.
-    fn commit(
-        &self,
-        signature_parameters: &C::AccountSignature,
-        commitment_parameters: &C::AccountCommitment,
-    ) -> Result<<C::AccountCommitment as CommitmentScheme>::Output, AccountError> {
-        // Construct the commitment input for the account address.
-        let pk_sig = self.pk_sig(signature_parameters)?;
-        let commit_input = to_bytes![pk_sig, self.sk_prf]?;
-
-        Ok(C::AccountCommitment::commit(
-            commitment_parameters,
-            &commit_input,
-            &self.r_pk,
-        )?)
-    }
 }
 
 impl<C: DPCComponents> FromStr for AccountPrivateKey<C> {
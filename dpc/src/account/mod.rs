@@ -22,9 +22,21 @@ pub use account::*;
 pub mod account_address;
 pub use account_address::*;
 
+pub mod account_compute_key;
+pub use account_compute_key::*;
+
+pub mod account_extended_private_key;
+pub use account_extended_private_key::*;
+
 pub mod account_format;
 pub use account_format::*;
 
+pub mod account_keystore;
+pub use account_keystore::*;
+
+pub mod account_mnemonic;
+pub use account_mnemonic::*;
+
 pub mod account_private_key;
 pub use account_private_key::*;
 
@@ -15,8 +15,19 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    account::{Account, AccountAddress, AccountPrivateKey, AccountViewKey},
+    account::{
+        Account,
+        AccountAddress,
+        AccountDerivationPath,
+        AccountExtendedPrivateKey,
+        AccountKeystore,
+        AccountMnemonic,
+        AccountPrivateKey,
+        AccountViewKey,
+        HARDENED_OFFSET,
+    },
     base_dpc::{instantiated::Components, parameters::SystemParameters},
+    errors::AccountError,
     traits::account::AccountScheme,
 };
 
@@ -68,3 +79,313 @@ pub fn test_address_from_str() {
     assert!(address.is_ok());
     assert_eq!(address_string, address.unwrap().to_string());
 }
+
+#[test]
+pub fn test_extended_private_key_derivation() {
+    let master_seed: Vec<u8> = (0u8..16).collect();
+    let master = AccountExtendedPrivateKey::<Components>::new_master(&master_seed).unwrap();
+
+    // (path, seed, chain code)
+    let test_vectors = [
+        (
+            "m",
+            "2135a4ef0d6efce286fbf85993a096333703b91ab22e26429b27b44323ac7358",
+            "fb84adbee5c55995cd12a1c633d8693eb37c4246b39d120d274a927564c7d74d",
+        ),
+        (
+            "m/0'",
+            "172670e064627739cf4c3482c474de2244bcb7e44ff295ac41bd1c573e34980c",
+            "d919e26795af24979058673bf83f32efe74642b5548f704b82f04720edf77960",
+        ),
+        (
+            "m/0'/5'",
+            "b67179a6558c151ee1a77295fb6b7e0eee68bf8d95cbc696741b7a2b0afcfb79",
+            "47038c2f0c594232059238ebb61f4f0a53efb29a69492f4d5b50a5ad7f653c7f",
+        ),
+    ];
+
+    for (depth, (path, seed, chain_code)) in test_vectors.iter().enumerate() {
+        let path = AccountDerivationPath::from_str(path).unwrap();
+        let key = master.derive_path(&path).unwrap();
+
+        assert_eq!(depth, key.depth as usize);
+        assert_eq!(*seed, hex::encode(key.seed));
+        assert_eq!(*chain_code, hex::encode(key.chain_code));
+    }
+
+    // Deriving one child at a time yields the same key as deriving the full path.
+    let child = master
+        .derive_child(HARDENED_OFFSET)
+        .unwrap()
+        .derive_child(5 + HARDENED_OFFSET)
+        .unwrap();
+    assert_eq!(
+        child,
+        master
+            .derive_path(&AccountDerivationPath::from_str("m/0'/5'").unwrap())
+            .unwrap()
+    );
+    assert_eq!(5 + HARDENED_OFFSET, child.child_index);
+
+    // Non-hardened derivation is not supported.
+    assert!(matches!(
+        master.derive_child(5),
+        Err(AccountError::NonHardenedDerivationIndex(5))
+    ));
+
+    // The master seed must be between 16 and 64 bytes.
+    assert!(AccountExtendedPrivateKey::<Components>::new_master(&[0u8; 15]).is_err());
+    assert!(AccountExtendedPrivateKey::<Components>::new_master(&[0u8; 65]).is_err());
+}
+
+#[test]
+pub fn test_extended_private_key_account() {
+    let parameters = SystemParameters::<Components>::load().unwrap();
+
+    let master_seed: Vec<u8> = (0u8..16).collect();
+    let key = AccountExtendedPrivateKey::<Components>::new_master(&master_seed)
+        .unwrap()
+        .derive_path(&AccountDerivationPath::from_str("m/0'/5'").unwrap())
+        .unwrap();
+
+    let private_key = key
+        .to_private_key(&parameters.account_signature, &parameters.account_commitment)
+        .unwrap();
+    let view_key = key
+        .to_view_key(&parameters.account_signature, &parameters.account_commitment)
+        .unwrap();
+    let address = key
+        .to_address(
+            &parameters.account_signature,
+            &parameters.account_commitment,
+            &parameters.account_encryption,
+        )
+        .unwrap();
+
+    // The derived keys match the keys of the account private key with the derived seed.
+    assert_eq!(
+        private_key,
+        AccountPrivateKey::from_seed(&parameters.account_signature, &parameters.account_commitment, &key.seed).unwrap()
+    );
+    assert_eq!(
+        view_key,
+        AccountViewKey::from_private_key(
+            &parameters.account_signature,
+            &parameters.account_commitment,
+            &private_key
+        )
+        .unwrap()
+    );
+
+    assert_eq!(
+        "APrivateKey1vqEa8cWi96oavyWXyysJDDUjM6ozvnGGTK8jHtoEuECaUYp",
+        private_key.to_string()
+    );
+    assert_eq!(
+        "AViewKey1cVizFmVgJjSGzvsEjAhfD39S6VWpSkebE5kP7FaR7umm",
+        view_key.to_string()
+    );
+    assert_eq!(
+        "aleo1ue3zprtmcw685mdzcvyvyp03q7lvs3jhjq3mxkccn29fe4j3kyrsy2l9rj",
+        address.to_string()
+    );
+}
+
+#[test]
+pub fn test_derivation_path_from_str() {
+    let path = AccountDerivationPath::from_str("m/0'/5h").unwrap();
+    assert_eq!(vec![HARDENED_OFFSET, 5 + HARDENED_OFFSET], path.indices());
+    assert_eq!("m/0'/5'", path.to_string());
+
+    assert_eq!(Vec::<u32>::new(), AccountDerivationPath::from_str("m").unwrap().indices());
+
+    assert!(matches!(
+        AccountDerivationPath::from_str("m/0'/5"),
+        Err(AccountError::NonHardenedDerivationIndex(5))
+    ));
+    assert!(AccountDerivationPath::from_str("0'/5'").is_err());
+    assert!(AccountDerivationPath::from_str("m/a'").is_err());
+    assert!(AccountDerivationPath::from_str("m/2147483648'").is_err());
+}
+
+#[test]
+pub fn test_mnemonic_vectors() {
+    // (entropy, mnemonic, seed with the passphrase "TREZOR")
+    let test_vectors = [
+        (
+            "00000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+        ),
+        (
+            "8080808080808080808080808080808080808080808080808080808080808080",
+            "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic bless",
+            "c0c519bd0e91a2ed54357d9d1ebef6f5af218a153624cf4f2da911a0ed8f7a09e2ef61af0aca007096df430022f7a2b6fb91661a9589097069720d015e4e982f",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+            "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+        ),
+    ];
+
+    for (entropy, phrase, seed) in test_vectors.iter() {
+        let mnemonic = AccountMnemonic::from_entropy(&hex::decode(entropy).unwrap()).unwrap();
+        assert_eq!(*phrase, mnemonic.to_string());
+        assert_eq!(*seed, hex::encode(&mnemonic.to_seed("TREZOR")[..]));
+
+        let recovered_mnemonic = AccountMnemonic::from_str(phrase).unwrap();
+        assert_eq!(mnemonic, recovered_mnemonic);
+        assert_eq!(*entropy, hex::encode(recovered_mnemonic.entropy()));
+    }
+}
+
+#[test]
+pub fn test_mnemonic_account() {
+    let rng = &mut thread_rng();
+    let parameters = SystemParameters::<Components>::load().unwrap();
+
+    let mnemonic = AccountMnemonic::new(24, rng).unwrap();
+    let recovered_mnemonic = AccountMnemonic::from_str(&mnemonic.to_string()).unwrap();
+    assert_eq!(mnemonic, recovered_mnemonic);
+
+    let private_key = mnemonic
+        .to_private_key::<Components>(
+            "passphrase",
+            &parameters.account_signature,
+            &parameters.account_commitment,
+        )
+        .unwrap();
+    let recovered_private_key = recovered_mnemonic
+        .to_private_key::<Components>(
+            "passphrase",
+            &parameters.account_signature,
+            &parameters.account_commitment,
+        )
+        .unwrap();
+    assert_eq!(private_key, recovered_private_key);
+
+    // The seed of the master key is the account private key seed.
+    let master = mnemonic.to_extended_private_key::<Components>("passphrase").unwrap();
+    assert_eq!(master.seed, private_key.seed);
+
+    // A different passphrase derives a different account.
+    let other_private_key = mnemonic
+        .to_private_key::<Components>("", &parameters.account_signature, &parameters.account_commitment)
+        .unwrap();
+    assert_ne!(private_key, other_private_key);
+}
+
+#[test]
+pub fn test_mnemonic_errors() {
+    let rng = &mut thread_rng();
+
+    // The last word of an all-zero entropy mnemonic must be "about".
+    assert!(matches!(
+        AccountMnemonic::from_str(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"
+        ),
+        Err(AccountError::InvalidMnemonicChecksum)
+    ));
+    assert!(matches!(
+        AccountMnemonic::from_str(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon aleo"
+        ),
+        Err(AccountError::InvalidMnemonicWord(word)) if word == "aleo"
+    ));
+    assert!(matches!(
+        AccountMnemonic::from_str(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        ),
+        Err(AccountError::InvalidMnemonicWordCount(11))
+    ));
+
+    assert!(matches!(
+        AccountMnemonic::new(13, rng),
+        Err(AccountError::InvalidMnemonicWordCount(13))
+    ));
+    assert!(matches!(
+        AccountMnemonic::from_entropy(&[0u8; 15]),
+        Err(AccountError::InvalidByteLength(15))
+    ));
+}
+
+#[test]
+pub fn test_keystore() {
+    let rng = &mut thread_rng();
+    let parameters = SystemParameters::<Components>::load().unwrap();
+
+    let account = Account::<Components>::new(
+        &parameters.account_signature,
+        &parameters.account_commitment,
+        &parameters.account_encryption,
+        rng,
+    )
+    .unwrap();
+
+    let keystore = account
+        .private_key
+        .to_keystore(
+            "password",
+            &parameters.account_signature,
+            &parameters.account_commitment,
+            &parameters.account_encryption,
+            rng,
+        )
+        .unwrap();
+
+    // The address is stored in the clear, and the private key is not.
+    let parsed_keystore = AccountKeystore::from_bytes(&keystore).unwrap();
+    assert_eq!(account.address.to_string(), parsed_keystore.address);
+    let keystore_string = String::from_utf8(keystore.clone()).unwrap();
+    assert!(!keystore_string.contains(&account.private_key.to_string()));
+    assert!(!keystore_string.contains(&hex::encode(account.private_key.seed)));
+
+    let recovered_private_key = AccountPrivateKey::<Components>::from_keystore(&keystore, "password").unwrap();
+    assert_eq!(account.private_key, recovered_private_key);
+
+    // A wrong password is rejected.
+    assert!(matches!(
+        AccountPrivateKey::<Components>::from_keystore(&keystore, "wrong password"),
+        Err(AccountError::InvalidKeystorePassword)
+    ));
+
+    // The address is authenticated.
+    let mut tampered_keystore = parsed_keystore.clone();
+    tampered_keystore.address = "aleo1ag4alvc4g7d4apzgvr5f4jt44l0aezev2dx8m0klgwypnh9u5uxs42rclr".into();
+    assert!(matches!(
+        AccountPrivateKey::<Components>::from_keystore(&tampered_keystore.to_bytes().unwrap(), "password"),
+        Err(AccountError::InvalidKeystorePassword)
+    ));
+
+    // Scrypt parameters that are too costly to evaluate are rejected before deriving the key.
+    let mut costly_keystore = parsed_keystore.clone();
+    costly_keystore.crypto.kdfparams.log_n = 64;
+    assert!(matches!(
+        AccountPrivateKey::<Components>::from_keystore(&costly_keystore.to_bytes().unwrap(), "password"),
+        Err(AccountError::InvalidKeystoreScryptLogN(64, _))
+    ));
+
+    let mut short_salt_keystore = parsed_keystore.clone();
+    short_salt_keystore.crypto.kdfparams.salt = "00".into();
+    assert!(matches!(
+        AccountPrivateKey::<Components>::from_keystore(&short_salt_keystore.to_bytes().unwrap(), "password"),
+        Err(AccountError::InvalidKeystoreSaltLength(1))
+    ));
+
+    // Unknown versions are rejected.
+    let mut future_keystore = parsed_keystore;
+    future_keystore.version += 1;
+    assert!(matches!(
+        AccountPrivateKey::<Components>::from_keystore(&future_keystore.to_bytes().unwrap(), "password"),
+        Err(AccountError::UnsupportedKeystoreVersion(_))
+    ));
+
+    // Malformed keystores are rejected.
+    assert!(AccountPrivateKey::<Components>::from_keystore(b"{}", "password").is_err());
+}
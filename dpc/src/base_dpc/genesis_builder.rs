@@ -0,0 +1,254 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    account::{Account, AccountAddress},
+    base_dpc::{
+        parameters::{PublicParameters, SystemParameters},
+        program::PrivateProgramInput,
+        record::DPCRecord,
+        record_payload::RecordPayload,
+        transaction::DPCTransaction,
+        transaction_builder::{execute_with_noop_programs, generate_dummy_record},
+        BaseDPCComponents,
+        LocalData,
+        TransactionKernel,
+        DPC,
+    },
+    errors::GenesisBuilderError,
+    traits::{AccountScheme, DPCScheme, Record},
+};
+use snarkvm_algorithms::traits::CRH;
+use snarkvm_objects::{
+    dpc::DPCTransactions,
+    merkle_root_with_subroots,
+    pedersen_merkle_root,
+    traits::LedgerScheme,
+    Block,
+    BlockHeader,
+    BlockHeaderHash,
+    MerkleRootHash,
+    PedersenMerkleRootHash,
+    ProofOfSuccinctWork,
+    MASKED_TREE_DEPTH,
+};
+use snarkvm_utilities::{to_bytes, ToBytes};
+
+use rand::Rng;
+
+/// The difficulty target of the genesis block in `snarkvm-parameters`.
+pub const GENESIS_DIFFICULTY_TARGET: u64 = 0x07FF_FFFF_FFFF_FFFF_u64;
+
+/// The allocated records and the genesis block returned by `GenesisBuilder::build`.
+pub type GenesisOutput<C> = (Vec<DPCRecord<C>>, Block<DPCTransaction<C>>);
+
+/// Builds the genesis block of a new network, such as a private devnet.
+///
+/// Each coinbase transaction mints up to `NUM_OUTPUT_RECORDS` allocations from dummy input
+/// records, and is proven against an empty ledger. The header commits to the transactions,
+/// and the block can be given to `Ledger` in place of the genesis block in `snarkvm-parameters`.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "C: BaseDPCComponents"))]
+pub struct GenesisBuilder<C: BaseDPCComponents> {
+    allocations: Vec<(AccountAddress<C>, u64)>,
+    network_id: u8,
+    time: i64,
+    difficulty_target: u64,
+}
+
+impl<C: BaseDPCComponents> Default for GenesisBuilder<C> {
+    fn default() -> Self {
+        Self {
+            allocations: vec![],
+            network_id: 0,
+            time: 0,
+            difficulty_target: GENESIS_DIFFICULTY_TARGET,
+        }
+    }
+}
+
+impl<C: BaseDPCComponents> GenesisBuilder<C> {
+    /// Creates an empty genesis builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an allocation of the given amount to the given address.
+    pub fn add_allocation(mut self, address: AccountAddress<C>, amount: u64) -> Self {
+        self.allocations.push((address, amount));
+        self
+    }
+
+    /// Sets the network id. Defaults to zero.
+    pub fn network_id(mut self, network_id: u8) -> Self {
+        self.network_id = network_id;
+        self
+    }
+
+    /// Sets the block timestamp. Defaults to zero.
+    pub fn time(mut self, time: i64) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Sets the difficulty target. Defaults to `GENESIS_DIFFICULTY_TARGET`.
+    pub fn difficulty_target(mut self, difficulty_target: u64) -> Self {
+        self.difficulty_target = difficulty_target;
+        self
+    }
+
+    /// Checks that there is at least one allocation, and that the total
+    /// minted amount fits in a transaction value balance.
+    pub fn check_allocations(&self) -> Result<(), GenesisBuilderError> {
+        if self.allocations.is_empty() {
+            return Err(GenesisBuilderError::MissingAllocations);
+        }
+
+        if let Some(index) = self.allocations.iter().position(|(_, amount)| *amount == 0) {
+            return Err(GenesisBuilderError::ZeroAllocation(index));
+        }
+
+        let total = self
+            .allocations
+            .iter()
+            .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+            .ok_or(GenesisBuilderError::ValueOverflow)?;
+        if total > i64::MAX as u64 {
+            return Err(GenesisBuilderError::ValueOverflow);
+        }
+
+        Ok(())
+    }
+
+    /// Builds the genesis block, and returns the allocated records and the block.
+    ///
+    /// The coinbase transactions are proven against a new ledger of type `L`,
+    /// which must be able to open without a path, such as an in-memory ledger.
+    pub fn build<L, R: Rng>(
+        self,
+        parameters: &PublicParameters<C>,
+        ledger_parameters: &C::MerkleParameters,
+        rng: &mut R,
+    ) -> anyhow::Result<GenesisOutput<C>>
+    where
+        L: LedgerScheme<Block = Block<DPCTransaction<C>>, MerkleParameters = C::MerkleParameters>,
+        DPC<C>: DPCScheme<
+            L,
+            Account = Account<C>,
+            LocalData = LocalData<C>,
+            NetworkParameters = PublicParameters<C>,
+            Payload = RecordPayload,
+            PrivateProgramInput = PrivateProgramInput,
+            Record = DPCRecord<C>,
+            SystemParameters = SystemParameters<C>,
+            Transaction = DPCTransaction<C>,
+            TransactionKernel = TransactionKernel<C>,
+        >,
+    {
+        self.check_allocations()?;
+
+        let system_parameters = &parameters.system_parameters;
+        let noop_program_id = to_bytes![C::ProgramVerificationKeyCRH::hash(
+            &system_parameters.program_verification_key_crh,
+            &to_bytes![parameters.noop_program_snark_parameters().verification_key]?
+        )?]?;
+
+        // The coinbase transactions are proven against the digest of a ledger without commitments.
+        let empty_block = Block {
+            header: self.header(MerkleRootHash([0u8; 32]), PedersenMerkleRootHash([0u8; 32])),
+            transactions: DPCTransactions::new(),
+        };
+        let ledger = L::new(None, ledger_parameters.clone(), empty_block)?;
+
+        // The dummy input records are owned by an account that is discarded afterwards.
+        let dummy_account = Account::<C>::new(
+            &system_parameters.account_signature,
+            &system_parameters.account_commitment,
+            &system_parameters.account_encryption,
+            rng,
+        )?;
+
+        let mut records = Vec::with_capacity(self.allocations.len());
+        let mut transactions = DPCTransactions::new();
+        for allocations in self.allocations.chunks(C::NUM_OUTPUT_RECORDS) {
+            let mut old_records = Vec::with_capacity(C::NUM_INPUT_RECORDS);
+            for _ in 0..C::NUM_INPUT_RECORDS {
+                old_records.push(generate_dummy_record(
+                    system_parameters,
+                    dummy_account.address.clone(),
+                    &noop_program_id,
+                    rng,
+                )?);
+            }
+            let old_account_private_keys = vec![dummy_account.private_key.clone(); C::NUM_INPUT_RECORDS];
+
+            // Mint the allocations, padded with dummy records.
+            let mut new_record_owners = Vec::with_capacity(C::NUM_OUTPUT_RECORDS);
+            let mut new_is_dummy_flags = Vec::with_capacity(C::NUM_OUTPUT_RECORDS);
+            let mut new_values = Vec::with_capacity(C::NUM_OUTPUT_RECORDS);
+            for (address, amount) in allocations {
+                new_record_owners.push(address.clone());
+                new_is_dummy_flags.push(false);
+                new_values.push(*amount);
+            }
+            while new_record_owners.len() < C::NUM_OUTPUT_RECORDS {
+                new_record_owners.push(dummy_account.address.clone());
+                new_is_dummy_flags.push(true);
+                new_values.push(0);
+            }
+
+            let (new_records, transaction) = execute_with_noop_programs(
+                parameters,
+                &noop_program_id,
+                old_records,
+                old_account_private_keys,
+                new_record_owners,
+                &new_is_dummy_flags,
+                &new_values,
+                rng.gen(),
+                self.network_id,
+                &ledger,
+                rng,
+            )?;
+
+            records.extend(new_records.into_iter().filter(|record| !record.is_dummy()));
+            transactions.push(transaction);
+        }
+
+        let transaction_ids = transactions.to_transaction_ids()?;
+        let (merkle_root, subroots) = merkle_root_with_subroots(&transaction_ids, MASKED_TREE_DEPTH);
+        let header = self.header(MerkleRootHash(merkle_root), pedersen_merkle_root(&subroots));
+
+        Ok((records, Block { header, transactions }))
+    }
+
+    /// Returns the genesis block header with the given merkle roots.
+    fn header(
+        &self,
+        merkle_root_hash: MerkleRootHash,
+        pedersen_merkle_root_hash: PedersenMerkleRootHash,
+    ) -> BlockHeader {
+        BlockHeader {
+            previous_block_hash: BlockHeaderHash([0u8; 32]),
+            merkle_root_hash,
+            pedersen_merkle_root_hash,
+            proof: ProofOfSuccinctWork::default(),
+            time: self.time,
+            difficulty_target: self.difficulty_target,
+            nonce: 0,
+        }
+    }
+}
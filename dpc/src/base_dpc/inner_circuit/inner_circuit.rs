@@ -15,7 +15,7 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    account::AccountPrivateKey,
+    account::AccountComputeKey,
     base_dpc::{
         inner_circuit_gadget::execute_inner_proof_gadget,
         parameters::SystemParameters,
@@ -43,7 +43,7 @@ pub struct InnerCircuit<C: BaseDPCComponents> {
     // Inputs for old records.
     old_records: Vec<DPCRecord<C>>,
     old_witnesses: Vec<MerklePath<C::MerkleParameters>>,
-    old_account_private_keys: Vec<AccountPrivateKey<C>>,
+    old_account_compute_keys: Vec<AccountComputeKey<C>>,
     old_serial_numbers: Vec<<C::AccountSignature as SignatureScheme>::PublicKey>,
 
     // Inputs for new records.
@@ -80,7 +80,7 @@ impl<C: BaseDPCComponents> InnerCircuit<C> {
             vec![<C::AccountSignature as SignatureScheme>::PublicKey::default(); num_input_records];
         let old_records = vec![DPCRecord::default(); num_input_records];
         let old_witnesses = vec![MerklePath::default(); num_input_records];
-        let old_account_private_keys = vec![AccountPrivateKey::default(); num_input_records];
+        let old_account_compute_keys = vec![AccountComputeKey::default(); num_input_records];
 
         let new_commitments = vec![<C::RecordCommitment as CommitmentScheme>::Output::default(); num_output_records];
         let new_serial_number_nonce_randomness = vec![[0u8; 32]; num_output_records];
@@ -120,7 +120,7 @@ impl<C: BaseDPCComponents> InnerCircuit<C> {
             // Input records
             old_records,
             old_witnesses,
-            old_account_private_keys,
+            old_account_compute_keys,
             old_serial_numbers,
 
             // Output records
@@ -155,7 +155,7 @@ impl<C: BaseDPCComponents> InnerCircuit<C> {
         // Old records
         old_records: Vec<DPCRecord<C>>,
         old_witnesses: Vec<MerklePath<C::MerkleParameters>>,
-        old_account_private_keys: Vec<AccountPrivateKey<C>>,
+        old_account_compute_keys: Vec<AccountComputeKey<C>>,
         old_serial_numbers: Vec<<C::AccountSignature as SignatureScheme>::PublicKey>,
 
         // New records
@@ -185,7 +185,7 @@ impl<C: BaseDPCComponents> InnerCircuit<C> {
 
         assert_eq!(num_input_records, old_records.len());
         assert_eq!(num_input_records, old_witnesses.len());
-        assert_eq!(num_input_records, old_account_private_keys.len());
+        assert_eq!(num_input_records, old_account_compute_keys.len());
         assert_eq!(num_input_records, old_serial_numbers.len());
 
         assert_eq!(num_output_records, new_records.len());
@@ -221,7 +221,7 @@ impl<C: BaseDPCComponents> InnerCircuit<C> {
             // Input records
             old_records,
             old_witnesses,
-            old_account_private_keys,
+            old_account_compute_keys,
             old_serial_numbers,
 
             // Output records
@@ -257,7 +257,7 @@ impl<C: BaseDPCComponents> ConstraintSynthesizer<C::InnerField> for InnerCircuit
             // Old records
             &self.old_records,
             &self.old_witnesses,
-            &self.old_account_private_keys,
+            &self.old_account_compute_keys,
             &self.old_serial_numbers,
             // New records
             &self.new_records,
@@ -15,7 +15,7 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    account::AccountPrivateKey,
+    account::AccountComputeKey,
     base_dpc::{
         parameters::SystemParameters,
         record::DPCRecord,
@@ -25,6 +25,7 @@ use crate::{
     traits::Record,
 };
 use snarkvm_algorithms::{
+    commitment_tree::padded_num_leaves,
     merkle_tree::{MerklePath, MerkleTreeDigest},
     traits::{CommitmentScheme, EncryptionScheme, MerkleParameters, SignatureScheme, CRH, PRF},
 };
@@ -72,7 +73,7 @@ pub fn execute_inner_proof_gadget<C: BaseDPCComponents, CS: ConstraintSystem<C::
     // Old record stuff
     old_records: &[DPCRecord<C>],
     old_witnesses: &[MerklePath<C::MerkleParameters>],
-    old_account_private_keys: &[AccountPrivateKey<C>],
+    old_account_compute_keys: &[AccountComputeKey<C>],
     old_serial_numbers: &[<C::AccountSignature as SignatureScheme>::PublicKey],
 
     // New record stuff
@@ -124,7 +125,7 @@ pub fn execute_inner_proof_gadget<C: BaseDPCComponents, CS: ConstraintSystem<C::
         //
         old_records,
         old_witnesses,
-        old_account_private_keys,
+        old_account_compute_keys,
         old_serial_numbers,
         //
         new_records,
@@ -179,7 +180,7 @@ fn base_dpc_execute_gadget_helper<
     //
     old_records: &[DPCRecord<C>],
     old_witnesses: &[MerklePath<C::MerkleParameters>],
-    old_account_private_keys: &[AccountPrivateKey<C>],
+    old_account_compute_keys: &[AccountComputeKey<C>],
     old_serial_numbers: &[AccountSignature::PublicKey],
 
     //
@@ -354,10 +355,10 @@ where
     let mut old_record_commitments_gadgets = Vec::with_capacity(old_records.len());
     let mut old_death_program_ids_gadgets = Vec::with_capacity(old_records.len());
 
-    for (i, (((record, witness), account_private_key), given_serial_number)) in old_records
+    for (i, (((record, witness), account_compute_key), given_serial_number)) in old_records
         .iter()
         .zip(old_witnesses)
-        .zip(old_account_private_keys)
+        .zip(old_account_compute_keys)
         .zip(old_serial_numbers)
         .enumerate()
     {
@@ -464,19 +465,16 @@ where
             // Declare variables for account contents.
             let account_cs = &mut cs.ns(|| "Check account");
 
-            // Allocate the account private key.
+            // Allocate the account compute key.
             let (pk_sig, sk_prf, r_pk) = {
-                let pk_sig_native = account_private_key
-                    .pk_sig(&system_parameters.account_signature)
-                    .map_err(|_| SynthesisError::AssignmentMissing)?;
                 let pk_sig =
                     AccountSignatureGadget::PublicKeyGadget::alloc(&mut account_cs.ns(|| "Declare pk_sig"), || {
-                        Ok(&pk_sig_native)
+                        Ok(&account_compute_key.pk_sig)
                     })?;
-                let sk_prf = PGadget::new_seed(&mut account_cs.ns(|| "Declare sk_prf"), &account_private_key.sk_prf);
+                let sk_prf = PGadget::new_seed(&mut account_cs.ns(|| "Declare sk_prf"), &account_compute_key.sk_prf);
                 let r_pk =
                     AccountCommitmentGadget::RandomnessGadget::alloc(&mut account_cs.ns(|| "Declare r_pk"), || {
-                        Ok(&account_private_key.r_pk)
+                        Ok(&account_compute_key.r_pk)
                     })?;
 
                 (pk_sig, sk_prf, r_pk)
@@ -504,17 +502,14 @@ where
                 // given account's view key bytes (padded). This is equivalent to
                 // verifying that the base field element from the computed account
                 // commitment contains the same bit-value as the scalar field element
-                // computed from the given account private key.
+                // computed from the given account compute key.
                 let given_account_view_key = {
-                    // Derive the given account view key based on the given account private key.
+                    // Derive the given account view key based on the given account compute key.
                     let given_account_view_key = AccountEncryptionGadget::PrivateKeyGadget::alloc(
                         &mut account_cs.ns(|| "Allocate account view key"),
                         || {
-                            Ok(account_private_key
-                                .to_decryption_key(
-                                    &system_parameters.account_signature,
-                                    &system_parameters.account_commitment,
-                                )
+                            Ok(account_compute_key
+                                .to_decryption_key(&system_parameters.account_commitment)
                                 .map_err(|_| SynthesisError::AssignmentMissing)?)
                         },
                     )?;
@@ -1268,7 +1263,8 @@ where
         let memo = UInt8::alloc_input_vec(cs.ns(|| "Allocate memorandum"), memo)?;
         let network_id = UInt8::alloc_input_vec(cs.ns(|| "Allocate network id"), &[network_id])?;
 
-        let mut old_record_commitment_bytes = vec![];
+        let mut local_data_leaf_bytes =
+            Vec::with_capacity(padded_num_leaves(C::NUM_INPUT_RECORDS + C::NUM_OUTPUT_RECORDS));
         let mut input_bytes = vec![];
         for i in 0..C::NUM_INPUT_RECORDS {
             let mut cs = cs.ns(|| format!("Construct local data with input record {}", i));
@@ -1292,14 +1288,12 @@ where
                 &commitment_randomness,
             )?;
 
-            old_record_commitment_bytes
-                .extend_from_slice(&commitment.to_bytes(&mut cs.ns(|| "old_record_local_data"))?);
+            local_data_leaf_bytes.push(commitment.to_bytes(&mut cs.ns(|| "old_record_local_data"))?);
 
             input_bytes.clear();
         }
         drop(input_bytes);
 
-        let mut new_record_commitment_bytes = Vec::new();
         let mut input_bytes = vec![];
         for j in 0..C::NUM_OUTPUT_RECORDS {
             let mut cs = cs.ns(|| format!("Construct local data with output record {}", j));
@@ -1321,36 +1315,50 @@ where
                 &commitment_randomness,
             )?;
 
-            new_record_commitment_bytes
-                .extend_from_slice(&commitment.to_bytes(&mut cs.ns(|| "new_record_local_data"))?);
+            local_data_leaf_bytes.push(commitment.to_bytes(&mut cs.ns(|| "new_record_local_data"))?);
 
             input_bytes.clear();
         }
         drop(input_bytes);
 
-        let inner1_commitment_hash = LocalDataCRHGadget::check_evaluation_gadget(
-            cs.ns(|| "Compute to local data commitment inner1 hash"),
-            &local_data_crh_parameters,
-            old_record_commitment_bytes,
-        )?;
+        // Pad the leaves with default commitments, as is done by the local data commitment tree.
+        let default_leaf_bytes = UInt8::constant_vec(
+            &to_bytes![<C::LocalDataCommitment as CommitmentScheme>::Output::default()]
+                .map_err(|_| SynthesisError::AssignmentMissing)?,
+        );
+        local_data_leaf_bytes.resize(
+            padded_num_leaves(C::NUM_INPUT_RECORDS + C::NUM_OUTPUT_RECORDS),
+            default_leaf_bytes,
+        );
+
+        let mut local_data_level = Vec::with_capacity(local_data_leaf_bytes.len() / 2);
+        for (i, pair) in local_data_leaf_bytes.chunks(2).enumerate() {
+            local_data_level.push(LocalDataCRHGadget::check_evaluation_gadget(
+                cs.ns(|| format!("Compute to local data commitment inner hash {}", i)),
+                &local_data_crh_parameters,
+                [&pair[0][..], &pair[1][..]].concat(),
+            )?);
+        }
 
-        let inner2_commitment_hash = LocalDataCRHGadget::check_evaluation_gadget(
-            cs.ns(|| "Compute to local data commitment inner2 hash"),
-            &local_data_crh_parameters,
-            new_record_commitment_bytes,
-        )?;
+        let mut depth = 0;
+        while local_data_level.len() > 1 {
+            depth += 1;
 
-        let mut inner_commitment_hash_bytes = Vec::new();
-        inner_commitment_hash_bytes
-            .extend_from_slice(&inner1_commitment_hash.to_bytes(&mut cs.ns(|| "inner1_commitment_hash"))?);
-        inner_commitment_hash_bytes
-            .extend_from_slice(&inner2_commitment_hash.to_bytes(&mut cs.ns(|| "inner2_commitment_hash"))?);
+            let mut level_bytes = Vec::with_capacity(local_data_level.len());
+            for (i, hash) in local_data_level.iter().enumerate() {
+                level_bytes.push(hash.to_bytes(&mut cs.ns(|| format!("inner_commitment_hash_{}_{}", depth, i)))?);
+            }
 
-        let candidate_local_data_root = LocalDataCRHGadget::check_evaluation_gadget(
-            cs.ns(|| "Compute to local data commitment root"),
-            &local_data_crh_parameters,
-            inner_commitment_hash_bytes,
-        )?;
+            local_data_level.clear();
+            for (i, pair) in level_bytes.chunks(2).enumerate() {
+                local_data_level.push(LocalDataCRHGadget::check_evaluation_gadget(
+                    cs.ns(|| format!("Compute to local data commitment hash {} at depth {}", i, depth)),
+                    &local_data_crh_parameters,
+                    [&pair[0][..], &pair[1][..]].concat(),
+                )?);
+            }
+        }
+        let candidate_local_data_root = local_data_level.remove(0);
 
         let declared_local_data_root =
             LocalDataCRHGadget::OutputGadget::alloc_input(cs.ns(|| "Allocate local data root"), || {
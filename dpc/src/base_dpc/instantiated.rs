@@ -20,7 +20,7 @@ use crate::{
         inner_circuit_verifier_input::InnerCircuitVerifierInput,
         outer_circuit::OuterCircuit,
         outer_circuit_verifier_input::OuterCircuitVerifierInput,
-        program::{NoopCircuit, ProgramLocalData},
+        program::{HashLockCircuit, NoopCircuit, ProgramLocalData},
         transaction::DPCTransaction,
         BaseDPCComponents,
         LocalData as DPCLocalData,
@@ -60,6 +60,18 @@ use blake2::Blake2s as Blake2sHash;
 pub const NUM_INPUT_RECORDS: usize = 2;
 pub const NUM_OUTPUT_RECORDS: usize = 2;
 
+/// Returns the number of windows of `window_size` bits needed to hash `num_bytes` bytes,
+/// rounded up to a multiple of 8 (so the windows span whole bytes), and no fewer than `min_windows`.
+const fn num_windows(num_bytes: usize, window_size: usize, min_windows: usize) -> usize {
+    let num_windows = (8 * num_bytes + window_size - 1) / window_size;
+    let num_windows = (num_windows + 7) / 8 * 8;
+    if num_windows > min_windows {
+        num_windows
+    } else {
+        min_windows
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct AccountWindow;
 impl PedersenSize for AccountWindow {
@@ -75,11 +87,16 @@ impl PedersenSize for EncryptedRecordWindow {
     const WINDOW_SIZE: usize = 44;
 }
 
+/// The inner SNARK verification key has two public inputs for every input and output record,
+/// so transactions with more than four records require additional windows.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct InnerSNARKVkHashWindow;
+pub struct InnerSNARKVkHashWindow<const NUM_INPUTS: usize, const NUM_OUTPUTS: usize>;
 
-impl PedersenSize for InnerSNARKVkHashWindow {
-    const NUM_WINDOWS: usize = 296;
+impl<const NUM_INPUTS: usize, const NUM_OUTPUTS: usize> PedersenSize
+    for InnerSNARKVkHashWindow<NUM_INPUTS, NUM_OUTPUTS>
+{
+    // 680 bytes of fixed key elements, and 97 bytes for each of the 9 + 2 * (NUM_INPUTS + NUM_OUTPUTS) input elements.
+    const NUM_WINDOWS: usize = num_windows(680 + 97 * (9 + 2 * (NUM_INPUTS + NUM_OUTPUTS)), 63, 296);
     const WINDOW_SIZE: usize = 63;
 }
 
@@ -114,11 +131,14 @@ impl PedersenSize for RecordWindow {
     const WINDOW_SIZE: usize = 233;
 }
 
+/// The serial number nonce is computed over the serial numbers of all input records,
+/// so transactions with more than three input records require additional windows.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct SnNonceWindow;
+pub struct SnNonceWindow<const NUM_INPUTS: usize>;
 
-impl PedersenSize for SnNonceWindow {
-    const NUM_WINDOWS: usize = 32;
+impl<const NUM_INPUTS: usize> PedersenSize for SnNonceWindow<NUM_INPUTS> {
+    // The record index, 32 bytes of randomness, and 64 bytes for each input serial number.
+    const NUM_WINDOWS: usize = num_windows(1 + 32 + 64 * NUM_INPUTS, 63, 32);
     const WINDOW_SIZE: usize = 63;
 }
 
@@ -131,9 +151,17 @@ impl PedersenSize for TwoToOneWindow {
 
 define_merkle_tree_parameters!(CommitmentMerkleParameters, MerkleTreeCRH, 32);
 
-pub struct Components;
+/// The instantiated DPC components, with `NUM_INPUT_RECORDS` input records and `NUM_OUTPUT_RECORDS`
+/// output records per transaction.
+pub type Components = ComponentsWithArity<NUM_INPUT_RECORDS, NUM_OUTPUT_RECORDS>;
+
+/// The instantiated DPC components, with `NUM_INPUTS` input records and `NUM_OUTPUTS` output records
+/// per transaction.
+///
+/// Each arity requires its own inner and outer SNARK parameters.
+pub struct ComponentsWithArity<const NUM_INPUTS: usize, const NUM_OUTPUTS: usize>;
 
-impl DPCComponents for Components {
+impl<const NUM_INPUTS: usize, const NUM_OUTPUTS: usize> DPCComponents for ComponentsWithArity<NUM_INPUTS, NUM_OUTPUTS> {
     type AccountCommitment = AccountCommitment;
     type AccountCommitmentGadget = AccountCommitmentGadget;
     type AccountEncryption = AccountEncryption;
@@ -143,7 +171,7 @@ impl DPCComponents for Components {
     type EncryptedRecordCRH = EncryptedRecordCRH;
     type EncryptedRecordCRHGadget = EncryptedRecordCRHGadget;
     type InnerField = InnerField;
-    type InnerSNARKVerificationKeyCRH = InnerSNARKVerificationKeyCRH;
+    type InnerSNARKVerificationKeyCRH = InnerSNARKVerificationKeyCRH<NUM_INPUTS, NUM_OUTPUTS>;
     type InnerSNARKVerificationKeyCRHGadget = InnerSNARKVerificationKeyCRHGadget;
     type LocalDataCRH = LocalDataCRH;
     type LocalDataCRHGadget = LocalDataCRHGadget;
@@ -158,22 +186,24 @@ impl DPCComponents for Components {
     type ProgramVerificationKeyCommitmentGadget = ProgramVerificationKeyCommitmentGadget;
     type RecordCommitment = RecordCommitment;
     type RecordCommitmentGadget = RecordCommitmentGadget;
-    type SerialNumberNonceCRH = SerialNumberNonce;
+    type SerialNumberNonceCRH = SerialNumberNonce<NUM_INPUTS>;
     type SerialNumberNonceCRHGadget = SerialNumberNonceGadget;
 
-    const NUM_INPUT_RECORDS: usize = NUM_INPUT_RECORDS;
-    const NUM_OUTPUT_RECORDS: usize = NUM_OUTPUT_RECORDS;
+    const NUM_INPUT_RECORDS: usize = NUM_INPUTS;
+    const NUM_OUTPUT_RECORDS: usize = NUM_OUTPUTS;
 }
 
-impl BaseDPCComponents for Components {
+impl<const NUM_INPUTS: usize, const NUM_OUTPUTS: usize> BaseDPCComponents
+    for ComponentsWithArity<NUM_INPUTS, NUM_OUTPUTS>
+{
     type EncryptionGroup = EdwardsBls;
     type EncryptionModelParameters = EdwardsParameters;
-    type InnerSNARK = InnerSNARK;
+    type InnerSNARK = InnerSNARK<Self>;
     type InnerSNARKGadget = InnerSNARKGadget;
     type MerkleHashGadget = MerkleTreeCRHGadget;
     type MerkleParameters = CommitmentMerkleParameters;
     type NoopProgramSNARK = NoopProgramSNARK<Self>;
-    type OuterSNARK = OuterSNARK;
+    type OuterSNARK = OuterSNARK<Self>;
     type ProgramSNARKGadget = ProgramSNARKGadget;
 }
 
@@ -195,13 +225,16 @@ pub type AccountSignature = SchnorrSignature<EdwardsAffine, Blake2sHash>;
 
 pub type MerkleTreeCRH = BoweHopwoodPedersenCompressedCRH<EdwardsBls, TwoToOneWindow>;
 pub type EncryptedRecordCRH = BoweHopwoodPedersenCompressedCRH<EdwardsBls, EncryptedRecordWindow>;
-pub type InnerSNARKVerificationKeyCRH = BoweHopwoodPedersenCompressedCRH<EdwardsSW, InnerSNARKVkHashWindow>;
-pub type SerialNumberNonce = BoweHopwoodPedersenCompressedCRH<EdwardsBls, SnNonceWindow>;
+pub type InnerSNARKVerificationKeyCRH<const NUM_INPUTS: usize, const NUM_OUTPUTS: usize> =
+    BoweHopwoodPedersenCompressedCRH<EdwardsSW, InnerSNARKVkHashWindow<NUM_INPUTS, NUM_OUTPUTS>>;
+pub type SerialNumberNonce<const NUM_INPUTS: usize> =
+    BoweHopwoodPedersenCompressedCRH<EdwardsBls, SnNonceWindow<NUM_INPUTS>>;
 pub type ProgramVerificationKeyCRH = BoweHopwoodPedersenCompressedCRH<EdwardsSW, ProgramVkHashWindow>;
 
-pub type InnerSNARK = Groth16<InnerPairing, InnerCircuit<Components>, InnerCircuitVerifierInput<Components>>;
-pub type OuterSNARK = Groth16<OuterPairing, OuterCircuit<Components>, OuterCircuitVerifierInput<Components>>;
+pub type InnerSNARK<C> = Groth16<InnerPairing, InnerCircuit<C>, InnerCircuitVerifierInput<C>>;
+pub type OuterSNARK<C> = Groth16<OuterPairing, OuterCircuit<C>, OuterCircuitVerifierInput<C>>;
 pub type NoopProgramSNARK<C> = GM17<InnerPairing, NoopCircuit<C>, ProgramLocalData<C>>;
+pub type HashLockProgramSNARK<C> = GM17<InnerPairing, HashLockCircuit<C>, ProgramLocalData<C>>;
 pub type PRF = Blake2s;
 
 pub type Tx = DPCTransaction<Components>;
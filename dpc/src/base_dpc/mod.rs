@@ -15,12 +15,13 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    account::{Account, AccountAddress, AccountPrivateKey},
+    account::{Account, AccountAddress, AccountComputeKey, AccountPrivateKey},
     base_dpc::record_payload::RecordPayload,
-    errors::DPCError,
+    errors::{DPCError, TransactionVerificationError},
     traits::{AccountScheme, DPCComponents, DPCScheme, Record},
 };
 use snarkvm_algorithms::{
+    cfg_iter,
     commitment_tree::CommitmentMerkleTree,
     merkle_tree::{MerklePath, MerkleTreeDigest},
     traits::{
@@ -52,10 +53,17 @@ use snarkvm_utilities::{
 use itertools::{izip, Itertools};
 use rand::Rng;
 use std::{
+    collections::HashSet,
     io::{Read, Result as IoResult, Write},
     marker::PhantomData,
 };
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub mod genesis_builder;
+pub use genesis_builder::*;
+
 pub mod inner_circuit;
 pub use inner_circuit::*;
 
@@ -74,6 +82,9 @@ pub use record::*;
 pub mod transaction;
 pub use transaction::*;
 
+pub mod transaction_builder;
+pub use transaction_builder::*;
+
 pub mod instantiated;
 
 #[cfg(test)]
@@ -118,7 +129,10 @@ pub trait BaseDPCComponents: DPCComponents {
         VerifierInput = ProgramLocalData<Self>,
     >;
 
-    /// SNARK Verifier gadget for the "dummy program" that does nothing with its input.
+    /// SNARK Verifier gadget for the program SNARKs.
+    ///
+    /// It verifies the proofs of every program whose SNARK shares the proof system of
+    /// `NoopProgramSNARK`, not only the proofs of the "dummy program" (see `ProgramRegistry`).
     type ProgramSNARKGadget: SNARKVerifierGadget<Self::NoopProgramSNARK, Self::OuterField>;
 }
 
@@ -188,6 +202,87 @@ impl<Components: BaseDPCComponents> TransactionKernel<Components> {
             network_id: self.network_id,
         }
     }
+
+    /// Signs the transaction contents against the given ledger digest and returns a kernel that
+    /// can be proven by `DPC::execute_online_signed` without the account private keys.
+    ///
+    /// The prover must hold a ledger in which the digest is a current or past ledger digest.
+    pub fn sign<R: Rng>(
+        &self,
+        ledger_digest: MerkleTreeDigest<Components::MerkleParameters>,
+        rng: &mut R,
+    ) -> Result<SignedTransactionKernel<Components>, DPCError> {
+        let signature_time = start_timer!(|| "Sign and randomize transaction contents");
+
+        let local_data_root = self.local_data_merkle_tree.root();
+        let signature_message = to_bytes![
+            self.network_id,
+            ledger_digest,
+            self.old_serial_numbers,
+            self.new_commitments,
+            self.program_commitment,
+            local_data_root,
+            self.value_balance,
+            self.memorandum
+        ]?;
+
+        let mut old_account_compute_keys = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
+        let mut signatures = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
+        for (private_key, randomizer) in self.old_account_private_keys.iter().zip_eq(&self.old_randomizers) {
+            old_account_compute_keys.push(AccountComputeKey::from_private_key(
+                &self.system_parameters.account_signature,
+                private_key,
+            )?);
+
+            // Sign the transaction data
+            let account_signature = Components::AccountSignature::sign(
+                &self.system_parameters.account_signature,
+                &private_key.sk_sig,
+                &signature_message,
+                rng,
+            )?;
+
+            // Randomize the signature
+            let randomized_signature = Components::AccountSignature::randomize_signature(
+                &self.system_parameters.account_signature,
+                &account_signature,
+                randomizer,
+            )?;
+
+            signatures.push(randomized_signature);
+        }
+
+        end_timer!(signature_time);
+
+        Ok(SignedTransactionKernel {
+            system_parameters: self.system_parameters.clone(),
+
+            old_account_compute_keys,
+            old_records: self.old_records.clone(),
+            old_serial_numbers: self.old_serial_numbers.clone(),
+
+            ledger_digest,
+            signatures,
+
+            new_records: self.new_records.clone(),
+            new_sn_nonce_randomness: self.new_sn_nonce_randomness.clone(),
+            new_commitments: self.new_commitments.clone(),
+
+            new_records_encryption_randomness: self.new_records_encryption_randomness.clone(),
+            new_encrypted_records: self.new_encrypted_records.clone(),
+            new_encrypted_record_hashes: self.new_encrypted_record_hashes.clone(),
+
+            program_commitment: self.program_commitment.clone(),
+            program_randomness: self.program_randomness.clone(),
+
+            local_data_merkle_tree: self.local_data_merkle_tree.clone(),
+            local_data_commitment_randomizers: self.local_data_commitment_randomizers.clone(),
+
+            value_balance: self.value_balance,
+            memorandum: self.memorandum,
+            network_id: self.network_id,
+        })
+    }
 }
 
 impl<Components: BaseDPCComponents> ToBytes for TransactionKernel<Components> {
@@ -261,7 +356,7 @@ impl<Components: BaseDPCComponents> ToBytes for TransactionKernel<Components> {
 impl<Components: BaseDPCComponents> FromBytes for TransactionKernel<Components> {
     #[inline]
     fn read<R: Read>(mut reader: R) -> IoResult<Self> {
-        let system_parameters = SystemParameters::<Components>::load().expect("Could not load system parameters");
+        let system_parameters = SystemParameters::<Components>::load()?;
 
         // Read old record components
 
@@ -359,7 +454,7 @@ impl<Components: BaseDPCComponents> FromBytes for TransactionKernel<Components>
             .expect("Could not load local data merkle tree");
 
         let mut local_data_commitment_randomizers = vec![];
-        for _ in 0..4 {
+        for _ in 0..(Components::NUM_INPUT_RECORDS + Components::NUM_OUTPUT_RECORDS) {
             let local_data_commitment_randomizer: <Components::LocalDataCommitment as CommitmentScheme>::Randomness =
                 FromBytes::read(&mut reader)?;
             local_data_commitment_randomizers.push(local_data_commitment_randomizer);
@@ -396,491 +491,662 @@ impl<Components: BaseDPCComponents> FromBytes for TransactionKernel<Components>
     }
 }
 
-/// Stores local data required to produce program proofs.
-pub struct LocalData<Components: BaseDPCComponents> {
-    pub system_parameters: SystemParameters<Components>,
-
-    // Old records and serial numbers
-    pub old_records: Vec<DPCRecord<Components>>,
-    pub old_serial_numbers: Vec<<Components::AccountSignature as SignatureScheme>::PublicKey>,
+/// Returned by `TransactionKernel::sign`. Stores the signed transaction contents and the data
+/// required to prove them in `BaseDPC::execute_online_signed`.
+///
+/// The account private keys are replaced by account compute keys, which reveal the
+/// records and serial numbers of the accounts but can't sign new transaction contents.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "Components: BaseDPCComponents"),
+    PartialEq(bound = "Components: BaseDPCComponents"),
+    Eq(bound = "Components: BaseDPCComponents"),
+    Debug(bound = "Components: BaseDPCComponents")
+)]
+pub struct SignedTransactionKernel<Components: BaseDPCComponents> {
+    #[derivative(PartialEq = "ignore", Debug = "ignore")]
+    system_parameters: SystemParameters<Components>,
 
-    // New records
-    pub new_records: Vec<DPCRecord<Components>>,
+    // Old record stuff
+    old_account_compute_keys: Vec<AccountComputeKey<Components>>,
+    old_records: Vec<DPCRecord<Components>>,
+    old_serial_numbers: Vec<<Components::AccountSignature as SignatureScheme>::PublicKey>,
 
-    // Commitment to the above information.
-    pub local_data_merkle_tree: CommitmentMerkleTree<Components::LocalDataCommitment, Components::LocalDataCRH>,
-    pub local_data_commitment_randomizers: Vec<<Components::LocalDataCommitment as CommitmentScheme>::Randomness>,
+    // Signature stuff
+    ledger_digest: MerkleTreeDigest<Components::MerkleParameters>,
+    #[derivative(PartialEq = "ignore")]
+    signatures: Vec<<Components::AccountSignature as SignatureScheme>::Output>,
 
-    pub memorandum: <DPCTransaction<Components> as Transaction>::Memorandum,
-    pub network_id: u8,
-}
+    // New record stuff
+    new_records: Vec<DPCRecord<Components>>,
+    new_sn_nonce_randomness: Vec<[u8; 32]>,
+    new_commitments: Vec<<Components::RecordCommitment as CommitmentScheme>::Output>,
 
-///////////////////////////////////////////////////////////////////////////////
+    new_records_encryption_randomness: Vec<<Components::AccountEncryption as EncryptionScheme>::Randomness>,
+    new_encrypted_records: Vec<EncryptedRecord<Components>>,
+    new_encrypted_record_hashes: Vec<<Components::EncryptedRecordCRH as CRH>::Output>,
 
-impl<Components: BaseDPCComponents> DPC<Components> {
-    pub fn generate_system_parameters<R: Rng>(rng: &mut R) -> Result<SystemParameters<Components>, DPCError> {
-        let time = start_timer!(|| "Account commitment scheme setup");
-        let account_commitment = Components::AccountCommitment::setup(rng);
-        end_timer!(time);
+    // Program and local data root and randomness
+    program_commitment: <Components::ProgramVerificationKeyCommitment as CommitmentScheme>::Output,
+    program_randomness: <Components::ProgramVerificationKeyCommitment as CommitmentScheme>::Randomness,
 
-        let time = start_timer!(|| "Account encryption scheme setup");
-        let account_encryption = <Components::AccountEncryption as EncryptionScheme>::setup(rng);
-        end_timer!(time);
+    local_data_merkle_tree: CommitmentMerkleTree<Components::LocalDataCommitment, Components::LocalDataCRH>,
+    local_data_commitment_randomizers: Vec<<Components::LocalDataCommitment as CommitmentScheme>::Randomness>,
 
-        let time = start_timer!(|| "Account signature setup");
-        let account_signature = Components::AccountSignature::setup(rng)?;
-        end_timer!(time);
+    value_balance: AleoAmount,
+    memorandum: <DPCTransaction<Components> as Transaction>::Memorandum,
+    network_id: u8,
+}
 
-        let time = start_timer!(|| "Encrypted record CRH setup");
-        let encrypted_record_crh = Components::EncryptedRecordCRH::setup(rng);
-        end_timer!(time);
+impl<Components: BaseDPCComponents> SignedTransactionKernel<Components> {
+    #[allow(clippy::wrong_self_convention)]
+    pub fn into_local_data(&self) -> LocalData<Components> {
+        LocalData {
+            system_parameters: self.system_parameters.clone(),
 
-        let time = start_timer!(|| "Inner SNARK verification key CRH setup");
-        let inner_snark_verification_key_crh = Components::InnerSNARKVerificationKeyCRH::setup(rng);
-        end_timer!(time);
+            old_records: self.old_records.to_vec(),
+            old_serial_numbers: self.old_serial_numbers.to_vec(),
 
-        let time = start_timer!(|| "Local data commitment setup");
-        let local_data_commitment = Components::LocalDataCommitment::setup(rng);
-        end_timer!(time);
+            new_records: self.new_records.to_vec(),
 
-        let time = start_timer!(|| "Local data CRH setup");
-        let local_data_crh = Components::LocalDataCRH::setup(rng);
-        end_timer!(time);
+            local_data_merkle_tree: self.local_data_merkle_tree.clone(),
+            local_data_commitment_randomizers: self.local_data_commitment_randomizers.clone(),
 
-        let time = start_timer!(|| "Program verification key CRH setup");
-        let program_verification_key_crh = Components::ProgramVerificationKeyCRH::setup(rng);
-        end_timer!(time);
+            memorandum: self.memorandum,
+            network_id: self.network_id,
+        }
+    }
 
-        let time = start_timer!(|| "Program verification key commitment setup");
-        let program_verification_key_commitment = Components::ProgramVerificationKeyCommitment::setup(rng);
-        end_timer!(time);
+    /// Returns the ledger digest that the transaction contents were signed against.
+    pub fn ledger_digest(&self) -> &MerkleTreeDigest<Components::MerkleParameters> {
+        &self.ledger_digest
+    }
 
-        let time = start_timer!(|| "Record commitment scheme setup");
-        let record_commitment = Components::RecordCommitment::setup(rng);
-        end_timer!(time);
+    /// Returns the index of the first input whose signature does not match the transaction contents.
+    fn find_invalid_signature(
+        &self,
+        system_parameters: &SystemParameters<Components>,
+    ) -> Result<Option<usize>, DPCError> {
+        let signature_message = to_bytes![
+            self.network_id,
+            self.ledger_digest,
+            self.old_serial_numbers,
+            self.new_commitments,
+            self.program_commitment,
+            self.local_data_merkle_tree.root(),
+            self.value_balance,
+            self.memorandum
+        ]?;
 
-        let time = start_timer!(|| "Serial nonce CRH setup");
-        let serial_number_nonce = Components::SerialNumberNonceCRH::setup(rng);
-        end_timer!(time);
+        for (i, (serial_number, signature)) in self.old_serial_numbers.iter().zip_eq(&self.signatures).enumerate() {
+            if !Components::AccountSignature::verify(
+                &system_parameters.account_signature,
+                serial_number,
+                &signature_message,
+                signature,
+            )? {
+                return Ok(Some(i));
+            }
+        }
 
-        Ok(SystemParameters {
-            account_commitment,
-            account_encryption,
-            account_signature,
-            encrypted_record_crh,
-            inner_snark_verification_key_crh,
-            local_data_crh,
-            local_data_commitment,
-            program_verification_key_commitment,
-            program_verification_key_crh,
-            record_commitment,
-            serial_number_nonce,
-        })
+        Ok(None)
     }
+}
 
-    pub fn generate_noop_program_snark_parameters<R: Rng>(
-        system_parameters: &SystemParameters<Components>,
-        rng: &mut R,
-    ) -> Result<NoopProgramSNARKParameters<Components>, DPCError> {
-        let (pk, pvk) = Components::NoopProgramSNARK::setup(&NoopCircuit::blank(system_parameters), rng)?;
+impl<Components: BaseDPCComponents> ToBytes for SignedTransactionKernel<Components> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write old record components
 
-        Ok(NoopProgramSNARKParameters {
-            proving_key: pk,
-            verification_key: pvk.into(),
-        })
-    }
+        for old_account_compute_key in &self.old_account_compute_keys {
+            old_account_compute_key.write(&mut writer)?;
+        }
 
-    pub fn generate_sn(
-        system_parameters: &SystemParameters<Components>,
-        record: &DPCRecord<Components>,
-        account_private_key: &AccountPrivateKey<Components>,
-    ) -> Result<(<Components::AccountSignature as SignatureScheme>::PublicKey, Vec<u8>), DPCError> {
-        let sn_time = start_timer!(|| "Generate serial number");
-        let sk_prf = &account_private_key.sk_prf;
-        let sn_nonce = to_bytes!(record.serial_number_nonce())?;
-        // Compute the serial number.
-        let prf_input = FromBytes::read(sn_nonce.as_slice())?;
-        let prf_seed = FromBytes::read(to_bytes!(sk_prf)?.as_slice())?;
-        let sig_and_pk_randomizer = to_bytes![Components::PRF::evaluate(&prf_seed, &prf_input)?]?;
+        for old_record in &self.old_records {
+            old_record.write(&mut writer)?;
+        }
 
-        let sn = Components::AccountSignature::randomize_public_key(
-            &system_parameters.account_signature,
-            &account_private_key.pk_sig(&system_parameters.account_signature)?,
-            &sig_and_pk_randomizer,
-        )?;
-        end_timer!(sn_time);
-        Ok((sn, sig_and_pk_randomizer))
-    }
+        for old_serial_number in &self.old_serial_numbers {
+            old_serial_number.write(&mut writer)?;
+        }
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn generate_record<R: Rng>(
-        system_parameters: &SystemParameters<Components>,
-        sn_nonce: <Components::SerialNumberNonceCRH as CRH>::Output,
-        owner: AccountAddress<Components>,
-        is_dummy: bool,
-        value: u64,
-        payload: RecordPayload,
-        birth_program_id: Vec<u8>,
-        death_program_id: Vec<u8>,
-        rng: &mut R,
-    ) -> Result<DPCRecord<Components>, DPCError> {
-        let record_time = start_timer!(|| "Generate record");
-        // Sample new commitment randomness.
-        let commitment_randomness = <Components::RecordCommitment as CommitmentScheme>::Randomness::rand(rng);
+        // Write signature components
 
-        // Total = 32 + 1 + 8 + 32 + 48 + 48 + 32 = 201 bytes
-        let commitment_input = to_bytes![
-            owner,            // 256 bits = 32 bytes
-            is_dummy,         // 1 bit = 1 byte
-            value,            // 64 bits = 8 bytes
-            payload,          // 256 bits = 32 bytes
-            birth_program_id, // 384 bits = 48 bytes
-            death_program_id, // 384 bits = 48 bytes
-            sn_nonce          // 256 bits = 32 bytes
-        ]?;
+        self.ledger_digest.write(&mut writer)?;
 
-        let commitment = Components::RecordCommitment::commit(
-            &system_parameters.record_commitment,
-            &commitment_input,
-            &commitment_randomness,
-        )?;
+        for signature in &self.signatures {
+            signature.write(&mut writer)?;
+        }
 
-        let record = DPCRecord {
-            owner,
-            is_dummy,
-            value,
-            payload,
-            birth_program_id,
-            death_program_id,
-            serial_number_nonce: sn_nonce,
-            commitment,
-            commitment_randomness,
-            _components: PhantomData,
-        };
-        end_timer!(record_time);
-        Ok(record)
-    }
-}
+        // Write new record components
 
-impl<Components: BaseDPCComponents, L: LedgerScheme> DPCScheme<L> for DPC<Components>
-where
-    L: LedgerScheme<
-        Commitment = <Components::RecordCommitment as CommitmentScheme>::Output,
-        MerkleParameters = Components::MerkleParameters,
-        MerklePath = MerklePath<Components::MerkleParameters>,
-        MerkleTreeDigest = MerkleTreeDigest<Components::MerkleParameters>,
-        SerialNumber = <Components::AccountSignature as SignatureScheme>::PublicKey,
-        Transaction = DPCTransaction<Components>,
-    >,
-{
-    type Account = Account<Components>;
-    type LocalData = LocalData<Components>;
-    type NetworkParameters = PublicParameters<Components>;
-    type Payload = <Self::Record as Record>::Payload;
-    type PrivateProgramInput = PrivateProgramInput;
-    type Record = DPCRecord<Components>;
-    type SystemParameters = SystemParameters<Components>;
-    type Transaction = DPCTransaction<Components>;
-    type TransactionKernel = TransactionKernel<Components>;
+        for new_record in &self.new_records {
+            new_record.write(&mut writer)?;
+        }
 
-    fn setup<R: Rng>(
-        ledger_parameters: &Components::MerkleParameters,
-        rng: &mut R,
-    ) -> anyhow::Result<Self::NetworkParameters> {
-        let setup_time = start_timer!(|| "BaseDPC::setup");
-        let system_parameters = Self::generate_system_parameters(rng)?;
+        for new_sn_nonce_randomness in &self.new_sn_nonce_randomness {
+            new_sn_nonce_randomness.write(&mut writer)?;
+        }
 
-        let program_snark_setup_time = start_timer!(|| "Dummy program SNARK setup");
-        let noop_program_snark_parameters = Self::generate_noop_program_snark_parameters(&system_parameters, rng)?;
-        let program_snark_proof = Components::NoopProgramSNARK::prove(
-            &noop_program_snark_parameters.proving_key,
-            &NoopCircuit::blank(&system_parameters),
-            rng,
-        )?;
-        end_timer!(program_snark_setup_time);
+        for new_commitment in &self.new_commitments {
+            new_commitment.write(&mut writer)?;
+        }
 
-        let program_snark_vk_and_proof = PrivateProgramInput {
-            verification_key: to_bytes![noop_program_snark_parameters.verification_key]?,
-            proof: to_bytes![program_snark_proof]?,
-        };
+        for new_records_encryption_randomness in &self.new_records_encryption_randomness {
+            new_records_encryption_randomness.write(&mut writer)?;
+        }
 
-        let snark_setup_time = start_timer!(|| "Execute inner SNARK setup");
-        let inner_circuit = InnerCircuit::blank(&system_parameters, ledger_parameters);
-        let inner_snark_parameters = Components::InnerSNARK::setup(&inner_circuit, rng)?;
-        end_timer!(snark_setup_time);
+        for new_encrypted_record in &self.new_encrypted_records {
+            new_encrypted_record.write(&mut writer)?;
+        }
 
-        let snark_setup_time = start_timer!(|| "Execute outer SNARK setup");
-        let inner_snark_vk: <Components::InnerSNARK as SNARK>::VerificationParameters =
-            inner_snark_parameters.1.clone().into();
-        let inner_snark_proof = Components::InnerSNARK::prove(&inner_snark_parameters.0, &inner_circuit, rng)?;
+        for new_encrypted_record_hash in &self.new_encrypted_record_hashes {
+            new_encrypted_record_hash.write(&mut writer)?;
+        }
 
-        let outer_snark_parameters = Components::OuterSNARK::setup(
-            &OuterCircuit::blank(
-                system_parameters.clone(),
-                ledger_parameters.clone(),
-                inner_snark_vk,
-                inner_snark_proof,
-                program_snark_vk_and_proof,
-            ),
-            rng,
-        )?;
-        end_timer!(snark_setup_time);
-        end_timer!(setup_time);
+        // Write transaction components
 
-        let inner_snark_parameters = (Some(inner_snark_parameters.0), inner_snark_parameters.1);
-        let outer_snark_parameters = (Some(outer_snark_parameters.0), outer_snark_parameters.1);
+        self.program_commitment.write(&mut writer)?;
+        self.program_randomness.write(&mut writer)?;
 
-        Ok(PublicParameters {
+        self.local_data_merkle_tree.write(&mut writer)?;
+
+        for local_data_commitment_randomizer in &self.local_data_commitment_randomizers {
+            local_data_commitment_randomizer.write(&mut writer)?;
+        }
+
+        self.value_balance.write(&mut writer)?;
+        self.memorandum.write(&mut writer)?;
+        self.network_id.write(&mut writer)
+    }
+}
+
+impl<Components: BaseDPCComponents> FromBytes for SignedTransactionKernel<Components> {
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let system_parameters = SystemParameters::<Components>::load()?;
+
+        // Read old record components
+
+        let mut old_account_compute_keys = vec![];
+        for _ in 0..Components::NUM_INPUT_RECORDS {
+            let old_account_compute_key: AccountComputeKey<Components> = FromBytes::read(&mut reader)?;
+            old_account_compute_keys.push(old_account_compute_key);
+        }
+
+        let mut old_records = vec![];
+        for _ in 0..Components::NUM_INPUT_RECORDS {
+            let old_record: DPCRecord<Components> = FromBytes::read(&mut reader)?;
+            old_records.push(old_record);
+        }
+
+        let mut old_serial_numbers = vec![];
+        for _ in 0..Components::NUM_INPUT_RECORDS {
+            let old_serial_number: <Components::AccountSignature as SignatureScheme>::PublicKey =
+                FromBytes::read(&mut reader)?;
+            old_serial_numbers.push(old_serial_number);
+        }
+
+        // Read signature components
+
+        let ledger_digest: MerkleTreeDigest<Components::MerkleParameters> = FromBytes::read(&mut reader)?;
+
+        let mut signatures = vec![];
+        for _ in 0..Components::NUM_INPUT_RECORDS {
+            let signature: <Components::AccountSignature as SignatureScheme>::Output = FromBytes::read(&mut reader)?;
+            signatures.push(signature);
+        }
+
+        // Read new record components
+
+        let mut new_records = vec![];
+        for _ in 0..Components::NUM_OUTPUT_RECORDS {
+            let new_record: DPCRecord<Components> = FromBytes::read(&mut reader)?;
+            new_records.push(new_record);
+        }
+
+        let mut new_sn_nonce_randomness = vec![];
+        for _ in 0..Components::NUM_OUTPUT_RECORDS {
+            let randomness: [u8; 32] = FromBytes::read(&mut reader)?;
+            new_sn_nonce_randomness.push(randomness);
+        }
+
+        let mut new_commitments = vec![];
+        for _ in 0..Components::NUM_OUTPUT_RECORDS {
+            let new_commitment: <Components::RecordCommitment as CommitmentScheme>::Output =
+                FromBytes::read(&mut reader)?;
+            new_commitments.push(new_commitment);
+        }
+
+        let mut new_records_encryption_randomness = vec![];
+        for _ in 0..Components::NUM_OUTPUT_RECORDS {
+            let encryption_randomness: <Components::AccountEncryption as EncryptionScheme>::Randomness =
+                FromBytes::read(&mut reader)?;
+            new_records_encryption_randomness.push(encryption_randomness);
+        }
+
+        let mut new_encrypted_records = vec![];
+        for _ in 0..Components::NUM_OUTPUT_RECORDS {
+            let encrypted_record: EncryptedRecord<Components> = FromBytes::read(&mut reader)?;
+            new_encrypted_records.push(encrypted_record);
+        }
+
+        let mut new_encrypted_record_hashes = vec![];
+        for _ in 0..Components::NUM_OUTPUT_RECORDS {
+            let encrypted_record_hash: <Components::EncryptedRecordCRH as CRH>::Output = FromBytes::read(&mut reader)?;
+            new_encrypted_record_hashes.push(encrypted_record_hash);
+        }
+
+        // Read transaction components
+
+        let program_commitment: <Components::ProgramVerificationKeyCommitment as CommitmentScheme>::Output =
+            FromBytes::read(&mut reader)?;
+        let program_randomness: <Components::ProgramVerificationKeyCommitment as CommitmentScheme>::Randomness =
+            FromBytes::read(&mut reader)?;
+
+        let local_data_merkle_tree =
+            CommitmentMerkleTree::<Components::LocalDataCommitment, Components::LocalDataCRH>::from_bytes(
+                &mut reader,
+                system_parameters.local_data_crh.clone(),
+            )?;
+
+        let mut local_data_commitment_randomizers = vec![];
+        for _ in 0..(Components::NUM_INPUT_RECORDS + Components::NUM_OUTPUT_RECORDS) {
+            let local_data_commitment_randomizer: <Components::LocalDataCommitment as CommitmentScheme>::Randomness =
+                FromBytes::read(&mut reader)?;
+            local_data_commitment_randomizers.push(local_data_commitment_randomizer);
+        }
+
+        let value_balance: AleoAmount = FromBytes::read(&mut reader)?;
+        let memorandum: <DPCTransaction<Components> as Transaction>::Memorandum = FromBytes::read(&mut reader)?;
+        let network_id: u8 = FromBytes::read(&mut reader)?;
+
+        Ok(Self {
             system_parameters,
-            noop_program_snark_parameters,
-            inner_snark_parameters,
-            outer_snark_parameters,
+
+            old_account_compute_keys,
+            old_records,
+            old_serial_numbers,
+
+            ledger_digest,
+            signatures,
+
+            new_records,
+            new_sn_nonce_randomness,
+            new_commitments,
+
+            new_records_encryption_randomness,
+            new_encrypted_records,
+            new_encrypted_record_hashes,
+
+            program_commitment,
+            program_randomness,
+            local_data_merkle_tree,
+            local_data_commitment_randomizers,
+            value_balance,
+            memorandum,
+            network_id,
         })
     }
+}
 
-    fn create_account<R: Rng>(parameters: &Self::SystemParameters, rng: &mut R) -> anyhow::Result<Self::Account> {
-        let time = start_timer!(|| "BaseDPC::create_account");
-        let account = Account::new(
-            &parameters.account_signature,
-            &parameters.account_commitment,
-            &parameters.account_encryption,
-            rng,
-        )?;
+/// Stores local data required to produce program proofs.
+pub struct LocalData<Components: BaseDPCComponents> {
+    pub system_parameters: SystemParameters<Components>,
+
+    // Old records and serial numbers
+    pub old_records: Vec<DPCRecord<Components>>,
+    pub old_serial_numbers: Vec<<Components::AccountSignature as SignatureScheme>::PublicKey>,
+
+    // New records
+    pub new_records: Vec<DPCRecord<Components>>,
+
+    // Commitment to the above information.
+    pub local_data_merkle_tree: CommitmentMerkleTree<Components::LocalDataCommitment, Components::LocalDataCRH>,
+    pub local_data_commitment_randomizers: Vec<<Components::LocalDataCommitment as CommitmentScheme>::Randomness>,
+
+    pub memorandum: <DPCTransaction<Components> as Transaction>::Memorandum,
+    pub network_id: u8,
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+impl<Components: BaseDPCComponents> DPC<Components> {
+    pub fn generate_system_parameters<R: Rng>(rng: &mut R) -> Result<SystemParameters<Components>, DPCError> {
+        let time = start_timer!(|| "Account commitment scheme setup");
+        let account_commitment = Components::AccountCommitment::setup(rng);
         end_timer!(time);
 
-        Ok(account)
-    }
+        let time = start_timer!(|| "Account encryption scheme setup");
+        let account_encryption = <Components::AccountEncryption as EncryptionScheme>::setup(rng);
+        end_timer!(time);
 
-    fn execute_offline<R: Rng>(
-        parameters: Self::SystemParameters,
-        old_records: Vec<Self::Record>,
-        old_account_private_keys: Vec<<Self::Account as AccountScheme>::AccountPrivateKey>,
-        new_record_owners: Vec<<Self::Account as AccountScheme>::AccountAddress>,
-        new_is_dummy_flags: &[bool],
-        new_values: &[u64],
-        new_payloads: Vec<Self::Payload>,
-        new_birth_program_ids: Vec<Vec<u8>>,
-        new_death_program_ids: Vec<Vec<u8>>,
-        memorandum: <Self::Transaction as Transaction>::Memorandum,
-        network_id: u8,
-        rng: &mut R,
-    ) -> anyhow::Result<Self::TransactionKernel> {
-        assert_eq!(Components::NUM_INPUT_RECORDS, old_records.len());
-        assert_eq!(Components::NUM_INPUT_RECORDS, old_account_private_keys.len());
+        let time = start_timer!(|| "Account signature setup");
+        let account_signature = Components::AccountSignature::setup(rng)?;
+        end_timer!(time);
 
-        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_record_owners.len());
-        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_is_dummy_flags.len());
-        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_payloads.len());
-        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_birth_program_ids.len());
-        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_death_program_ids.len());
+        let time = start_timer!(|| "Encrypted record CRH setup");
+        let encrypted_record_crh = Components::EncryptedRecordCRH::setup(rng);
+        end_timer!(time);
 
-        let mut old_serial_numbers = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
-        let mut old_randomizers = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
-        let mut joint_serial_numbers = Vec::new();
-        let mut old_death_program_ids = Vec::with_capacity(old_records.len());
+        let time = start_timer!(|| "Inner SNARK verification key CRH setup");
+        let inner_snark_verification_key_crh = Components::InnerSNARKVerificationKeyCRH::setup(rng);
+        end_timer!(time);
 
-        let mut value_balance = AleoAmount::ZERO;
+        let time = start_timer!(|| "Local data commitment setup");
+        let local_data_commitment = Components::LocalDataCommitment::setup(rng);
+        end_timer!(time);
 
-        // Compute the ledger membership witness and serial number from the old records.
-        for (i, record) in old_records.iter().enumerate() {
-            let input_record_time = start_timer!(|| format!("Process input record {}", i));
+        let time = start_timer!(|| "Local data CRH setup");
+        let local_data_crh = Components::LocalDataCRH::setup(rng);
+        end_timer!(time);
 
-            if !record.is_dummy() {
-                value_balance = value_balance.add(AleoAmount::from_bytes(record.value() as i64));
-            }
+        let time = start_timer!(|| "Program verification key CRH setup");
+        let program_verification_key_crh = Components::ProgramVerificationKeyCRH::setup(rng);
+        end_timer!(time);
 
-            let (sn, randomizer) = Self::generate_sn(&parameters, record, &old_account_private_keys[i])?;
-            joint_serial_numbers.extend_from_slice(&to_bytes![sn]?);
-            old_serial_numbers.push(sn);
-            old_randomizers.push(randomizer);
-            old_death_program_ids.push(record.death_program_id().to_vec());
+        let time = start_timer!(|| "Program verification key commitment setup");
+        let program_verification_key_commitment = Components::ProgramVerificationKeyCommitment::setup(rng);
+        end_timer!(time);
 
-            end_timer!(input_record_time);
-        }
+        let time = start_timer!(|| "Record commitment scheme setup");
+        let record_commitment = Components::RecordCommitment::setup(rng);
+        end_timer!(time);
+
+        let time = start_timer!(|| "Serial nonce CRH setup");
+        let serial_number_nonce = Components::SerialNumberNonceCRH::setup(rng);
+        end_timer!(time);
+
+        Ok(SystemParameters {
+            account_commitment,
+            account_encryption,
+            account_signature,
+            encrypted_record_crh,
+            inner_snark_verification_key_crh,
+            local_data_crh,
+            local_data_commitment,
+            program_verification_key_commitment,
+            program_verification_key_crh,
+            record_commitment,
+            serial_number_nonce,
+        })
+    }
+
+    pub fn generate_noop_program_snark_parameters<R: Rng>(
+        system_parameters: &SystemParameters<Components>,
+        rng: &mut R,
+    ) -> Result<NoopProgramSNARKParameters<Components>, DPCError> {
+        let (pk, pvk) = Components::NoopProgramSNARK::setup(&NoopCircuit::blank(system_parameters), rng)?;
 
-        let mut new_records = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
-        let mut new_commitments = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
-        let mut new_sn_nonce_randomness = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
+        Ok(NoopProgramSNARKParameters {
+            proving_key: pk,
+            verification_key: pvk.into(),
+        })
+    }
 
-        // Generate new records and commitments for them.
-        for (j, (new_record_owner, new_payload, new_death_program_id)) in
-            izip!(new_record_owners, new_payloads, new_death_program_ids).enumerate()
-        {
-            if j == Components::NUM_OUTPUT_RECORDS {
-                break;
-            }
+    pub fn generate_sn(
+        system_parameters: &SystemParameters<Components>,
+        record: &DPCRecord<Components>,
+        account_private_key: &AccountPrivateKey<Components>,
+    ) -> Result<(<Components::AccountSignature as SignatureScheme>::PublicKey, Vec<u8>), DPCError> {
+        let sn_time = start_timer!(|| "Generate serial number");
+        let sk_prf = &account_private_key.sk_prf;
+        let sn_nonce = to_bytes!(record.serial_number_nonce())?;
+        // Compute the serial number.
+        let prf_input = FromBytes::read(sn_nonce.as_slice())?;
+        let prf_seed = FromBytes::read(to_bytes!(sk_prf)?.as_slice())?;
+        let sig_and_pk_randomizer = to_bytes![Components::PRF::evaluate(&prf_seed, &prf_input)?]?;
 
-            let output_record_time = start_timer!(|| format!("Process output record {}", j));
-            let sn_nonce_time = start_timer!(|| "Generate serial number nonce");
+        let sn = Components::AccountSignature::randomize_public_key(
+            &system_parameters.account_signature,
+            &account_private_key.pk_sig(&system_parameters.account_signature)?,
+            &sig_and_pk_randomizer,
+        )?;
+        end_timer!(sn_time);
+        Ok((sn, sig_and_pk_randomizer))
+    }
 
-            // Sample randomness sn_randomness for the CRH input.
-            let sn_randomness: [u8; 32] = rng.gen();
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_record<R: Rng>(
+        system_parameters: &SystemParameters<Components>,
+        sn_nonce: <Components::SerialNumberNonceCRH as CRH>::Output,
+        owner: AccountAddress<Components>,
+        is_dummy: bool,
+        value: u64,
+        payload: RecordPayload,
+        birth_program_id: Vec<u8>,
+        death_program_id: Vec<u8>,
+        rng: &mut R,
+    ) -> Result<DPCRecord<Components>, DPCError> {
+        let record_time = start_timer!(|| "Generate record");
+        // Sample new commitment randomness.
+        let commitment_randomness = <Components::RecordCommitment as CommitmentScheme>::Randomness::rand(rng);
 
-            let crh_input = to_bytes![j as u8, sn_randomness, joint_serial_numbers]?;
-            let sn_nonce = Components::SerialNumberNonceCRH::hash(&parameters.serial_number_nonce, &crh_input)?;
+        // Total = 32 + 1 + 8 + 32 + 48 + 48 + 32 = 201 bytes
+        let commitment_input = to_bytes![
+            owner,            // 256 bits = 32 bytes
+            is_dummy,         // 1 bit = 1 byte
+            value,            // 64 bits = 8 bytes
+            payload,          // 256 bits = 32 bytes
+            birth_program_id, // 384 bits = 48 bytes
+            death_program_id, // 384 bits = 48 bytes
+            sn_nonce          // 256 bits = 32 bytes
+        ]?;
 
-            end_timer!(sn_nonce_time);
+        let commitment = Components::RecordCommitment::commit(
+            &system_parameters.record_commitment,
+            &commitment_input,
+            &commitment_randomness,
+        )?;
 
-            let record = Self::generate_record(
-                &parameters,
-                sn_nonce,
-                new_record_owner,
-                new_is_dummy_flags[j],
-                new_values[j],
-                new_payload,
-                new_birth_program_ids[j].clone(),
-                new_death_program_id,
-                rng,
-            )?;
+        let record = DPCRecord {
+            owner,
+            is_dummy,
+            value,
+            payload,
+            birth_program_id,
+            death_program_id,
+            serial_number_nonce: sn_nonce,
+            commitment,
+            commitment_randomness,
+            _components: PhantomData,
+        };
+        end_timer!(record_time);
+        Ok(record)
+    }
 
-            if !record.is_dummy() {
-                value_balance = value_balance.sub(AleoAmount::from_bytes(record.value() as i64));
-            }
+    /// Returns the hash of the inner SNARK verification key, which is a public input of the outer SNARK.
+    fn inner_snark_id(
+        parameters: &PublicParameters<Components>,
+    ) -> anyhow::Result<<Components::InnerSNARKVerificationKeyCRH as CRH>::Output> {
+        let inner_snark_vk: <<Components as BaseDPCComponents>::InnerSNARK as SNARK>::VerificationParameters =
+            parameters.inner_snark_parameters.1.clone().into();
 
-            new_commitments.push(record.commitment().clone());
-            new_sn_nonce_randomness.push(sn_randomness);
-            new_records.push(record);
+        Ok(Components::InnerSNARKVerificationKeyCRH::hash(
+            &parameters.system_parameters.inner_snark_verification_key_crh,
+            &to_bytes![inner_snark_vk]?,
+        )?)
+    }
 
-            end_timer!(output_record_time);
+    /// Returns `Ok(())` iff the transaction does not conflict with itself or with the ledger.
+    fn verify_against_ledger<L>(
+        transaction: &DPCTransaction<Components>,
+        ledger: &L,
+    ) -> Result<(), TransactionVerificationError>
+    where
+        L: LedgerScheme<
+            Commitment = <Components::RecordCommitment as CommitmentScheme>::Output,
+            MerkleTreeDigest = MerkleTreeDigest<Components::MerkleParameters>,
+            SerialNumber = <Components::AccountSignature as SignatureScheme>::PublicKey,
+            Transaction = DPCTransaction<Components>,
+        >,
+    {
+        // Returns an error if the transaction is for a different network than the ledger.
+        if let Some(network_id) = ledger.network_id() {
+            if transaction.network_id() != network_id {
+                return Err(TransactionVerificationError::NetworkMismatch(
+                    network_id,
+                    transaction.network_id(),
+                ));
+            }
         }
 
-        // TODO (raychu86) Add index and program register inputs + outputs to local data commitment leaves
-        let local_data_merkle_tree_timer = start_timer!(|| "Compute local data merkle tree");
-
-        let mut local_data_commitment_randomizers = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
+        // Returns an error if there are duplicate serial numbers in the transaction.
+        if has_duplicates(transaction.old_serial_numbers().iter()) {
+            return Err(TransactionVerificationError::DuplicateSn);
+        }
 
-        let mut old_record_commitments = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
-        for i in 0..Components::NUM_INPUT_RECORDS {
-            let record = &old_records[i];
-            let input_bytes = to_bytes![old_serial_numbers[i], record.commitment(), memorandum, network_id]?;
+        // Returns an error if there are duplicate commitments numbers in the transaction.
+        if has_duplicates(transaction.new_commitments().iter()) {
+            return Err(TransactionVerificationError::DuplicateCm);
+        }
 
-            let commitment_randomness = <Components::LocalDataCommitment as CommitmentScheme>::Randomness::rand(rng);
-            let commitment = Components::LocalDataCommitment::commit(
-                &parameters.local_data_commitment,
-                &input_bytes,
-                &commitment_randomness,
-            )?;
+        let ledger_time = start_timer!(|| "Ledger checks");
 
-            old_record_commitments.push(commitment);
-            local_data_commitment_randomizers.push(commitment_randomness);
+        // Returns an error if the transaction memo previously existed in the ledger.
+        if ledger.contains_memo(transaction.memorandum()) {
+            return Err(TransactionVerificationError::ExistingMemo);
         }
 
-        let mut new_record_commitments = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
-        for record in new_records.iter().take(Components::NUM_OUTPUT_RECORDS) {
-            let input_bytes = to_bytes![record.commitment(), memorandum, network_id]?;
+        // Returns an error if any transaction serial number previously existed in the ledger.
+        for (index, sn) in transaction.old_serial_numbers().iter().enumerate() {
+            if ledger.contains_sn(sn) {
+                return Err(TransactionVerificationError::ExistingSn(index));
+            }
+        }
 
-            let commitment_randomness = <Components::LocalDataCommitment as CommitmentScheme>::Randomness::rand(rng);
-            let commitment = Components::LocalDataCommitment::commit(
-                &parameters.local_data_commitment,
-                &input_bytes,
-                &commitment_randomness,
-            )?;
+        // Returns an error if any transaction commitment previously existed in the ledger.
+        for (index, cm) in transaction.new_commitments().iter().enumerate() {
+            if ledger.contains_cm(cm) {
+                return Err(TransactionVerificationError::ExistingCm(index));
+            }
+        }
 
-            new_record_commitments.push(commitment);
-            local_data_commitment_randomizers.push(commitment_randomness);
+        // Returns an error if the ledger digest in the transaction is invalid.
+        if !ledger.validate_digest(&transaction.ledger_digest) {
+            return Err(TransactionVerificationError::InvalidLedgerDigest);
         }
 
-        let leaves = [
-            old_record_commitments[0].clone(),
-            old_record_commitments[1].clone(),
-            new_record_commitments[0].clone(),
-            new_record_commitments[1].clone(),
-        ];
-        let local_data_merkle_tree = CommitmentMerkleTree::new(parameters.local_data_crh.clone(), &leaves)?;
+        end_timer!(ledger_time);
 
-        end_timer!(local_data_merkle_tree_timer);
+        Ok(())
+    }
 
-        let program_comm_timer = start_timer!(|| "Compute program commitment");
-        let (program_commitment, program_randomness) = {
-            let mut input = Vec::new();
-            for id in old_death_program_ids {
-                input.extend_from_slice(&id);
-            }
+    /// Verifies the transaction signatures, and returns the public input of the transaction outer proof.
+    fn outer_snark_verifier_input(
+        parameters: &PublicParameters<Components>,
+        transaction: &DPCTransaction<Components>,
+        ledger_parameters: &Components::MerkleParameters,
+        inner_snark_id: &<Components::InnerSNARKVerificationKeyCRH as CRH>::Output,
+    ) -> anyhow::Result<Result<OuterCircuitVerifierInput<Components>, TransactionVerificationError>> {
+        let signature_time = start_timer!(|| "Signature checks");
 
-            for id in new_birth_program_ids {
-                input.extend_from_slice(&id);
-            }
-            let program_randomness =
-                <Components::ProgramVerificationKeyCommitment as CommitmentScheme>::Randomness::rand(rng);
-            let program_commitment = Components::ProgramVerificationKeyCommitment::commit(
-                &parameters.program_verification_key_commitment,
-                &input,
-                &program_randomness,
-            )?;
-            (program_commitment, program_randomness)
-        };
-        end_timer!(program_comm_timer);
+        let signature_message = &to_bytes![
+            transaction.network_id(),
+            transaction.ledger_digest(),
+            transaction.old_serial_numbers(),
+            transaction.new_commitments(),
+            transaction.program_commitment(),
+            transaction.local_data_root(),
+            transaction.value_balance(),
+            transaction.memorandum()
+        ]?;
 
-        // Encrypt the new records
+        let account_signature = &parameters.system_parameters.account_signature;
+        for (index, (pk, sig)) in transaction
+            .old_serial_numbers()
+            .iter()
+            .zip(&transaction.signatures)
+            .enumerate()
+        {
+            if !Components::AccountSignature::verify(account_signature, pk, signature_message, sig)? {
+                return Ok(Err(TransactionVerificationError::InvalidSignature(index)));
+            }
+        }
 
-        let mut new_records_encryption_randomness = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
-        let mut new_encrypted_records = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
+        end_timer!(signature_time);
 
-        for record in &new_records {
-            let (record_encryption_randomness, encrypted_record) =
-                RecordEncryption::encrypt_record(&parameters, record, rng)?;
+        // Construct the ciphertext hashes
 
-            new_records_encryption_randomness.push(record_encryption_randomness);
-            new_encrypted_records.push(encrypted_record);
+        if transaction.encrypted_records.len() != transaction.new_commitments().len() {
+            return Ok(Err(TransactionVerificationError::EncryptedRecordCountMismatch(
+                transaction.encrypted_records.len(),
+                transaction.new_commitments().len(),
+            )));
         }
 
-        // Construct the ciphertext hashes
-
         let mut new_encrypted_record_hashes = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
-        for encrypted_record in &new_encrypted_records {
-            let encrypted_record_hash = RecordEncryption::encrypted_record_hash(&parameters, &encrypted_record)?;
+        for encrypted_record in &transaction.encrypted_records {
+            let encrypted_record_hash =
+                RecordEncryption::encrypted_record_hash(&parameters.system_parameters, encrypted_record)?;
 
             new_encrypted_record_hashes.push(encrypted_record_hash);
         }
 
-        let transaction_kernel = TransactionKernel {
-            system_parameters: parameters,
-
-            old_records,
-            old_account_private_keys,
-            old_serial_numbers,
-            old_randomizers,
-
-            new_records,
-            new_sn_nonce_randomness,
-            new_commitments,
-
-            new_records_encryption_randomness,
-            new_encrypted_records,
+        let inner_snark_input = InnerCircuitVerifierInput {
+            system_parameters: parameters.system_parameters.clone(),
+            ledger_parameters: ledger_parameters.clone(),
+            ledger_digest: transaction.ledger_digest().clone(),
+            old_serial_numbers: transaction.old_serial_numbers().to_vec(),
+            new_commitments: transaction.new_commitments().to_vec(),
             new_encrypted_record_hashes,
-
-            program_commitment,
-            program_randomness,
-            local_data_merkle_tree,
-            local_data_commitment_randomizers,
-
-            value_balance,
-            memorandum,
-            network_id,
+            memo: *transaction.memorandum(),
+            program_commitment: transaction.program_commitment().clone(),
+            local_data_root: transaction.local_data_root().clone(),
+            value_balance: transaction.value_balance(),
+            network_id: transaction.network_id(),
         };
-        Ok(transaction_kernel)
-    }
 
-    fn execute_online<R: Rng>(
-        parameters: &Self::NetworkParameters,
-        transaction_kernel: Self::TransactionKernel,
-        old_death_program_proofs: Vec<Self::PrivateProgramInput>,
-        new_birth_program_proofs: Vec<Self::PrivateProgramInput>,
+        Ok(Ok(OuterCircuitVerifierInput {
+            inner_snark_verifier_input: inner_snark_input,
+            inner_snark_id: inner_snark_id.clone(),
+        }))
+    }
+
+    /// Proves a transaction from a kernel signed with `TransactionKernel::sign`.
+    ///
+    /// The signatures are checked before proving, so the transaction contents can't be altered
+    /// without the account private keys. The records behind the signed commitments are enforced
+    /// by the inner SNARK, which fails to verify if they were altered.
+    ///
+    /// The kernel may be signed against any past ledger digest, so it can still be proven
+    /// after new blocks are added to the ledger.
+    pub fn execute_online_signed<L, R: Rng>(
+        parameters: &PublicParameters<Components>,
+        signed_transaction_kernel: SignedTransactionKernel<Components>,
+        old_death_program_proofs: Vec<PrivateProgramInput>,
+        new_birth_program_proofs: Vec<PrivateProgramInput>,
         ledger: &L,
         rng: &mut R,
-    ) -> anyhow::Result<(Vec<Self::Record>, Self::Transaction)> {
+    ) -> anyhow::Result<(Vec<DPCRecord<Components>>, DPCTransaction<Components>)>
+    where
+        L: LedgerScheme<
+            Commitment = <Components::RecordCommitment as CommitmentScheme>::Output,
+            MerkleParameters = Components::MerkleParameters,
+            MerklePath = MerklePath<Components::MerkleParameters>,
+            MerkleTreeDigest = MerkleTreeDigest<Components::MerkleParameters>,
+            SerialNumber = <Components::AccountSignature as SignatureScheme>::PublicKey,
+            Transaction = DPCTransaction<Components>,
+        >,
+    {
         assert_eq!(Components::NUM_INPUT_RECORDS, old_death_program_proofs.len());
         assert_eq!(Components::NUM_OUTPUT_RECORDS, new_birth_program_proofs.len());
 
         let exec_time = start_timer!(|| "BaseDPC::execute_online");
 
-        let TransactionKernel {
-            system_parameters,
+        if let Some(index) = signed_transaction_kernel.find_invalid_signature(&parameters.system_parameters)? {
+            return Err(DPCError::InvalidKernelSignature(index).into());
+        }
 
+        let SignedTransactionKernel {
+            system_parameters: _,
+
+            old_account_compute_keys,
             old_records,
-            old_account_private_keys,
             old_serial_numbers,
-            old_randomizers,
+
+            ledger_digest,
+            signatures,
 
             new_records,
             new_sn_nonce_randomness,
@@ -897,7 +1163,7 @@ where
             value_balance,
             memorandum,
             network_id,
-        } = transaction_kernel;
+        } = signed_transaction_kernel;
 
         let local_data_root = local_data_merkle_tree.root();
 
@@ -906,7 +1172,10 @@ where
 
         // Construct the ledger witnesses
 
-        let ledger_digest = ledger.digest().expect("could not get digest");
+        // The kernel may have been signed against a past ledger digest, which the witnesses are proven against.
+        if !ledger.validate_digest(&ledger_digest) {
+            return Err(DPCError::InvalidLedgerDigest.into());
+        }
 
         // Generate the ledger membership witnesses
         let mut old_witnesses = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
@@ -916,59 +1185,19 @@ where
             if record.is_dummy() {
                 old_witnesses.push(MerklePath::default());
             } else {
-                let witness = ledger.prove_cm(&record.commitment())?;
+                let witness = ledger.prove_cm_at(&record.commitment(), &ledger_digest)?;
                 old_witnesses.push(witness);
             }
         }
 
-        // Generate Schnorr signature on transaction data
-        // TODO (raychu86) Remove ledger_digest from signature and move the schnorr signing into `execute_offline`
-        let signature_time = start_timer!(|| "Sign and randomize transaction contents");
-
-        let signature_message = to_bytes![
-            network_id,
-            ledger_digest,
-            old_serial_numbers,
-            new_commitments,
-            program_commitment,
-            local_data_root,
-            value_balance,
-            memorandum
-        ]?;
-
-        let mut signatures = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
-        for i in 0..Components::NUM_INPUT_RECORDS {
-            let sk_sig = &old_account_private_keys[i].sk_sig;
-            let randomizer = &old_randomizers[i];
-
-            // Sign the transaction data
-            let account_signature = Components::AccountSignature::sign(
-                &system_parameters.account_signature,
-                sk_sig,
-                &signature_message,
-                rng,
-            )?;
-
-            // Randomize the signature
-            let randomized_signature = Components::AccountSignature::randomize_signature(
-                &system_parameters.account_signature,
-                &account_signature,
-                randomizer,
-            )?;
-
-            signatures.push(randomized_signature);
-        }
-
-        end_timer!(signature_time);
-
         // Prepare record encryption components used in the inner SNARK
 
         let mut new_records_encryption_gadget_components = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
 
         for (record, ciphertext_randomness) in new_records.iter().zip_eq(&new_records_encryption_randomness) {
             let record_encryption_gadget_components = RecordEncryption::prepare_encryption_gadget_components(
-                &system_parameters,
-                &record,
+                &parameters.system_parameters,
+                record,
                 ciphertext_randomness,
             )?;
 
@@ -982,7 +1211,7 @@ where
                 ledger_digest.clone(),
                 old_records,
                 old_witnesses,
-                old_account_private_keys,
+                old_account_compute_keys,
                 old_serial_numbers.clone(),
                 new_records.clone(),
                 new_sn_nonce_randomness,
@@ -1023,205 +1252,536 @@ where
                 network_id,
             };
 
-            let verification_key = &parameters.inner_snark_parameters.1;
+            let verification_key = &parameters.inner_snark_parameters.1;
+
+            if !Components::InnerSNARK::verify(verification_key, &input, &inner_proof)? {
+                return Err(DPCError::InvalidInnerProof.into());
+            }
+        }
+
+        let inner_snark_vk: <Components::InnerSNARK as SNARK>::VerificationParameters =
+            parameters.inner_snark_parameters.1.clone().into();
+
+        let inner_snark_id = <Components::InnerSNARKVerificationKeyCRH as CRH>::hash(
+            &parameters.system_parameters.inner_snark_verification_key_crh,
+            &to_bytes![inner_snark_vk]?,
+        )?;
+
+        let transaction_proof = {
+            let circuit = OuterCircuit::new(
+                parameters.system_parameters.clone(),
+                ledger.parameters().clone(),
+                ledger_digest.clone(),
+                old_serial_numbers.clone(),
+                new_commitments.clone(),
+                new_encrypted_record_hashes,
+                memorandum,
+                value_balance,
+                network_id,
+                inner_snark_vk,
+                inner_proof,
+                old_death_program_attributes,
+                new_birth_program_attributes,
+                program_commitment.clone(),
+                program_randomness,
+                local_data_root.clone(),
+                inner_snark_id.clone(),
+            );
+
+            let outer_snark_parameters = match &parameters.outer_snark_parameters.0 {
+                Some(outer_snark_parameters) => outer_snark_parameters,
+                None => return Err(DPCError::MissingOuterSnarkProvingParameters.into()),
+            };
+
+            Components::OuterSNARK::prove(&outer_snark_parameters, &circuit, rng)?
+        };
+
+        let transaction = DPCTransaction::new(
+            old_serial_numbers,
+            new_commitments,
+            memorandum,
+            ledger_digest,
+            inner_snark_id,
+            transaction_proof,
+            program_commitment,
+            local_data_root,
+            value_balance,
+            Network::from_network_id(network_id),
+            signatures,
+            new_encrypted_records,
+        );
+
+        end_timer!(exec_time);
+
+        Ok((new_records, transaction))
+    }
+}
+
+impl<Components: BaseDPCComponents, L: LedgerScheme> DPCScheme<L> for DPC<Components>
+where
+    L: LedgerScheme<
+        Commitment = <Components::RecordCommitment as CommitmentScheme>::Output,
+        MerkleParameters = Components::MerkleParameters,
+        MerklePath = MerklePath<Components::MerkleParameters>,
+        MerkleTreeDigest = MerkleTreeDigest<Components::MerkleParameters>,
+        SerialNumber = <Components::AccountSignature as SignatureScheme>::PublicKey,
+        Transaction = DPCTransaction<Components>,
+    >,
+    Components::MerkleParameters: Sync,
+    <Components::InnerSNARKVerificationKeyCRH as CRH>::Output: Sync,
+    PublicParameters<Components>: Sync,
+    DPCTransaction<Components>: Sync,
+    OuterCircuitVerifierInput<Components>: Send + Sync,
+{
+    type Account = Account<Components>;
+    type LocalData = LocalData<Components>;
+    type NetworkParameters = PublicParameters<Components>;
+    type Payload = <Self::Record as Record>::Payload;
+    type PrivateProgramInput = PrivateProgramInput;
+    type Record = DPCRecord<Components>;
+    type SystemParameters = SystemParameters<Components>;
+    type Transaction = DPCTransaction<Components>;
+    type TransactionKernel = TransactionKernel<Components>;
+
+    fn setup<R: Rng>(
+        ledger_parameters: &Components::MerkleParameters,
+        rng: &mut R,
+    ) -> anyhow::Result<Self::NetworkParameters> {
+        let setup_time = start_timer!(|| "BaseDPC::setup");
+        let system_parameters = Self::generate_system_parameters(rng)?;
+
+        let program_snark_setup_time = start_timer!(|| "Dummy program SNARK setup");
+        let noop_program_snark_parameters = Self::generate_noop_program_snark_parameters(&system_parameters, rng)?;
+        let program_snark_proof = Components::NoopProgramSNARK::prove(
+            &noop_program_snark_parameters.proving_key,
+            &NoopCircuit::blank(&system_parameters),
+            rng,
+        )?;
+        end_timer!(program_snark_setup_time);
+
+        let program_snark_vk_and_proof = PrivateProgramInput {
+            verification_key: to_bytes![noop_program_snark_parameters.verification_key]?,
+            proof: to_bytes![program_snark_proof]?,
+        };
+
+        let snark_setup_time = start_timer!(|| "Execute inner SNARK setup");
+        let inner_circuit = InnerCircuit::blank(&system_parameters, ledger_parameters);
+        let inner_snark_parameters = Components::InnerSNARK::setup(&inner_circuit, rng)?;
+        end_timer!(snark_setup_time);
+
+        let snark_setup_time = start_timer!(|| "Execute outer SNARK setup");
+        let inner_snark_vk: <Components::InnerSNARK as SNARK>::VerificationParameters =
+            inner_snark_parameters.1.clone().into();
+        let inner_snark_proof = Components::InnerSNARK::prove(&inner_snark_parameters.0, &inner_circuit, rng)?;
+
+        let outer_snark_parameters = Components::OuterSNARK::setup(
+            &OuterCircuit::blank(
+                system_parameters.clone(),
+                ledger_parameters.clone(),
+                inner_snark_vk,
+                inner_snark_proof,
+                program_snark_vk_and_proof,
+            ),
+            rng,
+        )?;
+        end_timer!(snark_setup_time);
+        end_timer!(setup_time);
+
+        let inner_snark_parameters = (Some(inner_snark_parameters.0), inner_snark_parameters.1);
+        let outer_snark_parameters = (Some(outer_snark_parameters.0), outer_snark_parameters.1);
+
+        Ok(PublicParameters {
+            system_parameters,
+            noop_program_snark_parameters,
+            inner_snark_parameters,
+            outer_snark_parameters,
+        })
+    }
+
+    fn create_account<R: Rng>(parameters: &Self::SystemParameters, rng: &mut R) -> anyhow::Result<Self::Account> {
+        let time = start_timer!(|| "BaseDPC::create_account");
+        let account = Account::new(
+            &parameters.account_signature,
+            &parameters.account_commitment,
+            &parameters.account_encryption,
+            rng,
+        )?;
+        end_timer!(time);
+
+        Ok(account)
+    }
+
+    fn execute_offline<R: Rng>(
+        parameters: Self::SystemParameters,
+        old_records: Vec<Self::Record>,
+        old_account_private_keys: Vec<<Self::Account as AccountScheme>::AccountPrivateKey>,
+        new_record_owners: Vec<<Self::Account as AccountScheme>::AccountAddress>,
+        new_is_dummy_flags: &[bool],
+        new_values: &[u64],
+        new_payloads: Vec<Self::Payload>,
+        new_birth_program_ids: Vec<Vec<u8>>,
+        new_death_program_ids: Vec<Vec<u8>>,
+        memorandum: <Self::Transaction as Transaction>::Memorandum,
+        network_id: u8,
+        rng: &mut R,
+    ) -> anyhow::Result<Self::TransactionKernel> {
+        assert_eq!(Components::NUM_INPUT_RECORDS, old_records.len());
+        assert_eq!(Components::NUM_INPUT_RECORDS, old_account_private_keys.len());
+
+        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_record_owners.len());
+        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_is_dummy_flags.len());
+        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_payloads.len());
+        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_birth_program_ids.len());
+        assert_eq!(Components::NUM_OUTPUT_RECORDS, new_death_program_ids.len());
+
+        let mut old_serial_numbers = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
+        let mut old_randomizers = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
+        let mut joint_serial_numbers = Vec::new();
+        let mut old_death_program_ids = Vec::with_capacity(old_records.len());
+
+        let mut value_balance = AleoAmount::ZERO;
+
+        // Compute the ledger membership witness and serial number from the old records.
+        for (i, record) in old_records.iter().enumerate() {
+            let input_record_time = start_timer!(|| format!("Process input record {}", i));
+
+            if !record.is_dummy() {
+                value_balance = value_balance.add(AleoAmount::from_bytes(record.value() as i64));
+            }
+
+            let (sn, randomizer) = Self::generate_sn(&parameters, record, &old_account_private_keys[i])?;
+            joint_serial_numbers.extend_from_slice(&to_bytes![sn]?);
+            old_serial_numbers.push(sn);
+            old_randomizers.push(randomizer);
+            old_death_program_ids.push(record.death_program_id().to_vec());
+
+            end_timer!(input_record_time);
+        }
+
+        let mut new_records = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
+        let mut new_commitments = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
+        let mut new_sn_nonce_randomness = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
+
+        // Generate new records and commitments for them.
+        for (j, (new_record_owner, new_payload, new_death_program_id)) in
+            izip!(new_record_owners, new_payloads, new_death_program_ids).enumerate()
+        {
+            if j == Components::NUM_OUTPUT_RECORDS {
+                break;
+            }
+
+            let output_record_time = start_timer!(|| format!("Process output record {}", j));
+            let sn_nonce_time = start_timer!(|| "Generate serial number nonce");
 
-            assert!(Components::InnerSNARK::verify(verification_key, &input, &inner_proof)?);
-        }
+            // Sample randomness sn_randomness for the CRH input.
+            let sn_randomness: [u8; 32] = rng.gen();
 
-        let inner_snark_vk: <Components::InnerSNARK as SNARK>::VerificationParameters =
-            parameters.inner_snark_parameters.1.clone().into();
+            let crh_input = to_bytes![j as u8, sn_randomness, joint_serial_numbers]?;
+            let sn_nonce = Components::SerialNumberNonceCRH::hash(&parameters.serial_number_nonce, &crh_input)?;
 
-        let inner_snark_id = <Components::InnerSNARKVerificationKeyCRH as CRH>::hash(
-            &parameters.system_parameters.inner_snark_verification_key_crh,
-            &to_bytes![inner_snark_vk]?,
-        )?;
+            end_timer!(sn_nonce_time);
 
-        let transaction_proof = {
-            let circuit = OuterCircuit::new(
-                parameters.system_parameters.clone(),
-                ledger.parameters().clone(),
-                ledger_digest.clone(),
-                old_serial_numbers.clone(),
-                new_commitments.clone(),
-                new_encrypted_record_hashes,
-                memorandum,
-                value_balance,
-                network_id,
-                inner_snark_vk,
-                inner_proof,
-                old_death_program_attributes,
-                new_birth_program_attributes,
-                program_commitment.clone(),
-                program_randomness,
-                local_data_root.clone(),
-                inner_snark_id.clone(),
-            );
+            let record = Self::generate_record(
+                &parameters,
+                sn_nonce,
+                new_record_owner,
+                new_is_dummy_flags[j],
+                new_values[j],
+                new_payload,
+                new_birth_program_ids[j].clone(),
+                new_death_program_id,
+                rng,
+            )?;
 
-            let outer_snark_parameters = match &parameters.outer_snark_parameters.0 {
-                Some(outer_snark_parameters) => outer_snark_parameters,
-                None => return Err(DPCError::MissingOuterSnarkProvingParameters.into()),
-            };
+            if !record.is_dummy() {
+                value_balance = value_balance.sub(AleoAmount::from_bytes(record.value() as i64));
+            }
 
-            Components::OuterSNARK::prove(&outer_snark_parameters, &circuit, rng)?
-        };
+            new_commitments.push(record.commitment().clone());
+            new_sn_nonce_randomness.push(sn_randomness);
+            new_records.push(record);
 
-        let transaction = Self::Transaction::new(
-            old_serial_numbers,
-            new_commitments,
-            memorandum,
-            ledger_digest,
-            inner_snark_id,
-            transaction_proof,
-            program_commitment,
-            local_data_root,
-            value_balance,
-            Network::from_network_id(network_id),
-            signatures,
-            new_encrypted_records,
-        );
+            end_timer!(output_record_time);
+        }
 
-        end_timer!(exec_time);
+        // TODO (raychu86) Add index and program register inputs + outputs to local data commitment leaves
+        let local_data_merkle_tree_timer = start_timer!(|| "Compute local data merkle tree");
 
-        Ok((new_records, transaction))
-    }
+        let mut local_data_commitment_randomizers = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
 
-    fn verify(
-        parameters: &Self::NetworkParameters,
-        transaction: &Self::Transaction,
-        ledger: &L,
-    ) -> anyhow::Result<bool> {
-        let verify_time = start_timer!(|| "BaseDPC::verify");
+        let mut old_record_commitments = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
+        for i in 0..Components::NUM_INPUT_RECORDS {
+            let record = &old_records[i];
+            let input_bytes = to_bytes![old_serial_numbers[i], record.commitment(), memorandum, network_id]?;
 
-        // Returns false if there are duplicate serial numbers in the transaction.
-        if has_duplicates(transaction.old_serial_numbers().iter()) {
-            eprintln!("Transaction contains duplicate serial numbers");
-            return Ok(false);
-        }
+            let commitment_randomness = <Components::LocalDataCommitment as CommitmentScheme>::Randomness::rand(rng);
+            let commitment = Components::LocalDataCommitment::commit(
+                &parameters.local_data_commitment,
+                &input_bytes,
+                &commitment_randomness,
+            )?;
 
-        // Returns false if there are duplicate commitments numbers in the transaction.
-        if has_duplicates(transaction.new_commitments().iter()) {
-            eprintln!("Transaction contains duplicate commitments");
-            return Ok(false);
+            old_record_commitments.push(commitment);
+            local_data_commitment_randomizers.push(commitment_randomness);
         }
 
-        let ledger_time = start_timer!(|| "Ledger checks");
+        let mut new_record_commitments = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
+        for record in new_records.iter().take(Components::NUM_OUTPUT_RECORDS) {
+            let input_bytes = to_bytes![record.commitment(), memorandum, network_id]?;
 
-        // Returns false if the transaction memo previously existed in the ledger.
-        if ledger.contains_memo(transaction.memorandum()) {
-            eprintln!("Ledger already contains this transaction memo.");
-            return Ok(false);
-        }
+            let commitment_randomness = <Components::LocalDataCommitment as CommitmentScheme>::Randomness::rand(rng);
+            let commitment = Components::LocalDataCommitment::commit(
+                &parameters.local_data_commitment,
+                &input_bytes,
+                &commitment_randomness,
+            )?;
 
-        // Returns false if any transaction serial number previously existed in the ledger.
-        for sn in transaction.old_serial_numbers() {
-            if ledger.contains_sn(sn) {
-                eprintln!("Ledger already contains this transaction serial number.");
-                return Ok(false);
-            }
+            new_record_commitments.push(commitment);
+            local_data_commitment_randomizers.push(commitment_randomness);
         }
 
-        // Returns false if any transaction commitment previously existed in the ledger.
-        for cm in transaction.new_commitments() {
-            if ledger.contains_cm(cm) {
-                eprintln!("Ledger already contains this transaction commitment.");
-                return Ok(false);
+        let leaves = [old_record_commitments, new_record_commitments].concat();
+        let local_data_merkle_tree = CommitmentMerkleTree::new(parameters.local_data_crh.clone(), &leaves)?;
+
+        end_timer!(local_data_merkle_tree_timer);
+
+        let program_comm_timer = start_timer!(|| "Compute program commitment");
+        let (program_commitment, program_randomness) = {
+            let mut input = Vec::new();
+            for id in old_death_program_ids {
+                input.extend_from_slice(&id);
             }
-        }
 
-        // Returns false if the ledger digest in the transaction is invalid.
-        if !ledger.validate_digest(&transaction.ledger_digest) {
-            eprintln!("Ledger digest is invalid.");
-            return Ok(false);
-        }
+            for id in new_birth_program_ids {
+                input.extend_from_slice(&id);
+            }
+            let program_randomness =
+                <Components::ProgramVerificationKeyCommitment as CommitmentScheme>::Randomness::rand(rng);
+            let program_commitment = Components::ProgramVerificationKeyCommitment::commit(
+                &parameters.program_verification_key_commitment,
+                &input,
+                &program_randomness,
+            )?;
+            (program_commitment, program_randomness)
+        };
+        end_timer!(program_comm_timer);
 
-        end_timer!(ledger_time);
+        // Encrypt the new records
 
-        let signature_time = start_timer!(|| "Signature checks");
+        let mut new_records_encryption_randomness = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
+        let mut new_encrypted_records = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
 
-        let signature_message = &to_bytes![
-            transaction.network_id(),
-            transaction.ledger_digest(),
-            transaction.old_serial_numbers(),
-            transaction.new_commitments(),
-            transaction.program_commitment(),
-            transaction.local_data_root(),
-            transaction.value_balance(),
-            transaction.memorandum()
-        ]?;
+        for record in &new_records {
+            let (record_encryption_randomness, encrypted_record) =
+                RecordEncryption::encrypt_record(&parameters, record, rng)?;
 
-        let account_signature = &parameters.system_parameters.account_signature;
-        for (pk, sig) in transaction.old_serial_numbers().iter().zip(&transaction.signatures) {
-            if !Components::AccountSignature::verify(account_signature, pk, signature_message, sig)? {
-                eprintln!("Signature didn't verify.");
-                return Ok(false);
-            }
+            new_records_encryption_randomness.push(record_encryption_randomness);
+            new_encrypted_records.push(encrypted_record);
         }
 
-        end_timer!(signature_time);
-
         // Construct the ciphertext hashes
 
         let mut new_encrypted_record_hashes = Vec::with_capacity(Components::NUM_OUTPUT_RECORDS);
-        for encrypted_record in &transaction.encrypted_records {
-            let encrypted_record_hash =
-                RecordEncryption::encrypted_record_hash(&parameters.system_parameters, encrypted_record)?;
+        for encrypted_record in &new_encrypted_records {
+            let encrypted_record_hash = RecordEncryption::encrypted_record_hash(&parameters, &encrypted_record)?;
 
             new_encrypted_record_hashes.push(encrypted_record_hash);
         }
 
-        let inner_snark_input = InnerCircuitVerifierInput {
-            system_parameters: parameters.system_parameters.clone(),
-            ledger_parameters: ledger.parameters().clone(),
-            ledger_digest: transaction.ledger_digest().clone(),
-            old_serial_numbers: transaction.old_serial_numbers().to_vec(),
-            new_commitments: transaction.new_commitments().to_vec(),
+        let transaction_kernel = TransactionKernel {
+            system_parameters: parameters,
+
+            old_records,
+            old_account_private_keys,
+            old_serial_numbers,
+            old_randomizers,
+
+            new_records,
+            new_sn_nonce_randomness,
+            new_commitments,
+
+            new_records_encryption_randomness,
+            new_encrypted_records,
             new_encrypted_record_hashes,
-            memo: *transaction.memorandum(),
-            program_commitment: transaction.program_commitment().clone(),
-            local_data_root: transaction.local_data_root().clone(),
-            value_balance: transaction.value_balance(),
-            network_id: transaction.network_id(),
+
+            program_commitment,
+            program_randomness,
+            local_data_merkle_tree,
+            local_data_commitment_randomizers,
+
+            value_balance,
+            memorandum,
+            network_id,
         };
+        Ok(transaction_kernel)
+    }
 
-        let inner_snark_vk: <<Components as BaseDPCComponents>::InnerSNARK as SNARK>::VerificationParameters =
-            parameters.inner_snark_parameters.1.clone().into();
+    fn execute_online<R: Rng>(
+        parameters: &Self::NetworkParameters,
+        transaction_kernel: Self::TransactionKernel,
+        old_death_program_proofs: Vec<Self::PrivateProgramInput>,
+        new_birth_program_proofs: Vec<Self::PrivateProgramInput>,
+        ledger: &L,
+        rng: &mut R,
+    ) -> anyhow::Result<(Vec<Self::Record>, Self::Transaction)> {
+        // TODO (raychu86) Remove ledger_digest from signature and move the schnorr signing into `execute_offline`
+        let ledger_digest = ledger.digest().expect("could not get digest");
+        let signed_transaction_kernel = transaction_kernel.sign(ledger_digest, rng)?;
+
+        Self::execute_online_signed(
+            parameters,
+            signed_transaction_kernel,
+            old_death_program_proofs,
+            new_birth_program_proofs,
+            ledger,
+            rng,
+        )
+    }
 
-        let inner_snark_id = Components::InnerSNARKVerificationKeyCRH::hash(
-            &parameters.system_parameters.inner_snark_verification_key_crh,
-            &to_bytes![inner_snark_vk]?,
-        )?;
+    fn verify_detailed(
+        parameters: &Self::NetworkParameters,
+        transaction: &Self::Transaction,
+        ledger: &L,
+    ) -> anyhow::Result<Result<(), TransactionVerificationError>> {
+        let verify_time = start_timer!(|| "BaseDPC::verify");
 
-        let outer_snark_input = OuterCircuitVerifierInput {
-            inner_snark_verifier_input: inner_snark_input,
-            inner_snark_id,
-        };
+        if let Err(error) = Self::verify_against_ledger(transaction, ledger) {
+            return Ok(Err(error));
+        }
+
+        let inner_snark_id = Self::inner_snark_id(parameters)?;
+
+        let outer_snark_input =
+            match Self::outer_snark_verifier_input(parameters, transaction, ledger.parameters(), &inner_snark_id)? {
+                Ok(outer_snark_input) => outer_snark_input,
+                Err(error) => return Ok(Err(error)),
+            };
 
         if !Components::OuterSNARK::verify(
             &parameters.outer_snark_parameters.1,
             &outer_snark_input,
             &transaction.transaction_proof,
         )? {
-            eprintln!("Transaction proof failed to verify.");
-            return Ok(false);
+            return Ok(Err(TransactionVerificationError::OuterProofRejected));
         }
 
         end_timer!(verify_time);
 
-        Ok(true)
+        Ok(Ok(()))
     }
 
-    /// Returns true iff all the transactions in the block are valid according to the ledger.
-    fn verify_transactions(
+    /// Returns `Ok(())` iff all the transactions in the block are valid according to the ledger,
+    /// and otherwise the reason the first invalid transaction is invalid.
+    ///
+    /// The ledger checks run sequentially across the whole block, and also reject transactions
+    /// that spend a serial number, or reuse a commitment or memo, of an earlier transaction in the block.
+    /// The signatures are checked in parallel, and the outer proofs are batch verified.
+    fn verify_transactions_detailed(
         parameters: &Self::NetworkParameters,
         transactions: &[Self::Transaction],
         ledger: &L,
-    ) -> anyhow::Result<bool> {
-        for transaction in transactions {
-            if !Self::verify(parameters, transaction, ledger)? {
-                return Ok(false);
+    ) -> anyhow::Result<Result<(), TransactionVerificationError>> {
+        let verify_time =
+            start_timer!(|| format!("BaseDPC::verify_transactions ({} transactions)", transactions.len()));
+
+        let invalid_transaction = |index: usize, error: TransactionVerificationError| {
+            Ok(Err(TransactionVerificationError::InvalidBlockTransaction(
+                index,
+                Box::new(error),
+            )))
+        };
+
+        let ledger_time = start_timer!(|| "Block ledger checks");
+
+        let mut block_serial_numbers = HashSet::new();
+        let mut block_commitments = HashSet::new();
+        let mut block_memos = HashSet::new();
+
+        for (index, transaction) in transactions.iter().enumerate() {
+            // All the transactions in the block must be for the same network.
+            if transaction.network_id() != transactions[0].network_id() {
+                return invalid_transaction(
+                    index,
+                    TransactionVerificationError::NetworkMismatch(
+                        transactions[0].network_id(),
+                        transaction.network_id(),
+                    ),
+                );
+            }
+
+            if let Err(error) = Self::verify_against_ledger(transaction, ledger) {
+                return invalid_transaction(index, error);
+            }
+
+            // Returns an error if a serial number is spent by an earlier transaction in the block.
+            for (sn_index, sn) in transaction.old_serial_numbers().iter().enumerate() {
+                if !block_serial_numbers.insert(sn) {
+                    return invalid_transaction(index, TransactionVerificationError::ConflictingSn(sn_index));
+                }
+            }
+
+            // Returns an error if a commitment is created by an earlier transaction in the block.
+            for (cm_index, cm) in transaction.new_commitments().iter().enumerate() {
+                if !block_commitments.insert(cm) {
+                    return invalid_transaction(index, TransactionVerificationError::ConflictingCm(cm_index));
+                }
+            }
+
+            // Returns an error if the memo is used by an earlier transaction in the block.
+            if !block_memos.insert(transaction.memorandum()) {
+                return invalid_transaction(index, TransactionVerificationError::ConflictingMemo);
+            }
+        }
+
+        end_timer!(ledger_time);
+
+        let inner_snark_id = Self::inner_snark_id(parameters)?;
+        let ledger_parameters = ledger.parameters();
+
+        let outer_snark_inputs = cfg_iter!(transactions)
+            .map(|transaction| {
+                Self::outer_snark_verifier_input(parameters, transaction, ledger_parameters, &inner_snark_id)
+            })
+            .collect::<Vec<_>>();
+
+        let mut verified_outer_snark_inputs = Vec::with_capacity(transactions.len());
+        for (index, outer_snark_input) in outer_snark_inputs.into_iter().enumerate() {
+            match outer_snark_input? {
+                Ok(outer_snark_input) => verified_outer_snark_inputs.push(outer_snark_input),
+                Err(error) => return invalid_transaction(index, error),
+            }
+        }
+
+        let proof_time = start_timer!(|| "Outer proof batch verification");
+
+        let outer_snark_vk = &parameters.outer_snark_parameters.1;
+        let inputs_and_proofs = verified_outer_snark_inputs
+            .iter()
+            .zip(transactions)
+            .map(|(outer_snark_input, transaction)| (outer_snark_input, &transaction.transaction_proof))
+            .collect::<Vec<_>>();
+
+        if !Components::OuterSNARK::verify_batch(outer_snark_vk, &inputs_and_proofs, &mut rand::thread_rng())? {
+            // The batch check does not identify the invalid proof, so verify each proof to find it.
+            let results = cfg_iter!(verified_outer_snark_inputs)
+                .zip(cfg_iter!(transactions))
+                .map(|(outer_snark_input, transaction)| {
+                    Components::OuterSNARK::verify(
+                        &parameters.outer_snark_parameters.1,
+                        outer_snark_input,
+                        &transaction.transaction_proof,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if let Some(index) = results.iter().position(|is_valid| !is_valid) {
+                return invalid_transaction(index, TransactionVerificationError::OuterProofRejected);
             }
         }
 
-        Ok(true)
+        end_timer!(proof_time);
+        end_timer!(verify_time);
+
+        Ok(Ok(()))
     }
 }
@@ -24,6 +24,7 @@ use snarkvm_gadgets::traits::{
     algorithms::{CRHGadget, CommitmentGadget, SNARKVerifierGadget},
     utilities::{
         alloc::{AllocBytesGadget, AllocGadget},
+        boolean::Boolean,
         eq::EqGadget,
         uint::unsigned_integer::{UInt, UInt8},
         ToBytesGadget,
@@ -57,6 +58,53 @@ fn field_element_to_bytes<C: BaseDPCComponents, CS: ConstraintSystem<C::OuterFie
     }
 }
 
+/// Verifies the proof of the program of the record at the given position,
+/// and returns the program id, which is the hash of the program verification key.
+///
+/// The proof is verified with `C::ProgramSNARKGadget`, so any program proven with the proof system
+/// of `C::NoopProgramSNARK` on the program input is accepted.
+pub fn execute_program_proof_gadget<C: BaseDPCComponents, CS: ConstraintSystem<C::OuterField>>(
+    cs: &mut CS,
+    program_vk_crh_parameters: &<C::ProgramVerificationKeyCRHGadget as CRHGadget<
+        C::ProgramVerificationKeyCRH,
+        C::OuterField,
+    >>::ParametersGadget,
+    program_input_bits: &[Vec<Boolean>],
+    program_verification_input: &PrivateProgramInput,
+    position: u8,
+) -> Result<Vec<UInt8>, SynthesisError> {
+    let program_proof = <C::ProgramSNARKGadget as SNARKVerifierGadget<_, _>>::ProofGadget::alloc_bytes(
+        &mut cs.ns(|| "Allocate proof"),
+        || Ok(&program_verification_input.proof),
+    )?;
+
+    let program_vk = <C::ProgramSNARKGadget as SNARKVerifierGadget<_, _>>::VerificationKeyGadget::alloc_bytes(
+        &mut cs.ns(|| "Allocate verification key"),
+        || Ok(&program_verification_input.verification_key),
+    )?;
+
+    let program_vk_bytes = program_vk.to_bytes(&mut cs.ns(|| "Convert program vk to bytes"))?;
+
+    let claimed_program_id = C::ProgramVerificationKeyCRHGadget::check_evaluation_gadget(
+        &mut cs.ns(|| "Compute program vk hash"),
+        program_vk_crh_parameters,
+        program_vk_bytes,
+    )?;
+
+    let claimed_program_id_bytes = claimed_program_id.to_bytes(&mut cs.ns(|| "Convert program vk hash to bytes"))?;
+
+    let position = UInt8::constant(position).to_bits_le();
+
+    C::ProgramSNARKGadget::check_verify(
+        &mut cs.ns(|| "Check that proof is satisfied"),
+        &program_vk,
+        ([position].iter()).chain(program_input_bits.iter()),
+        &program_proof,
+    )?;
+
+    Ok(claimed_program_id_bytes)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn execute_outer_proof_gadget<C: BaseDPCComponents, CS: ConstraintSystem<C::OuterField>>(
     cs: &mut CS,
@@ -360,40 +408,15 @@ where
         .enumerate()
         .take(C::NUM_INPUT_RECORDS)
     {
-        let cs = &mut cs.ns(|| format!("Check death program for input record {}", i));
-
-        let death_program_proof = <C::ProgramSNARKGadget as SNARKVerifierGadget<_, _>>::ProofGadget::alloc_bytes(
-            &mut cs.ns(|| "Allocate proof"),
-            || Ok(&input.proof),
-        )?;
-
-        let death_program_vk =
-            <C::ProgramSNARKGadget as SNARKVerifierGadget<_, _>>::VerificationKeyGadget::alloc_bytes(
-                &mut cs.ns(|| "Allocate verification key"),
-                || Ok(&input.verification_key),
-            )?;
-
-        let death_program_vk_bytes = death_program_vk.to_bytes(&mut cs.ns(|| "Convert death pred vk to bytes"))?;
-
-        let claimed_death_program_id = C::ProgramVerificationKeyCRHGadget::check_evaluation_gadget(
-            &mut cs.ns(|| "Compute death program vk hash"),
+        let death_program_id = execute_program_proof_gadget::<C, _>(
+            &mut cs.ns(|| format!("Check death program for input record {}", i)),
             &program_vk_crh_parameters,
-            death_program_vk_bytes,
+            &program_input_bits,
+            input,
+            i as u8,
         )?;
 
-        let claimed_death_program_id_bytes =
-            claimed_death_program_id.to_bytes(&mut cs.ns(|| "Convert death_pred vk hash to bytes"))?;
-
-        old_death_program_ids.push(claimed_death_program_id_bytes);
-
-        let position = UInt8::constant(i as u8).to_bits_le();
-
-        C::ProgramSNARKGadget::check_verify(
-            &mut cs.ns(|| "Check that proof is satisfied"),
-            &death_program_vk,
-            ([position].iter()).chain(program_input_bits.iter()),
-            &death_program_proof,
-        )?;
+        old_death_program_ids.push(death_program_id);
     }
 
     for (j, input) in new_birth_program_verification_inputs
@@ -401,40 +424,15 @@ where
         .enumerate()
         .take(C::NUM_OUTPUT_RECORDS)
     {
-        let cs = &mut cs.ns(|| format!("Check birth program for output record {}", j));
-
-        let birth_program_proof = <C::ProgramSNARKGadget as SNARKVerifierGadget<_, _>>::ProofGadget::alloc_bytes(
-            &mut cs.ns(|| "Allocate proof"),
-            || Ok(&input.proof),
-        )?;
-
-        let birth_program_vk =
-            <C::ProgramSNARKGadget as SNARKVerifierGadget<_, _>>::VerificationKeyGadget::alloc_bytes(
-                &mut cs.ns(|| "Allocate verification key"),
-                || Ok(&input.verification_key),
-            )?;
-
-        let birth_program_vk_bytes = birth_program_vk.to_bytes(&mut cs.ns(|| "Convert birth pred vk to bytes"))?;
-
-        let claimed_birth_program_id = C::ProgramVerificationKeyCRHGadget::check_evaluation_gadget(
-            &mut cs.ns(|| "Compute birth program vk hash"),
+        let birth_program_id = execute_program_proof_gadget::<C, _>(
+            &mut cs.ns(|| format!("Check birth program for output record {}", j)),
             &program_vk_crh_parameters,
-            birth_program_vk_bytes,
+            &program_input_bits,
+            input,
+            (C::NUM_INPUT_RECORDS + j) as u8,
         )?;
 
-        let claimed_birth_program_id_bytes =
-            claimed_birth_program_id.to_bytes(&mut cs.ns(|| "Convert birth_pred vk hash to bytes"))?;
-
-        new_birth_program_ids.push(claimed_birth_program_id_bytes);
-
-        let position = UInt8::constant((C::NUM_INPUT_RECORDS + j) as u8).to_bits_le();
-
-        C::ProgramSNARKGadget::check_verify(
-            &mut cs.ns(|| "Check that proof is satisfied"),
-            &birth_program_vk,
-            ([position].iter()).chain(program_input_bits.iter()),
-            &birth_program_proof,
-        )?;
+        new_birth_program_ids.push(birth_program_id);
     }
     // ********************************************************************
 
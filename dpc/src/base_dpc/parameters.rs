@@ -14,12 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::base_dpc::BaseDPCComponents;
-use snarkvm_algorithms::traits::{EncryptionScheme, SNARK};
+use crate::base_dpc::{
+    instantiated::{NUM_INPUT_RECORDS, NUM_OUTPUT_RECORDS},
+    BaseDPCComponents,
+};
+use snarkvm_algorithms::traits::{EncryptionScheme, CRH, SNARK};
 use snarkvm_parameters::{traits::Parameter, *};
 use snarkvm_utilities::bytes::FromBytes;
 
-use std::io::Result as IoResult;
+use std::io::{Error as IoError, ErrorKind, Result as IoResult};
 
 #[derive(Derivative)]
 #[derivative(Clone(bound = "C: BaseDPCComponents"))]
@@ -38,8 +41,22 @@ pub struct SystemParameters<C: BaseDPCComponents> {
 }
 
 impl<C: BaseDPCComponents> SystemParameters<C> {
-    // TODO (howardwu): Inspect what is going on with program_verification_key_commitment.
+    /// Loads the bundled system parameters, and returns an error if they don't support
+    /// `C::NUM_INPUT_RECORDS` input records and `C::NUM_OUTPUT_RECORDS` output records.
     pub fn load() -> IoResult<Self> {
+        let system_parameters = Self::load_unchecked()?;
+        system_parameters.check_arity()?;
+
+        Ok(system_parameters)
+    }
+
+    /// Loads the bundled system parameters, without checking that they support the number of records of `C`.
+    ///
+    /// The bundled serial number nonce CRH and inner SNARK verification key CRH parameters only cover
+    /// transactions with up to three input records and up to four records, so variants with more records
+    /// must replace them with their own parameters.
+    // TODO (howardwu): Inspect what is going on with program_verification_key_commitment.
+    pub fn load_unchecked() -> IoResult<Self> {
         let account_commitment: C::AccountCommitment =
             From::from(FromBytes::read(AccountCommitmentParameters::load_bytes()?.as_slice())?);
         let account_encryption_parameters: <C::AccountEncryption as EncryptionScheme>::Parameters =
@@ -80,6 +97,32 @@ impl<C: BaseDPCComponents> SystemParameters<C> {
             serial_number_nonce,
         })
     }
+
+    /// Returns an error if the parameters of the CRHs whose inputs grow with the number of records
+    /// don't match the windows of `C`.
+    pub fn check_arity(&self) -> IoResult<()> {
+        let unsupported_arity = |name: &str, error| {
+            IoError::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "the {} parameters don't support {} input and {} output records: {}",
+                    name,
+                    C::NUM_INPUT_RECORDS,
+                    C::NUM_OUTPUT_RECORDS,
+                    error
+                ),
+            )
+        };
+
+        self.serial_number_nonce
+            .hash(&[])
+            .map_err(|error| unsupported_arity("serial number nonce CRH", error))?;
+        self.inner_snark_verification_key_crh
+            .hash(&[])
+            .map_err(|error| unsupported_arity("inner SNARK verification key CRH", error))?;
+
+        Ok(())
+    }
 }
 
 #[derive(Derivative)]
@@ -89,6 +132,12 @@ pub struct NoopProgramSNARKParameters<C: BaseDPCComponents> {
     pub verification_key: <C::NoopProgramSNARK as SNARK>::VerificationParameters,
 }
 
+/// The parameters of a program SNARK.
+///
+/// Program SNARKs are verified in the outer circuit with the proof system of the noop program SNARK,
+/// so every program shares the parameter types of the noop program.
+pub type ProgramSNARKParameters<C> = NoopProgramSNARKParameters<C>;
+
 impl<C: BaseDPCComponents> NoopProgramSNARKParameters<C> {
     // TODO (howardwu): Why are we not preparing the VK here?
     pub fn load() -> IoResult<Self> {
@@ -183,7 +232,26 @@ impl<C: BaseDPCComponents> PublicParameters<C> {
         &self.system_parameters.serial_number_nonce
     }
 
+    /// Returns an error unless `C` has the number of records of the bundled inner and outer SNARK parameters.
+    fn check_bundled_arity() -> IoResult<()> {
+        match (C::NUM_INPUT_RECORDS, C::NUM_OUTPUT_RECORDS) == (NUM_INPUT_RECORDS, NUM_OUTPUT_RECORDS) {
+            true => Ok(()),
+            false => Err(IoError::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "the bundled SNARK parameters support {} input and {} output records, not {} and {}",
+                    NUM_INPUT_RECORDS,
+                    NUM_OUTPUT_RECORDS,
+                    C::NUM_INPUT_RECORDS,
+                    C::NUM_OUTPUT_RECORDS
+                ),
+            )),
+        }
+    }
+
     pub fn load(verify_only: bool) -> IoResult<Self> {
+        Self::check_bundled_arity()?;
+
         let system_parameters = SystemParameters::<C>::load()?;
         let noop_program_snark_parameters = NoopProgramSNARKParameters::<C>::load()?;
 
@@ -228,6 +296,8 @@ impl<C: BaseDPCComponents> PublicParameters<C> {
     }
 
     pub fn load_vk_direct() -> IoResult<Self> {
+        Self::check_bundled_arity()?;
+
         let system_parameters = SystemParameters::<C>::load()?;
         let noop_program_snark_parameters = NoopProgramSNARKParameters::<C>::load()?;
 
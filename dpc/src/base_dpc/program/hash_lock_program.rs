@@ -0,0 +1,158 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    base_dpc::{BaseDPCComponents, HashLockCircuit, LocalData, PrivateProgramInput, ProgramLocalData},
+    errors::{DPCError, ProgramError},
+    traits::{Program, Record},
+};
+use snarkvm_algorithms::traits::{CommitmentScheme, CRH, SNARK};
+use snarkvm_utilities::{to_bytes, FromBytes, ToBytes};
+
+use rand::Rng;
+use std::marker::PhantomData;
+
+/// A program that can only be executed by a prover who knows the preimage of its lock.
+///
+/// Use it as the death program of a record to require the preimage to spend the record,
+/// or as the birth program of a record to require the preimage to create the record.
+///
+/// Like every program verified by the outer circuit, it is proven with the proof system of
+/// `C::NoopProgramSNARK`, and its program id is the hash of its verification key under the
+/// program verification key CRH.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "C: BaseDPCComponents, S: SNARK"),
+    Debug(bound = "C: BaseDPCComponents, S: SNARK"),
+    PartialEq(bound = "C: BaseDPCComponents, S: SNARK"),
+    Eq(bound = "C: BaseDPCComponents, S: SNARK")
+)]
+pub struct HashLockProgram<C: BaseDPCComponents, S: SNARK> {
+    identity: Vec<u8>,
+    lock: <C::LocalDataCRH as CRH>::Output,
+    #[derivative(Debug = "ignore")]
+    preimage: [u8; 32],
+    _snark: PhantomData<S>,
+}
+
+impl<C: BaseDPCComponents, S: SNARK> HashLockProgram<C, S> {
+    pub fn new(identity: Vec<u8>, lock: <C::LocalDataCRH as CRH>::Output, preimage: [u8; 32]) -> Self {
+        Self {
+            identity,
+            lock,
+            preimage,
+            _snark: PhantomData,
+        }
+    }
+
+    /// Returns the lock of the given preimage.
+    pub fn lock(
+        local_data_crh: &C::LocalDataCRH,
+        preimage: &[u8; 32],
+    ) -> Result<<C::LocalDataCRH as CRH>::Output, DPCError> {
+        Ok(local_data_crh.hash(preimage)?)
+    }
+}
+
+impl<C: BaseDPCComponents, S: SNARK> Program for HashLockProgram<C, S>
+where
+    S: SNARK<AssignedCircuit = HashLockCircuit<C>, VerifierInput = ProgramLocalData<C>>,
+{
+    type LocalData = LocalData<C>;
+    type PrivateWitness = PrivateProgramInput;
+    type ProvingParameters = S::ProvingParameters;
+    type PublicInput = ProgramLocalData<C>;
+    type VerificationParameters = S::VerificationParameters;
+
+    fn execute<R: Rng>(
+        &self,
+        proving_key: &Self::ProvingParameters,
+        verification_key: &Self::VerificationParameters,
+        local_data: &Self::LocalData,
+        position: u8,
+        rng: &mut R,
+    ) -> Result<Self::PrivateWitness, DPCError> {
+        let num_records = local_data.old_records.len() + local_data.new_records.len();
+        if (position as usize) >= num_records {
+            return Err(ProgramError::PositionOutOfBounds(position).into());
+        }
+
+        if (position as usize) < local_data.old_records.len() {
+            let record = &local_data.old_records[position as usize];
+            if self.identity != record.death_program_id() {
+                return Err(ProgramError::MismatchedDeathProgram(position).into());
+            }
+        } else {
+            let record = &local_data.new_records[position as usize - local_data.old_records.len()];
+            if self.identity != record.birth_program_id() {
+                return Err(ProgramError::MismatchedBirthProgram(position).into());
+            }
+        }
+
+        let local_data_root = local_data.local_data_merkle_tree.root();
+
+        let circuit = HashLockCircuit::<C>::new(
+            &local_data.system_parameters,
+            &local_data_root,
+            position,
+            &self.lock,
+            &self.preimage,
+        );
+
+        let proof = S::prove(proving_key, &circuit, rng)?;
+
+        {
+            let program_snark_pvk: <S as SNARK>::PreparedVerificationParameters = verification_key.clone().into();
+
+            let program_pub_input: ProgramLocalData<C> = ProgramLocalData {
+                local_data_commitment_parameters: local_data
+                    .system_parameters
+                    .local_data_commitment
+                    .parameters()
+                    .clone(),
+                local_data_root,
+                position,
+            };
+
+            if !S::verify(&program_snark_pvk, &program_pub_input, &proof)? {
+                return Err(DPCError::InvalidProgramProof(position));
+            }
+        }
+
+        Ok(Self::PrivateWitness {
+            verification_key: to_bytes![verification_key]?,
+            proof: to_bytes![proof]?,
+        })
+    }
+
+    /// Returns `true` if the witness holds a valid program proof for the given program local data.
+    ///
+    /// The outer circuit additionally checks that the verification key of the witness hashes to the program id.
+    fn evaluate(&self, primary: &Self::PublicInput, witness: &Self::PrivateWitness) -> bool {
+        let verify = || -> Result<bool, DPCError> {
+            let verification_key = S::VerificationParameters::read(&witness.verification_key[..])?;
+            let proof = S::Proof::read(&witness.proof[..])?;
+
+            Ok(S::verify(&verification_key.into(), primary, &proof)?)
+        };
+
+        verify().unwrap_or(false)
+    }
+
+    fn into_compact_repr(&self) -> Vec<u8> {
+        self.identity.clone()
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    base_dpc::{parameters::SystemParameters, program::ProgramLocalDataGadget, BaseDPCComponents},
+    Assignment,
+};
+use snarkvm_algorithms::traits::CRH;
+use snarkvm_gadgets::traits::{
+    algorithms::CRHGadget,
+    utilities::{alloc::AllocGadget, eq::EqGadget, uint::UInt8, ToBytesGadget},
+};
+use snarkvm_r1cs::{errors::SynthesisError, ConstraintSynthesizer, ConstraintSystem};
+use snarkvm_utilities::{to_bytes, ToBytes};
+
+/// Hash lock program
+///
+/// Accepts if and only if the prover knows a preimage of the lock under the local data CRH.
+/// The lock is a constant of the circuit, so each lock has its own program id.
+pub struct HashLockCircuit<C: BaseDPCComponents> {
+    /// System parameters
+    pub system_parameters: Option<SystemParameters<C>>,
+
+    /// Commitment to the program input.
+    pub local_data_root: Option<<C::LocalDataCRH as CRH>::Output>,
+
+    /// Record position
+    pub position: u8,
+
+    /// Hash of the preimage that unlocks the program.
+    pub lock: <C::LocalDataCRH as CRH>::Output,
+
+    /// Preimage of the lock.
+    pub preimage: Option<[u8; 32]>,
+}
+
+impl<C: BaseDPCComponents> HashLockCircuit<C> {
+    pub fn blank(system_parameters: &SystemParameters<C>, lock: &<C::LocalDataCRH as CRH>::Output) -> Self {
+        let local_data_root = <C::LocalDataCRH as CRH>::Output::default();
+
+        Self {
+            system_parameters: Some(system_parameters.clone()),
+            local_data_root: Some(local_data_root),
+            position: 0u8,
+            lock: lock.clone(),
+            preimage: Some([0u8; 32]),
+        }
+    }
+
+    pub fn new(
+        system_parameters: &SystemParameters<C>,
+        local_data_root: &<C::LocalDataCRH as CRH>::Output,
+        position: u8,
+        lock: &<C::LocalDataCRH as CRH>::Output,
+        preimage: &[u8; 32],
+    ) -> Self {
+        Self {
+            system_parameters: Some(system_parameters.clone()),
+            local_data_root: Some(local_data_root.clone()),
+            position,
+            lock: lock.clone(),
+            preimage: Some(*preimage),
+        }
+    }
+}
+
+impl<C: BaseDPCComponents> ConstraintSynthesizer<C::InnerField> for HashLockCircuit<C> {
+    fn generate_constraints<CS: ConstraintSystem<C::InnerField>>(&self, cs: &mut CS) -> Result<(), SynthesisError> {
+        execute_hash_lock_gadget(
+            cs,
+            self.system_parameters.get()?,
+            self.local_data_root.get()?,
+            self.position,
+            &self.lock,
+            self.preimage.get()?,
+        )
+    }
+}
+
+fn execute_hash_lock_gadget<C: BaseDPCComponents, CS: ConstraintSystem<C::InnerField>>(
+    cs: &mut CS,
+    system_parameters: &SystemParameters<C>,
+    local_data_root: &<C::LocalDataCRH as CRH>::Output,
+    position: u8,
+    lock: &<C::LocalDataCRH as CRH>::Output,
+    preimage: &[u8; 32],
+) -> Result<(), SynthesisError> {
+    let _local_data = ProgramLocalDataGadget::<C>::alloc_input(
+        cs.ns(|| "Allocate program local data"),
+        system_parameters,
+        local_data_root,
+        position,
+    )?;
+
+    let local_data_crh_parameters = <C::LocalDataCRHGadget as CRHGadget<_, _>>::ParametersGadget::alloc(
+        &mut cs.ns(|| "Declare local data crh parameters"),
+        || Ok(system_parameters.local_data_crh.parameters().clone()),
+    )?;
+
+    let preimage = UInt8::alloc_vec(cs.ns(|| "Allocate preimage"), preimage)?;
+
+    let hash = C::LocalDataCRHGadget::check_evaluation_gadget(
+        cs.ns(|| "Compute preimage hash"),
+        &local_data_crh_parameters,
+        preimage,
+    )?;
+    let hash_bytes = hash.to_bytes(cs.ns(|| "Convert preimage hash to bytes"))?;
+
+    let lock_bytes = UInt8::constant_vec(&to_bytes![lock].map_err(|_| SynthesisError::AssignmentMissing)?);
+
+    hash_bytes.enforce_equal(cs.ns(|| "Check that the preimage hash matches the lock"), &lock_bytes)?;
+
+    Ok(())
+}
@@ -14,6 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod hash_lock_program;
+pub use hash_lock_program::*;
+
+pub mod hash_lock_program_circuit;
+pub use hash_lock_program_circuit::*;
+
 pub mod noop_program;
 pub use noop_program::*;
 
@@ -22,3 +28,9 @@ pub use noop_program_circuit::*;
 
 pub mod program;
 pub use program::*;
+
+pub mod program_registry;
+pub use program_registry::*;
+
+#[cfg(test)]
+mod tests;
@@ -15,14 +15,10 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    base_dpc::{parameters::SystemParameters, BaseDPCComponents},
+    base_dpc::{parameters::SystemParameters, program::ProgramLocalDataGadget, BaseDPCComponents},
     Assignment,
 };
-use snarkvm_algorithms::traits::{CommitmentScheme, CRH};
-use snarkvm_gadgets::traits::{
-    algorithms::{CRHGadget, CommitmentGadget},
-    utilities::{alloc::AllocGadget, uint::UInt8},
-};
+use snarkvm_algorithms::traits::CRH;
 use snarkvm_r1cs::{errors::SynthesisError, ConstraintSynthesizer, ConstraintSystem};
 
 /// Always-accept program
@@ -78,17 +74,11 @@ fn execute_noop_gadget<C: BaseDPCComponents, CS: ConstraintSystem<C::InnerField>
     local_data_root: &<C::LocalDataCRH as CRH>::Output,
     position: u8,
 ) -> Result<(), SynthesisError> {
-    let _position = UInt8::alloc_input_vec(cs.ns(|| "Alloc position"), &[position])?;
-
-    let _local_data_commitment_parameters_gadget =
-        <C::LocalDataCommitmentGadget as CommitmentGadget<_, _>>::ParametersGadget::alloc_input(
-            &mut cs.ns(|| "Declare local data commitment parameters"),
-            || Ok(system_parameters.local_data_commitment.parameters().clone()),
-        )?;
-
-    let _local_data_root_gadget = <C::LocalDataCRHGadget as CRHGadget<_, _>>::OutputGadget::alloc_input(
-        cs.ns(|| "Allocate local data root"),
-        || Ok(local_data_root),
+    let _local_data = ProgramLocalDataGadget::<C>::alloc_input(
+        cs.ns(|| "Allocate program local data"),
+        system_parameters,
+        local_data_root,
+        position,
     )?;
 
     Ok(())
@@ -14,9 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::base_dpc::BaseDPCComponents;
+use crate::base_dpc::{parameters::SystemParameters, BaseDPCComponents};
 use snarkvm_algorithms::traits::{CommitmentScheme, CRH};
 use snarkvm_fields::{errors::ConstraintFieldError, traits::to_field_vec::ToConstraintField};
+use snarkvm_gadgets::traits::{
+    algorithms::{CRHGadget, CommitmentGadget},
+    utilities::{alloc::AllocGadget, uint::UInt8},
+};
+use snarkvm_r1cs::{errors::SynthesisError, ConstraintSystem};
 
 /// Program verification key and proof
 /// Represented as bytes to be generic for any Program SNARK
@@ -55,3 +60,42 @@ where
         Ok(v)
     }
 }
+
+/// The public inputs of a program circuit, allocated in the order of `ProgramLocalData`.
+///
+/// The outer circuit verifies every program proof against these public inputs,
+/// so every program circuit must allocate them before any other public input.
+pub struct ProgramLocalDataGadget<C: BaseDPCComponents> {
+    pub position: Vec<UInt8>,
+    pub local_data_commitment_parameters:
+        <C::LocalDataCommitmentGadget as CommitmentGadget<C::LocalDataCommitment, C::InnerField>>::ParametersGadget,
+    pub local_data_root: <C::LocalDataCRHGadget as CRHGadget<C::LocalDataCRH, C::InnerField>>::OutputGadget,
+}
+
+impl<C: BaseDPCComponents> ProgramLocalDataGadget<C> {
+    pub fn alloc_input<CS: ConstraintSystem<C::InnerField>>(
+        mut cs: CS,
+        system_parameters: &SystemParameters<C>,
+        local_data_root: &<C::LocalDataCRH as CRH>::Output,
+        position: u8,
+    ) -> Result<Self, SynthesisError> {
+        let position = UInt8::alloc_input_vec(cs.ns(|| "Alloc position"), &[position])?;
+
+        let local_data_commitment_parameters =
+            <C::LocalDataCommitmentGadget as CommitmentGadget<_, _>>::ParametersGadget::alloc_input(
+                &mut cs.ns(|| "Declare local data commitment parameters"),
+                || Ok(system_parameters.local_data_commitment.parameters().clone()),
+            )?;
+
+        let local_data_root = <C::LocalDataCRHGadget as CRHGadget<_, _>>::OutputGadget::alloc_input(
+            cs.ns(|| "Allocate local data root"),
+            || Ok(local_data_root),
+        )?;
+
+        Ok(Self {
+            position,
+            local_data_commitment_parameters,
+            local_data_root,
+        })
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    base_dpc::{parameters::SystemParameters, BaseDPCComponents, ProgramSNARKParameters},
+    errors::DPCError,
+};
+use snarkvm_algorithms::traits::{CRH, SNARK};
+use snarkvm_utilities::{to_bytes, ToBytes};
+
+use rand::Rng;
+use std::collections::HashMap;
+
+/// Stores the SNARK parameters of the programs that records can use as birth and death programs,
+/// indexed by their program ids.
+///
+/// A program id is the hash of the program verification key under the program verification key CRH.
+/// The outer circuit verifies every program proof with `C::ProgramSNARKGadget`, so a program circuit
+/// must be proven with the proof system of `C::NoopProgramSNARK`, and must allocate its
+/// `ProgramLocalData` public inputs with `ProgramLocalDataGadget`.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "C: BaseDPCComponents"))]
+pub struct ProgramRegistry<C: BaseDPCComponents> {
+    system_parameters: SystemParameters<C>,
+    programs: HashMap<Vec<u8>, ProgramSNARKParameters<C>>,
+}
+
+impl<C: BaseDPCComponents> ProgramRegistry<C> {
+    pub fn new(system_parameters: SystemParameters<C>) -> Self {
+        Self {
+            system_parameters,
+            programs: HashMap::new(),
+        }
+    }
+
+    /// Returns the program id of the given program verification key.
+    pub fn program_id(
+        &self,
+        verification_key: &<C::NoopProgramSNARK as SNARK>::VerificationParameters,
+    ) -> Result<Vec<u8>, DPCError> {
+        let program_id = self
+            .system_parameters
+            .program_verification_key_crh
+            .hash(&to_bytes![verification_key]?)?;
+
+        Ok(to_bytes![program_id]?)
+    }
+
+    /// Registers the program with the given parameters, and returns its program id.
+    pub fn register(&mut self, parameters: ProgramSNARKParameters<C>) -> Result<Vec<u8>, DPCError> {
+        let program_id = self.program_id(&parameters.verification_key)?;
+        self.programs.insert(program_id.clone(), parameters);
+
+        Ok(program_id)
+    }
+
+    /// Generates the parameters of the given program circuit, registers the program,
+    /// and returns its program id.
+    pub fn setup<S, R: Rng>(&mut self, circuit: &S::Circuit, rng: &mut R) -> Result<Vec<u8>, DPCError>
+    where
+        S: SNARK<
+            ProvingParameters = <C::NoopProgramSNARK as SNARK>::ProvingParameters,
+            PreparedVerificationParameters = <C::NoopProgramSNARK as SNARK>::PreparedVerificationParameters,
+            VerificationParameters = <C::NoopProgramSNARK as SNARK>::VerificationParameters,
+        >,
+    {
+        let (proving_key, prepared_verification_key) = S::setup(circuit, rng)?;
+
+        self.register(ProgramSNARKParameters {
+            proving_key,
+            verification_key: prepared_verification_key.into(),
+        })
+    }
+
+    /// Returns the parameters of the program with the given program id, if it is registered.
+    pub fn get(&self, program_id: &[u8]) -> Option<&ProgramSNARKParameters<C>> {
+        self.programs.get(program_id)
+    }
+
+    /// Returns `true` if the program with the given program id is registered.
+    pub fn contains(&self, program_id: &[u8]) -> bool {
+        self.programs.contains_key(program_id)
+    }
+
+    /// Returns the number of registered programs.
+    pub fn len(&self) -> usize {
+        self.programs.len()
+    }
+
+    /// Returns `true` if no programs are registered.
+    pub fn is_empty(&self) -> bool {
+        self.programs.is_empty()
+    }
+}
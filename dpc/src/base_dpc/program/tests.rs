@@ -0,0 +1,281 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    account::Account,
+    base_dpc::{execute_program_proof_gadget, instantiated::*, program::*, record_payload::RecordPayload, DPC},
+    errors::{DPCError, ProgramError},
+    traits::{AccountScheme, DPCScheme, Program},
+};
+use snarkvm_algorithms::traits::{CommitmentScheme, CRH};
+use snarkvm_curves::bls12_377::Fq;
+use snarkvm_fields::traits::to_field_vec::ToConstraintField;
+use snarkvm_gadgets::traits::{
+    algorithms::CRHGadget,
+    utilities::{
+        alloc::AllocGadget,
+        boolean::Boolean,
+        uint::{UInt, UInt8},
+    },
+};
+use snarkvm_r1cs::{ConstraintSystem, TestConstraintSystem};
+use snarkvm_testing::storage::*;
+use snarkvm_utilities::{bytes::ToBytes, to_bytes};
+
+use rand::{Rng, SeedableRng};
+use rand_xorshift::XorShiftRng;
+
+type L = Ledger<Tx, CommitmentMerkleParameters, MemDb>;
+
+type TestHashLockProgram = HashLockProgram<Components, HashLockProgramSNARK<Components>>;
+
+/// Allocates the program input of the outer circuit for the given local data root.
+fn alloc_program_input_bits<CS: ConstraintSystem<Fq>>(cs: &mut CS, local_data: &LocalData) -> Vec<Vec<Boolean>> {
+    let local_data_crh_parameters_fe =
+        ToConstraintField::<InnerField>::to_field_elements(local_data.system_parameters.local_data_crh.parameters())
+            .unwrap();
+    let local_data_root_fe =
+        ToConstraintField::<InnerField>::to_field_elements(&local_data.local_data_merkle_tree.root()).unwrap();
+
+    let mut program_input_bits = vec![];
+    for (i, field_elements) in [local_data_crh_parameters_fe, local_data_root_fe].iter().enumerate() {
+        let bytes = UInt8::alloc_input_vec(
+            cs.ns(|| format!("Allocate input {}", i)),
+            &to_bytes![field_elements].unwrap(),
+        )
+        .unwrap();
+        let bits = bytes.iter().flat_map(|byte| byte.to_bits_le()).collect::<Vec<_>>();
+        if !bits.is_empty() {
+            program_input_bits.push(bits);
+        }
+    }
+    program_input_bits
+}
+
+#[test]
+fn test_program_ids() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    let system_parameters = InstantiatedDPC::generate_system_parameters(&mut rng).unwrap();
+
+    let mut registry = ProgramRegistry::new(system_parameters.clone());
+
+    let noop_program_snark_parameters =
+        InstantiatedDPC::generate_noop_program_snark_parameters(&system_parameters, &mut rng).unwrap();
+    let noop_program_id = registry.register(noop_program_snark_parameters.clone()).unwrap();
+    assert_eq!(
+        noop_program_id,
+        registry
+            .program_id(&noop_program_snark_parameters.verification_key)
+            .unwrap()
+    );
+
+    // Programs with different circuits have different program ids.
+    let lock = TestHashLockProgram::lock(&system_parameters.local_data_crh, &[1u8; 32]).unwrap();
+    let hash_lock_program_id = registry
+        .setup::<HashLockProgramSNARK<Components>, _>(&HashLockCircuit::blank(&system_parameters, &lock), &mut rng)
+        .unwrap();
+
+    let other_lock = TestHashLockProgram::lock(&system_parameters.local_data_crh, &[2u8; 32]).unwrap();
+    let other_hash_lock_program_id = registry
+        .setup::<HashLockProgramSNARK<Components>, _>(
+            &HashLockCircuit::blank(&system_parameters, &other_lock),
+            &mut rng,
+        )
+        .unwrap();
+
+    assert_ne!(hash_lock_program_id, noop_program_id);
+    assert_ne!(hash_lock_program_id, other_hash_lock_program_id);
+
+    assert_eq!(3, registry.len());
+    assert!(registry.contains(&noop_program_id));
+    assert!(registry.contains(&hash_lock_program_id));
+    assert!(registry.contains(&other_hash_lock_program_id));
+    assert!(!registry.contains(&[0u8; 32]));
+}
+
+#[test]
+fn test_hash_lock_program() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    let system_parameters = InstantiatedDPC::generate_system_parameters(&mut rng).unwrap();
+
+    let mut registry = ProgramRegistry::new(system_parameters.clone());
+
+    let noop_program_id = registry
+        .setup::<NoopProgramSNARK<Components>, _>(&NoopCircuit::blank(&system_parameters), &mut rng)
+        .unwrap();
+
+    let preimage: [u8; 32] = rng.gen();
+    let lock = TestHashLockProgram::lock(&system_parameters.local_data_crh, &preimage).unwrap();
+    let hash_lock_program_id = registry
+        .setup::<HashLockProgramSNARK<Components>, _>(&HashLockCircuit::blank(&system_parameters, &lock), &mut rng)
+        .unwrap();
+    let hash_lock_program_snark_parameters = registry.get(&hash_lock_program_id).unwrap();
+
+    // Construct a transaction kernel that spends records locked by the hash lock program.
+
+    let account = Account::new(
+        &system_parameters.account_signature,
+        &system_parameters.account_commitment,
+        &system_parameters.account_encryption,
+        &mut rng,
+    )
+    .unwrap();
+
+    let sn_nonce =
+        SerialNumberNonce::<NUM_INPUT_RECORDS>::hash(&system_parameters.serial_number_nonce, &[0u8; 1]).unwrap();
+    let old_record = DPC::generate_record(
+        &system_parameters,
+        sn_nonce,
+        account.address.clone(),
+        true,
+        0,
+        RecordPayload::default(),
+        noop_program_id.clone(),
+        hash_lock_program_id.clone(),
+        &mut rng,
+    )
+    .unwrap();
+
+    let transaction_kernel = <InstantiatedDPC as DPCScheme<L>>::execute_offline(
+        system_parameters.clone(),
+        vec![old_record; NUM_INPUT_RECORDS],
+        vec![account.private_key.clone(); NUM_INPUT_RECORDS],
+        vec![account.address; NUM_OUTPUT_RECORDS],
+        &[true; NUM_OUTPUT_RECORDS],
+        &[0; NUM_OUTPUT_RECORDS],
+        vec![RecordPayload::default(); NUM_OUTPUT_RECORDS],
+        vec![noop_program_id.clone(); NUM_OUTPUT_RECORDS],
+        vec![noop_program_id; NUM_OUTPUT_RECORDS],
+        [0u8; 32],
+        0,
+        &mut rng,
+    )
+    .unwrap();
+
+    let local_data = transaction_kernel.into_local_data();
+
+    // Check that the program can't be executed without the preimage.
+
+    let program = TestHashLockProgram::new(hash_lock_program_id.clone(), lock, [0u8; 32]);
+    let result = program.execute(
+        &hash_lock_program_snark_parameters.proving_key,
+        &hash_lock_program_snark_parameters.verification_key,
+        &local_data,
+        0,
+        &mut rng,
+    );
+    assert!(matches!(result, Err(DPCError::InvalidProgramProof(0))));
+
+    // Check that the program proof is accepted by the outer circuit.
+
+    let program = TestHashLockProgram::new(hash_lock_program_id.clone(), lock, preimage);
+    let private_input = program
+        .execute(
+            &hash_lock_program_snark_parameters.proving_key,
+            &hash_lock_program_snark_parameters.verification_key,
+            &local_data,
+            0,
+            &mut rng,
+        )
+        .unwrap();
+
+    // Check that the program proof only holds for the record position it was proven for.
+
+    let program_local_data = |position: u8| ProgramLocalData::<Components> {
+        local_data_commitment_parameters: system_parameters.local_data_commitment.parameters().clone(),
+        local_data_root: local_data.local_data_merkle_tree.root(),
+        position,
+    };
+    assert!(program.evaluate(&program_local_data(0), &private_input));
+    assert!(!program.evaluate(&program_local_data(1), &private_input));
+
+    // Check that the program only proves the records it is a program of.
+
+    let num_records = (NUM_INPUT_RECORDS + NUM_OUTPUT_RECORDS) as u8;
+    let result = program.execute(
+        &hash_lock_program_snark_parameters.proving_key,
+        &hash_lock_program_snark_parameters.verification_key,
+        &local_data,
+        num_records,
+        &mut rng,
+    );
+    assert!(matches!(
+        result,
+        Err(DPCError::ProgramError(ProgramError::PositionOutOfBounds(position))) if position == num_records
+    ));
+
+    let result = program.execute(
+        &hash_lock_program_snark_parameters.proving_key,
+        &hash_lock_program_snark_parameters.verification_key,
+        &local_data,
+        NUM_INPUT_RECORDS as u8,
+        &mut rng,
+    );
+    assert!(matches!(
+        result,
+        Err(DPCError::ProgramError(ProgramError::MismatchedBirthProgram(_)))
+    ));
+
+    let mut cs = TestConstraintSystem::<Fq>::new();
+
+    let program_vk_crh_parameters =
+        <ProgramVerificationKeyCRHGadget as CRHGadget<_, _>>::ParametersGadget::alloc_input(
+            &mut cs.ns(|| "Declare program_vk_crh_parameters"),
+            || Ok(system_parameters.program_verification_key_crh.parameters()),
+        )
+        .unwrap();
+    let program_input_bits = alloc_program_input_bits(&mut cs, &local_data);
+
+    let program_id = execute_program_proof_gadget::<Components, _>(
+        &mut cs.ns(|| "Check death program"),
+        &program_vk_crh_parameters,
+        &program_input_bits,
+        &private_input,
+        0,
+    )
+    .unwrap();
+
+    assert!(cs.is_satisfied());
+    assert_eq!(
+        program_id.iter().map(|byte| byte.value.unwrap()).collect::<Vec<_>>(),
+        hash_lock_program_id
+    );
+
+    // Check that the program proof is rejected for another record position.
+
+    let mut cs = TestConstraintSystem::<Fq>::new();
+
+    let program_vk_crh_parameters =
+        <ProgramVerificationKeyCRHGadget as CRHGadget<_, _>>::ParametersGadget::alloc_input(
+            &mut cs.ns(|| "Declare program_vk_crh_parameters"),
+            || Ok(system_parameters.program_verification_key_crh.parameters()),
+        )
+        .unwrap();
+    let program_input_bits = alloc_program_input_bits(&mut cs, &local_data);
+
+    execute_program_proof_gadget::<Components, _>(
+        &mut cs.ns(|| "Check death program"),
+        &program_vk_crh_parameters,
+        &program_input_bits,
+        &private_input,
+        1,
+    )
+    .unwrap();
+
+    assert!(!cs.is_satisfied());
+}
@@ -28,5 +28,8 @@ pub use record_encryption::*;
 
 pub mod record_payload;
 
+pub mod record_scanner;
+pub use record_scanner::*;
+
 #[cfg(test)]
 mod tests;
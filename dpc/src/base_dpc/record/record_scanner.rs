@@ -0,0 +1,240 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    account::{AccountAddress, AccountPrivateKey, AccountViewKey},
+    base_dpc::{
+        parameters::SystemParameters,
+        record::{encrypted_record::EncryptedRecord, record_encryption::RecordEncryption, DPCRecord},
+        transaction::DPCTransaction,
+        BaseDPCComponents,
+        DPC,
+    },
+    errors::DPCError,
+    traits::Record,
+};
+use snarkvm_algorithms::traits::{CommitmentScheme, SignatureScheme};
+use snarkvm_objects::{traits::Transaction, Block};
+
+use std::collections::HashMap;
+
+type SerialNumber<C> = <<C as crate::traits::DPCComponents>::AccountSignature as SignatureScheme>::PublicKey;
+type Commitment<C> = <<C as crate::traits::DPCComponents>::RecordCommitment as CommitmentScheme>::Output;
+
+/// A record that belongs to the scanned account, and where it was created and spent.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "C: BaseDPCComponents"), Debug(bound = "C: BaseDPCComponents"))]
+pub struct ScannedRecord<C: BaseDPCComponents> {
+    /// The decrypted record
+    pub record: DPCRecord<C>,
+    /// The id of the transaction that created the record
+    pub transaction_id: [u8; 32],
+    /// The height of the block that created the record
+    pub block_height: u32,
+    /// The serial number of the record, if the scanner has the account private key
+    pub serial_number: Option<SerialNumber<C>>,
+    /// The height of the block that spent the record, if it has been spent
+    pub spent_block_height: Option<u32>,
+}
+
+impl<C: BaseDPCComponents> ScannedRecord<C> {
+    /// Returns `true` if the record has been spent in a scanned block.
+    pub fn is_spent(&self) -> bool {
+        self.spent_block_height.is_some()
+    }
+}
+
+/// Finds the records that belong to an account by trial-decrypting the encrypted records
+/// of each transaction with the account view key.
+///
+/// Spent records can only be detected when the scanner has the account private key,
+/// as the serial number of a record is derived from it. When blocks are decommitted by a fork,
+/// `revert_to_block_height` forgets them before the blocks of the new canon chain are scanned.
+pub struct RecordScanner<C: BaseDPCComponents> {
+    system_parameters: SystemParameters<C>,
+    view_key: AccountViewKey<C>,
+    address: AccountAddress<C>,
+    private_key: Option<AccountPrivateKey<C>>,
+    records: Vec<ScannedRecord<C>>,
+    /// The index in `records` of each record, by commitment
+    record_indexes: HashMap<Commitment<C>, usize>,
+    /// The index in `records` of each record, by serial number
+    serial_number_indexes: HashMap<SerialNumber<C>, usize>,
+    /// The block height of every serial number spent in the scanned transactions
+    spent_serial_numbers: HashMap<SerialNumber<C>, u32>,
+}
+
+impl<C: BaseDPCComponents> RecordScanner<C> {
+    /// Creates a scanner that finds the records of the account with the given view key.
+    pub fn new(system_parameters: SystemParameters<C>, view_key: AccountViewKey<C>) -> Result<Self, DPCError> {
+        let address = AccountAddress::from_view_key(&system_parameters.account_encryption, &view_key)?;
+
+        Ok(Self {
+            system_parameters,
+            view_key,
+            address,
+            private_key: None,
+            records: vec![],
+            record_indexes: HashMap::new(),
+            serial_number_indexes: HashMap::new(),
+            spent_serial_numbers: HashMap::new(),
+        })
+    }
+
+    /// Creates a scanner that finds the records of the account with the given private key,
+    /// and tracks whether they are spent.
+    pub fn from_private_key(
+        system_parameters: SystemParameters<C>,
+        private_key: AccountPrivateKey<C>,
+    ) -> Result<Self, DPCError> {
+        let view_key = AccountViewKey::from_private_key(
+            &system_parameters.account_signature,
+            &system_parameters.account_commitment,
+            &private_key,
+        )?;
+
+        let mut scanner = Self::new(system_parameters, view_key)?;
+        scanner.private_key = Some(private_key);
+        Ok(scanner)
+    }
+
+    /// Returns the address of the scanned account.
+    pub fn address(&self) -> &AccountAddress<C> {
+        &self.address
+    }
+
+    /// Returns all the records found so far, in the order they were created.
+    pub fn records(&self) -> &[ScannedRecord<C>] {
+        &self.records
+    }
+
+    /// Returns the records found so far that have been spent.
+    pub fn spent_records(&self) -> impl Iterator<Item = &ScannedRecord<C>> {
+        self.records.iter().filter(|record| record.is_spent())
+    }
+
+    /// Returns the records found so far that have not been spent in a scanned block.
+    pub fn unspent_records(&self) -> impl Iterator<Item = &ScannedRecord<C>> {
+        self.records.iter().filter(|record| !record.is_spent())
+    }
+
+    /// Returns the total value of the unspent records.
+    pub fn balance(&self) -> u64 {
+        self.unspent_records().map(|record| record.record.value()).sum()
+    }
+
+    /// Scans the transactions of a block, and returns the number of new records found.
+    pub fn scan_block(&mut self, block: &Block<DPCTransaction<C>>, block_height: u32) -> Result<usize, DPCError> {
+        let mut num_records = 0;
+        for transaction in block.transactions.iter() {
+            num_records += self.scan_transaction(transaction, block_height)?;
+        }
+        Ok(num_records)
+    }
+
+    /// Scans a transaction included at the given block height, and returns the number of new records found.
+    pub fn scan_transaction(&mut self, transaction: &DPCTransaction<C>, block_height: u32) -> Result<usize, DPCError> {
+        // Mark the records spent by the transaction.
+        for serial_number in transaction.old_serial_numbers() {
+            self.spent_serial_numbers.insert(serial_number.clone(), block_height);
+
+            if let Some(index) = self.serial_number_indexes.get(serial_number) {
+                self.records[*index].spent_block_height = Some(block_height);
+            }
+        }
+
+        let transaction_id = transaction.transaction_id()?;
+
+        let mut num_records = 0;
+        for (commitment, encrypted_record) in transaction.new_commitments().iter().zip(&transaction.encrypted_records) {
+            // Skip the records that were already found, e.g. if a block is scanned twice.
+            if self.record_indexes.contains_key(commitment) {
+                continue;
+            }
+
+            let record = match self.decrypt_record(commitment, encrypted_record) {
+                Some(record) => record,
+                None => continue,
+            };
+
+            let serial_number = match &self.private_key {
+                Some(private_key) => Some(DPC::generate_sn(&self.system_parameters, &record, private_key)?.0),
+                None => None,
+            };
+
+            let spent_block_height = serial_number
+                .as_ref()
+                .and_then(|serial_number| self.spent_serial_numbers.get(serial_number).copied());
+
+            self.record_indexes.insert(commitment.clone(), self.records.len());
+            if let Some(serial_number) = &serial_number {
+                self.serial_number_indexes
+                    .insert(serial_number.clone(), self.records.len());
+            }
+
+            self.records.push(ScannedRecord {
+                record,
+                transaction_id,
+                block_height,
+                serial_number,
+                spent_block_height,
+            });
+            num_records += 1;
+        }
+
+        Ok(num_records)
+    }
+
+    /// Forgets the scanned blocks above the given block height, e.g. when they are decommitted
+    /// by a fork. The records they created are dropped, and the records they spent are unspent.
+    pub fn revert_to_block_height(&mut self, block_height: u32) {
+        self.records.retain(|scanned| scanned.block_height <= block_height);
+        self.spent_serial_numbers
+            .retain(|_, spent_block_height| *spent_block_height <= block_height);
+
+        self.record_indexes.clear();
+        self.serial_number_indexes.clear();
+
+        for (index, scanned) in self.records.iter_mut().enumerate() {
+            if scanned.spent_block_height > Some(block_height) {
+                scanned.spent_block_height = None;
+            }
+
+            self.record_indexes.insert(scanned.record.commitment(), index);
+            if let Some(serial_number) = &scanned.serial_number {
+                self.serial_number_indexes.insert(serial_number.clone(), index);
+            }
+        }
+    }
+
+    /// Returns the decrypted record if it belongs to the scanned account and matches its commitment.
+    fn decrypt_record(
+        &self,
+        commitment: &<C::RecordCommitment as CommitmentScheme>::Output,
+        encrypted_record: &EncryptedRecord<C>,
+    ) -> Option<DPCRecord<C>> {
+        // Decryption fails or returns an unrelated record if the record was encrypted for another account.
+        let record =
+            RecordEncryption::decrypt_record(&self.system_parameters, &self.view_key, encrypted_record).ok()?;
+
+        // The decrypted record is recommitted, so a matching commitment proves it is the record on the ledger.
+        if &record.commitment() != commitment || record.owner() != &self.address || record.is_dummy() {
+            return None;
+        }
+
+        Some(record)
+    }
+}
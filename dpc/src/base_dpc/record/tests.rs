@@ -14,14 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use super::{record_encryption::*, record_serializer::*};
+use super::{record_encryption::*, record_scanner::*, record_serializer::*, DPCRecord};
 use crate::{
     account::{Account, AccountViewKey},
-    base_dpc::{instantiated::*, record_payload::RecordPayload, DPC},
-    traits::{AccountScheme, RecordSerializerScheme},
+    base_dpc::{instantiated::*, record_payload::RecordPayload, DPCTransaction, DPC},
+    traits::{AccountScheme, Record, RecordSerializerScheme},
 };
 use snarkvm_algorithms::traits::CRH;
 use snarkvm_curves::edwards_bls12::{EdwardsParameters, EdwardsProjective as EdwardsBls};
+use snarkvm_objects::{traits::Transaction, AleoAmount, Network};
 use snarkvm_utilities::{bytes::ToBytes, to_bytes};
 
 use rand::{Rng, SeedableRng};
@@ -64,7 +65,8 @@ fn test_record_serialization() {
 
             let given_record = DPC::generate_record(
                 &system_parameters,
-                SerialNumberNonce::hash(&system_parameters.serial_number_nonce, &sn_nonce_input).unwrap(),
+                SerialNumberNonce::<NUM_INPUT_RECORDS>::hash(&system_parameters.serial_number_nonce, &sn_nonce_input)
+                    .unwrap(),
                 dummy_account.address,
                 false,
                 value,
@@ -131,7 +133,8 @@ fn test_record_encryption() {
 
             let given_record = DPC::generate_record(
                 &system_parameters,
-                SerialNumberNonce::hash(&system_parameters.serial_number_nonce, &sn_nonce_input).unwrap(),
+                SerialNumberNonce::<NUM_INPUT_RECORDS>::hash(&system_parameters.serial_number_nonce, &sn_nonce_input)
+                    .unwrap(),
                 dummy_account.address,
                 false,
                 value,
@@ -160,3 +163,149 @@ fn test_record_encryption() {
         }
     }
 }
+
+#[test]
+fn test_record_scanner() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    let system_parameters = InstantiatedDPC::generate_system_parameters(&mut rng).unwrap();
+    let program_snark_pp =
+        InstantiatedDPC::generate_noop_program_snark_parameters(&system_parameters, &mut rng).unwrap();
+
+    let program_snark_vk_bytes = to_bytes![
+        ProgramVerificationKeyCRH::hash(
+            &system_parameters.program_verification_key_crh,
+            &to_bytes![program_snark_pp.verification_key].unwrap()
+        )
+        .unwrap()
+    ]
+    .unwrap();
+
+    let new_account = |rng: &mut XorShiftRng| {
+        Account::new(
+            &system_parameters.account_signature,
+            &system_parameters.account_commitment,
+            &system_parameters.account_encryption,
+            rng,
+        )
+        .unwrap()
+    };
+    let account = new_account(&mut rng);
+    let other_account = new_account(&mut rng);
+
+    let new_record = |owner: &Account<Components>, is_dummy: bool, value: u64, rng: &mut XorShiftRng| {
+        let sn_nonce_input: [u8; 32] = rng.gen();
+        DPC::generate_record(
+            &system_parameters,
+            SerialNumberNonce::<NUM_INPUT_RECORDS>::hash(&system_parameters.serial_number_nonce, &sn_nonce_input)
+                .unwrap(),
+            owner.address.clone(),
+            is_dummy,
+            value,
+            RecordPayload::default(),
+            program_snark_vk_bytes.clone(),
+            program_snark_vk_bytes.clone(),
+            rng,
+        )
+        .unwrap()
+    };
+
+    let new_transaction = |serial_numbers: Vec<_>, records: &[&DPCRecord<Components>], rng: &mut XorShiftRng| {
+        let encrypted_records = records
+            .iter()
+            .map(|record| {
+                RecordEncryption::encrypt_record(&system_parameters, record, rng)
+                    .unwrap()
+                    .1
+            })
+            .collect();
+
+        DPCTransaction::<Components>::new(
+            serial_numbers,
+            records.iter().map(|record| record.commitment()).collect(),
+            rng.gen(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            AleoAmount(0),
+            Network::Mainnet,
+            vec![],
+            encrypted_records,
+        )
+    };
+
+    let owned_record = new_record(&account, false, 10, &mut rng);
+    let dummy_record = new_record(&account, true, 0, &mut rng);
+    let other_record = new_record(&other_account, false, 20, &mut rng);
+
+    // A record whose encrypted record doesn't match its on-chain commitment is ignored.
+    let mut mismatched_transaction = new_transaction(vec![], &[&other_record, &owned_record], &mut rng);
+    mismatched_transaction.encrypted_records.swap(0, 1);
+
+    let transaction = new_transaction(vec![], &[&owned_record, &dummy_record, &other_record], &mut rng);
+
+    let mut scanner = RecordScanner::from_private_key(system_parameters.clone(), account.private_key.clone()).unwrap();
+    assert_eq!(scanner.scan_transaction(&mismatched_transaction, 1).unwrap(), 0);
+    assert_eq!(scanner.scan_transaction(&transaction, 2).unwrap(), 1);
+    assert_eq!(scanner.scan_transaction(&transaction, 2).unwrap(), 0);
+
+    assert_eq!(scanner.records().len(), 1);
+    assert_eq!(scanner.records()[0].record, owned_record);
+    assert_eq!(scanner.records()[0].block_height, 2);
+    assert_eq!(
+        scanner.records()[0].transaction_id,
+        transaction.transaction_id().unwrap()
+    );
+    assert_eq!(scanner.unspent_records().count(), 1);
+    assert_eq!(scanner.balance(), 10);
+
+    // Spending the record is detected from its serial number.
+    let (serial_number, _) = DPC::generate_sn(&system_parameters, &owned_record, &account.private_key).unwrap();
+    assert_eq!(scanner.records()[0].serial_number, Some(serial_number));
+
+    let spending_transaction = new_transaction(vec![serial_number], &[&other_record], &mut rng);
+    assert_eq!(scanner.scan_transaction(&spending_transaction, 3).unwrap(), 0);
+
+    assert_eq!(scanner.spent_records().count(), 1);
+    assert_eq!(scanner.records()[0].spent_block_height, Some(3));
+    assert_eq!(scanner.balance(), 0);
+
+    // A view key finds the same records, but can't tell whether they are spent.
+    let view_key = AccountViewKey::from_private_key(
+        &system_parameters.account_signature,
+        &system_parameters.account_commitment,
+        &account.private_key,
+    )
+    .unwrap();
+    let mut view_key_scanner = RecordScanner::new(system_parameters.clone(), view_key).unwrap();
+    assert_eq!(view_key_scanner.address(), &account.address);
+
+    for (block_height, transaction) in [&mismatched_transaction, &transaction, &spending_transaction]
+        .iter()
+        .enumerate()
+    {
+        view_key_scanner
+            .scan_transaction(transaction, block_height as u32 + 1)
+            .unwrap();
+    }
+
+    assert_eq!(view_key_scanner.records().len(), 1);
+    assert_eq!(view_key_scanner.records()[0].record, owned_record);
+    assert_eq!(view_key_scanner.records()[0].serial_number, None);
+    assert_eq!(view_key_scanner.unspent_records().count(), 1);
+
+    // Reverting the spending block unspends the record, and reverting the creating block forgets it.
+    scanner.revert_to_block_height(2);
+    assert_eq!(scanner.records()[0].spent_block_height, None);
+    assert_eq!(scanner.balance(), 10);
+
+    scanner.revert_to_block_height(1);
+    assert!(scanner.records().is_empty());
+
+    // The record is found again when its block is scanned on the new canon chain.
+    assert_eq!(scanner.scan_transaction(&transaction, 2).unwrap(), 1);
+    assert_eq!(scanner.scan_transaction(&spending_transaction, 4).unwrap(), 0);
+    assert_eq!(scanner.records()[0].spent_block_height, Some(4));
+}
@@ -15,19 +15,22 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    account::Account,
+    account::{Account, AccountComputeKey},
     base_dpc::{
         execute_inner_proof_gadget,
         execute_outer_proof_gadget,
         inner_circuit::InnerCircuit,
-        parameters::{NoopProgramSNARKParameters, SystemParameters},
+        parameters::{NoopProgramSNARKParameters, PublicParameters, SystemParameters},
         program::*,
-        record::record_encryption::*,
+        record::{record_encryption::*, DPCRecord},
         record_payload::RecordPayload,
         BaseDPCComponents,
+        DPCTransaction,
+        TransactionBuilder,
         TransactionKernel,
         DPC,
     },
+    errors::TransactionBuilderError,
     instantiated::*,
     traits::{AccountScheme, DPCScheme, Program, Record},
 };
@@ -38,7 +41,7 @@ use snarkvm_algorithms::{
 use snarkvm_curves::bls12_377::{Fq, Fr};
 use snarkvm_objects::{
     dpc::DPCTransactions,
-    traits::LedgerScheme,
+    traits::{LedgerScheme, Transaction},
     Block,
     BlockHeader,
     BlockHeaderHash,
@@ -56,19 +59,19 @@ use snarkvm_utilities::{
 use itertools::Itertools;
 use rand::{Rng, SeedableRng};
 use rand_xorshift::XorShiftRng;
+use std::io::Result as IoResult;
 
-type L = Ledger<Tx, CommitmentMerkleParameters>;
+type L = Ledger<Tx, CommitmentMerkleParameters, MemDb>;
 
 /// Generates and returns noop program parameters and its corresponding program id.
-fn generate_test_noop_program_parameters<R: Rng>(
-    system_parameters: &SystemParameters<Components>,
+fn generate_test_noop_program_parameters<C: BaseDPCComponents, R: Rng>(
+    system_parameters: &SystemParameters<C>,
     rng: &mut R,
-) -> (NoopProgramSNARKParameters<Components>, Vec<u8>) {
-    let noop_program_snark_pp =
-        InstantiatedDPC::generate_noop_program_snark_parameters(&system_parameters, rng).unwrap();
+) -> (NoopProgramSNARKParameters<C>, Vec<u8>) {
+    let noop_program_snark_pp = DPC::<C>::generate_noop_program_snark_parameters(system_parameters, rng).unwrap();
 
     let noop_program_id = to_bytes![
-        ProgramVerificationKeyCRH::hash(
+        C::ProgramVerificationKeyCRH::hash(
             &system_parameters.program_verification_key_crh,
             &to_bytes![noop_program_snark_pp.verification_key].unwrap()
         )
@@ -79,6 +82,66 @@ fn generate_test_noop_program_parameters<R: Rng>(
     (noop_program_snark_pp, noop_program_id)
 }
 
+type VariantLedger<const NUM_INPUTS: usize, const NUM_OUTPUTS: usize> =
+    Ledger<DPCTransaction<ComponentsWithArity<NUM_INPUTS, NUM_OUTPUTS>>, CommitmentMerkleParameters, MemDb>;
+
+/// Initializes a test ledger from an empty genesis block, and generates an account with
+/// `NUM_INPUTS` distinct dummy records that are owned by it and bound to the given program id.
+fn setup_test_ledger_and_dummy_records<const NUM_INPUTS: usize, const NUM_OUTPUTS: usize, R: Rng>(
+    system_parameters: &SystemParameters<ComponentsWithArity<NUM_INPUTS, NUM_OUTPUTS>>,
+    ledger_parameters: CommitmentMerkleParameters,
+    program_id: &[u8],
+    rng: &mut R,
+) -> (
+    VariantLedger<NUM_INPUTS, NUM_OUTPUTS>,
+    Account<ComponentsWithArity<NUM_INPUTS, NUM_OUTPUTS>>,
+    Vec<DPCRecord<ComponentsWithArity<NUM_INPUTS, NUM_OUTPUTS>>>,
+) {
+    let account = Account::new(
+        &system_parameters.account_signature,
+        &system_parameters.account_commitment,
+        &system_parameters.account_encryption,
+        rng,
+    )
+    .unwrap();
+
+    let genesis_block = Block {
+        header: BlockHeader {
+            previous_block_hash: BlockHeaderHash([0u8; 32]),
+            merkle_root_hash: MerkleRootHash([0u8; 32]),
+            time: 0,
+            difficulty_target: 0x07FF_FFFF_FFFF_FFFF_u64,
+            nonce: 0,
+            pedersen_merkle_root_hash: PedersenMerkleRootHash([0u8; 32]),
+            proof: ProofOfSuccinctWork::default(),
+        },
+        transactions: DPCTransactions::new(),
+    };
+    let ledger = initialize_test_blockchain(ledger_parameters, genesis_block);
+
+    // Each record has its own serial number nonce, so the records have distinct serial numbers.
+    let records = (0..NUM_INPUTS)
+        .map(|index| {
+            let sn_nonce =
+                SerialNumberNonce::<NUM_INPUTS>::hash(&system_parameters.serial_number_nonce, &[index as u8]).unwrap();
+            DPC::generate_record(
+                system_parameters,
+                sn_nonce,
+                account.address.clone(),
+                true,
+                0,
+                RecordPayload::default(),
+                program_id.to_vec(),
+                program_id.to_vec(),
+                rng,
+            )
+            .unwrap()
+        })
+        .collect();
+
+    (ledger, account, records)
+}
+
 #[test]
 fn test_transaction_kernel_serialization() {
     let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
@@ -98,7 +161,8 @@ fn test_transaction_kernel_serialization() {
     )
     .unwrap();
 
-    let sn_nonce = SerialNumberNonce::hash(&system_parameters.serial_number_nonce, &[0u8; 1]).unwrap();
+    let sn_nonce =
+        SerialNumberNonce::<NUM_INPUT_RECORDS>::hash(&system_parameters.serial_number_nonce, &[0u8; 1]).unwrap();
     let old_record = DPC::generate_record(
         &system_parameters,
         sn_nonce,
@@ -152,6 +216,256 @@ fn test_transaction_kernel_serialization() {
     assert_eq!(transaction_kernel, recovered_transaction_kernel);
 }
 
+/// Executes a transaction kernel with `NUM_INPUTS` dummy input records and `NUM_OUTPUTS` output records,
+/// and checks that the kernel serializes and that the inner circuit is satisfied.
+fn test_inner_circuit_with_arity<const NUM_INPUTS: usize, const NUM_OUTPUTS: usize>() {
+    type C<const N: usize, const M: usize> = ComponentsWithArity<N, M>;
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    let ledger_parameters = CommitmentMerkleParameters::setup(&mut rng);
+    let system_parameters = DPC::<C<NUM_INPUTS, NUM_OUTPUTS>>::generate_system_parameters(&mut rng).unwrap();
+
+    let (_noop_program_snark_pp, noop_program_id) = generate_test_noop_program_parameters(&system_parameters, &mut rng);
+
+    let (ledger, test_account, old_records) =
+        setup_test_ledger_and_dummy_records(&system_parameters, ledger_parameters, &noop_program_id, &mut rng);
+
+    let old_account_private_keys = vec![test_account.private_key.clone(); NUM_INPUTS];
+
+    let new_record_owners = vec![test_account.address; NUM_OUTPUTS];
+    let new_is_dummy_flags = vec![false; NUM_OUTPUTS];
+    let new_values = vec![10; NUM_OUTPUTS];
+    let new_payloads = vec![RecordPayload::default(); NUM_OUTPUTS];
+    let new_birth_program_ids = vec![noop_program_id.clone(); NUM_OUTPUTS];
+    let new_death_program_ids = vec![noop_program_id; NUM_OUTPUTS];
+    let memo = [0u8; 32];
+
+    let transaction_kernel =
+        <DPC<C<NUM_INPUTS, NUM_OUTPUTS>> as DPCScheme<VariantLedger<NUM_INPUTS, NUM_OUTPUTS>>>::execute_offline(
+            system_parameters.clone(),
+            old_records,
+            old_account_private_keys,
+            new_record_owners,
+            &new_is_dummy_flags,
+            &new_values,
+            new_payloads,
+            new_birth_program_ids,
+            new_death_program_ids,
+            memo,
+            0,
+            &mut rng,
+        )
+        .unwrap();
+
+    // Check that the transaction kernel serializes with the given number of records,
+    // if the bundled system parameters it is deserialized with support them.
+    let transaction_kernel_bytes = to_bytes![&transaction_kernel].unwrap();
+    let recovered_transaction_kernel: IoResult<TransactionKernel<C<NUM_INPUTS, NUM_OUTPUTS>>> =
+        FromBytes::read(&transaction_kernel_bytes[..]);
+    match SystemParameters::<C<NUM_INPUTS, NUM_OUTPUTS>>::load() {
+        Ok(_) => assert_eq!(transaction_kernel, recovered_transaction_kernel.unwrap()),
+        Err(_) => assert!(recovered_transaction_kernel.is_err()),
+    }
+
+    let TransactionKernel {
+        old_records,
+        old_account_private_keys,
+        old_serial_numbers,
+        new_records,
+        new_sn_nonce_randomness,
+        new_commitments,
+        new_records_encryption_randomness,
+        new_encrypted_record_hashes,
+        program_commitment,
+        program_randomness,
+        local_data_merkle_tree,
+        local_data_commitment_randomizers,
+        value_balance,
+        network_id,
+        ..
+    } = transaction_kernel;
+
+    let old_account_compute_keys = old_account_private_keys
+        .iter()
+        .map(|private_key| AccountComputeKey::from_private_key(&system_parameters.account_signature, private_key))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    // All input records are dummies, so they do not need ledger membership witnesses.
+    let old_witnesses = vec![MerklePath::default(); NUM_INPUTS];
+
+    let new_records_encryption_gadget_components = new_records
+        .iter()
+        .zip_eq(&new_records_encryption_randomness)
+        .map(|(record, randomness)| {
+            RecordEncryption::prepare_encryption_gadget_components(&system_parameters, record, randomness).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mut core_cs = TestConstraintSystem::<Fr>::new();
+
+    execute_inner_proof_gadget::<_, _>(
+        &mut core_cs.ns(|| "Core checks"),
+        &system_parameters,
+        ledger.parameters(),
+        &ledger.digest().unwrap(),
+        &old_records,
+        &old_witnesses,
+        &old_account_compute_keys,
+        &old_serial_numbers,
+        &new_records,
+        &new_sn_nonce_randomness,
+        &new_commitments,
+        &new_records_encryption_randomness,
+        &new_records_encryption_gadget_components,
+        &new_encrypted_record_hashes,
+        &program_commitment,
+        &program_randomness,
+        &local_data_merkle_tree.root(),
+        &local_data_commitment_randomizers,
+        &memo,
+        value_balance,
+        network_id,
+    )
+    .unwrap();
+
+    if !core_cs.is_satisfied() {
+        println!("Unsatisfied constraints:");
+        println!("{}", core_cs.which_is_unsatisfied().unwrap());
+    }
+    assert!(core_cs.is_satisfied());
+
+    kill_storage(ledger);
+}
+
+#[test]
+fn test_inner_circuit_with_one_input_and_one_output() {
+    test_inner_circuit_with_arity::<1, 1>();
+}
+
+#[test]
+fn test_inner_circuit_with_four_inputs_and_two_outputs() {
+    test_inner_circuit_with_arity::<4, 2>();
+}
+
+#[test]
+fn test_load_parameters_with_arity() {
+    // The bundled CRH parameters cover one input and one output record, but not four input records.
+    assert!(SystemParameters::<ComponentsWithArity<1, 1>>::load().is_ok());
+    assert!(SystemParameters::<ComponentsWithArity<4, 2>>::load().is_err());
+
+    let system_parameters = SystemParameters::<ComponentsWithArity<4, 2>>::load_unchecked().unwrap();
+    assert!(system_parameters.check_arity().is_err());
+
+    // The bundled SNARK parameters only support two input and two output records.
+    assert!(PublicParameters::<ComponentsWithArity<1, 1>>::load_vk_direct().is_err());
+    assert!(PublicParameters::<ComponentsWithArity<2, 2>>::load_vk_direct().is_ok());
+}
+
+/// Executes a transaction with `NUM_INPUTS` dummy input records and `NUM_OUTPUTS` output records
+/// with parameters generated for that arity, and checks that the transaction verifies.
+fn test_execute_with_arity<const NUM_INPUTS: usize, const NUM_OUTPUTS: usize>() {
+    type C<const N: usize, const M: usize> = ComponentsWithArity<N, M>;
+    type VariantDPC<const N: usize, const M: usize> = DPC<C<N, M>>;
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    let ledger_parameters = CommitmentMerkleParameters::setup(&mut rng);
+    let parameters = <VariantDPC<NUM_INPUTS, NUM_OUTPUTS> as DPCScheme<VariantLedger<NUM_INPUTS, NUM_OUTPUTS>>>::setup(
+        &ledger_parameters,
+        &mut rng,
+    )
+    .unwrap();
+    let system_parameters = &parameters.system_parameters;
+    system_parameters.check_arity().unwrap();
+
+    let noop_program_id = to_bytes![
+        ProgramVerificationKeyCRH::hash(
+            &system_parameters.program_verification_key_crh,
+            &to_bytes![parameters.noop_program_snark_parameters.verification_key].unwrap()
+        )
+        .unwrap()
+    ]
+    .unwrap();
+
+    let (ledger, test_account, old_records) =
+        setup_test_ledger_and_dummy_records(system_parameters, ledger_parameters, &noop_program_id, &mut rng);
+
+    let transaction_kernel =
+        <VariantDPC<NUM_INPUTS, NUM_OUTPUTS> as DPCScheme<VariantLedger<NUM_INPUTS, NUM_OUTPUTS>>>::execute_offline(
+            system_parameters.clone(),
+            old_records,
+            vec![test_account.private_key.clone(); NUM_INPUTS],
+            vec![test_account.address; NUM_OUTPUTS],
+            &[false; NUM_OUTPUTS],
+            &[10; NUM_OUTPUTS],
+            vec![RecordPayload::default(); NUM_OUTPUTS],
+            vec![noop_program_id.clone(); NUM_OUTPUTS],
+            vec![noop_program_id.clone(); NUM_OUTPUTS],
+            [0u8; 32],
+            0,
+            &mut rng,
+        )
+        .unwrap();
+
+    // Generate the noop program proofs for every input and output record.
+    let local_data = transaction_kernel.into_local_data();
+    let noop_program =
+        NoopProgram::<_, <C<NUM_INPUTS, NUM_OUTPUTS> as BaseDPCComponents>::NoopProgramSNARK>::new(noop_program_id);
+
+    let mut program_proofs = (0..(NUM_INPUTS + NUM_OUTPUTS))
+        .map(|position| {
+            noop_program
+                .execute(
+                    &parameters.noop_program_snark_parameters.proving_key,
+                    &parameters.noop_program_snark_parameters.verification_key,
+                    &local_data,
+                    position as u8,
+                    &mut rng,
+                )
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let new_birth_program_proofs = program_proofs.split_off(NUM_INPUTS);
+    let old_death_program_proofs = program_proofs;
+
+    let (new_records, transaction) =
+        <VariantDPC<NUM_INPUTS, NUM_OUTPUTS> as DPCScheme<VariantLedger<NUM_INPUTS, NUM_OUTPUTS>>>::execute_online(
+            &parameters,
+            transaction_kernel,
+            old_death_program_proofs,
+            new_birth_program_proofs,
+            &ledger,
+            &mut rng,
+        )
+        .unwrap();
+
+    assert_eq!(new_records.len(), NUM_OUTPUTS);
+    assert_eq!(transaction.old_serial_numbers().len(), NUM_INPUTS);
+    assert_eq!(transaction.new_commitments().len(), NUM_OUTPUTS);
+    assert!(
+        <VariantDPC<NUM_INPUTS, NUM_OUTPUTS> as DPCScheme<VariantLedger<NUM_INPUTS, NUM_OUTPUTS>>>::verify(
+            &parameters,
+            &transaction,
+            &ledger
+        )
+        .unwrap()
+    );
+
+    kill_storage(ledger);
+}
+
+#[test]
+fn test_execute_with_one_input_and_one_output() {
+    test_execute_with_arity::<1, 1>();
+}
+
+#[test]
+fn test_execute_with_four_inputs_and_two_outputs() {
+    test_execute_with_arity::<4, 2>();
+}
+
 #[test]
 fn test_execute_base_dpc_constraints() {
     let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
@@ -168,51 +482,15 @@ fn test_execute_base_dpc_constraints() {
     let (alternate_noop_program_snark_pp, alternate_noop_program_id) =
         generate_test_noop_program_parameters(&system_parameters, &mut rng);
 
-    let signature_parameters = &system_parameters.account_signature;
-    let commitment_parameters = &system_parameters.account_commitment;
-    let encryption_parameters = &system_parameters.account_encryption;
-
-    // Generate metadata and an account for a dummy initial record.
-    let dummy_account = Account::new(
-        signature_parameters,
-        commitment_parameters,
-        encryption_parameters,
-        &mut rng,
-    )
-    .unwrap();
-
-    let genesis_block = Block {
-        header: BlockHeader {
-            previous_block_hash: BlockHeaderHash([0u8; 32]),
-            merkle_root_hash: MerkleRootHash([0u8; 32]),
-            time: 0,
-            difficulty_target: 0x07FF_FFFF_FFFF_FFFF_u64,
-            nonce: 0,
-            pedersen_merkle_root_hash: PedersenMerkleRootHash([0u8; 32]),
-            proof: ProofOfSuccinctWork::default(),
-        },
-        transactions: DPCTransactions::new(),
-    };
-
-    // Use genesis record, serial number, and memo to initialize the ledger.
-    let ledger = initialize_test_blockchain::<Tx, CommitmentMerkleParameters>(ledger_parameters, genesis_block);
-
-    let sn_nonce = SerialNumberNonce::hash(&system_parameters.serial_number_nonce, &[0u8; 1]).unwrap();
-    let old_record = DPC::generate_record(
+    // Use genesis record, serial number, and memo to initialize the ledger, and set the
+    // input records for our transaction to be the initial dummy records.
+    let (ledger, dummy_account, old_records) = setup_test_ledger_and_dummy_records(
         &system_parameters,
-        sn_nonce,
-        dummy_account.address,
-        true,
-        0,
-        RecordPayload::default(),
-        alternate_noop_program_id.clone(),
-        alternate_noop_program_id.clone(),
+        ledger_parameters,
+        &alternate_noop_program_id,
         &mut rng,
-    )
-    .unwrap();
+    );
 
-    // Set the input records for our transaction to be the initial dummy records.
-    let old_records = vec![old_record; NUM_INPUT_RECORDS];
     let old_account_private_keys = vec![dummy_account.private_key; NUM_INPUT_RECORDS];
 
     // Construct new records.
@@ -220,9 +498,9 @@ fn test_execute_base_dpc_constraints() {
     // Create an account for an actual new record.
 
     let new_account = Account::new(
-        signature_parameters,
-        commitment_parameters,
-        encryption_parameters,
+        &system_parameters.account_signature,
+        &system_parameters.account_commitment,
+        &system_parameters.account_encryption,
         &mut rng,
     )
     .unwrap();
@@ -318,6 +596,12 @@ fn test_execute_base_dpc_constraints() {
 
     let local_data_root = local_data_merkle_tree.root();
 
+    let old_account_compute_keys = old_account_private_keys
+        .iter()
+        .map(|private_key| AccountComputeKey::from_private_key(&system_parameters.account_signature, private_key))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
     // Construct the ledger witnesses
     let ledger_digest = ledger.digest().expect("could not get digest");
 
@@ -355,7 +639,7 @@ fn test_execute_base_dpc_constraints() {
         &ledger_digest,
         &old_records,
         &old_witnesses,
-        &old_account_private_keys,
+        &old_account_compute_keys,
         &old_serial_numbers,
         &new_records,
         &new_sn_nonce_randomness,
@@ -402,7 +686,7 @@ fn test_execute_base_dpc_constraints() {
     let inner_snark_vk: <<Components as BaseDPCComponents>::InnerSNARK as SNARK>::VerificationParameters =
         inner_snark_parameters.1.clone().into();
 
-    let inner_snark_id = InnerSNARKVerificationKeyCRH::hash(
+    let inner_snark_id = InnerSNARKVerificationKeyCRH::<NUM_INPUT_RECORDS, NUM_OUTPUT_RECORDS>::hash(
         &system_parameters.inner_snark_verification_key_crh,
         &to_bytes![inner_snark_vk].unwrap(),
     )
@@ -416,7 +700,7 @@ fn test_execute_base_dpc_constraints() {
             ledger_digest,
             old_records,
             old_witnesses,
-            old_account_private_keys,
+            old_account_compute_keys,
             old_serial_numbers.clone(),
             new_records,
             new_sn_nonce_randomness,
@@ -481,3 +765,105 @@ fn test_execute_base_dpc_constraints() {
 
     kill_storage(ledger);
 }
+
+#[test]
+fn test_transaction_builder_input_selection() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    let system_parameters = InstantiatedDPC::generate_system_parameters(&mut rng).unwrap();
+    let noop_program_id = vec![1u8; 48];
+    let other_program_id = vec![2u8; 48];
+
+    let account = Account::new(
+        &system_parameters.account_signature,
+        &system_parameters.account_commitment,
+        &system_parameters.account_encryption,
+        &mut rng,
+    )
+    .unwrap();
+
+    let mut new_record = |is_dummy: bool, value: u64, death_program_id: &[u8]| {
+        let sn_nonce_input: [u8; 32] = rng.gen();
+        DPC::generate_record(
+            &system_parameters,
+            SerialNumberNonce::<NUM_INPUT_RECORDS>::hash(&system_parameters.serial_number_nonce, &sn_nonce_input)
+                .unwrap(),
+            account.address.clone(),
+            is_dummy,
+            value,
+            RecordPayload::default(),
+            noop_program_id.clone(),
+            death_program_id.to_vec(),
+            &mut rng,
+        )
+        .unwrap()
+    };
+
+    let builder = TransactionBuilder::<Components>::new()
+        .add_input(new_record(false, 5, &noop_program_id), account.private_key.clone())
+        .add_input(new_record(false, 20, &noop_program_id), account.private_key.clone())
+        .add_input(new_record(false, 10, &noop_program_id), account.private_key.clone());
+
+    // The largest records are selected first.
+    assert_eq!(
+        builder.select_inputs(&noop_program_id),
+        Err(TransactionBuilderError::MissingRecipients)
+    );
+    let transfer = builder.clone().add_recipient(account.address.clone(), 15).fee(1);
+    assert_eq!(transfer.select_inputs(&noop_program_id), Ok((vec![1], 4)));
+    let transfer = builder.clone().add_recipient(account.address.clone(), 25).fee(5);
+    assert_eq!(transfer.select_inputs(&noop_program_id), Ok((vec![1, 2], 0)));
+
+    // At least one input is selected, even if the transaction transfers no value.
+    let transfer = builder.clone().add_recipient(account.address.clone(), 0);
+    assert_eq!(transfer.select_inputs(&noop_program_id), Ok((vec![1], 20)));
+    let transfer = TransactionBuilder::<Components>::new().add_recipient(account.address.clone(), 0);
+    assert_eq!(
+        transfer.select_inputs(&noop_program_id),
+        Err(TransactionBuilderError::MissingInputs)
+    );
+
+    // The value of the inputs must cover the recipients and the fee.
+    let transfer = builder.clone().add_recipient(account.address.clone(), 35).fee(1);
+    assert_eq!(
+        transfer.select_inputs(&noop_program_id),
+        Err(TransactionBuilderError::InsufficientFunds(35, 36))
+    );
+    let transfer = builder.clone().add_recipient(account.address.clone(), u64::MAX).fee(1);
+    assert_eq!(
+        transfer.select_inputs(&noop_program_id),
+        Err(TransactionBuilderError::ValueOverflow)
+    );
+
+    // The transaction must fit in the number of input and output records.
+    let transfer = builder.clone().add_recipient(account.address.clone(), 32);
+    assert_eq!(
+        transfer.select_inputs(&noop_program_id),
+        Err(TransactionBuilderError::TooManyInputRecords(3, NUM_INPUT_RECORDS))
+    );
+    let transfer = builder
+        .clone()
+        .add_recipient(account.address.clone(), 5)
+        .add_recipient(account.address.clone(), 5);
+    assert_eq!(
+        transfer.select_inputs(&noop_program_id),
+        Err(TransactionBuilderError::TooManyOutputRecords(3, NUM_OUTPUT_RECORDS))
+    );
+
+    // The inputs must be spendable by the noop program.
+    let transfer = builder
+        .clone()
+        .add_input(new_record(true, 0, &noop_program_id), account.private_key.clone())
+        .add_recipient(account.address.clone(), 5);
+    assert_eq!(
+        transfer.select_inputs(&noop_program_id),
+        Err(TransactionBuilderError::DummyInputRecord(3))
+    );
+    let transfer = builder
+        .add_input(new_record(false, 5, &other_program_id), account.private_key.clone())
+        .add_recipient(account.address.clone(), 5);
+    assert_eq!(
+        transfer.select_inputs(&noop_program_id),
+        Err(TransactionBuilderError::UnsupportedDeathProgram(3))
+    );
+}
@@ -0,0 +1,359 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    account::{Account, AccountAddress, AccountPrivateKey},
+    base_dpc::{
+        parameters::{PublicParameters, SystemParameters},
+        program::{NoopProgram, PrivateProgramInput},
+        record::DPCRecord,
+        record_payload::RecordPayload,
+        transaction::DPCTransaction,
+        BaseDPCComponents,
+        LocalData,
+        TransactionKernel,
+        DPC,
+    },
+    errors::TransactionBuilderError,
+    traits::{DPCScheme, Program, Record},
+};
+use snarkvm_algorithms::traits::CRH;
+use snarkvm_objects::traits::LedgerScheme;
+use snarkvm_utilities::{to_bytes, ToBytes};
+
+use rand::Rng;
+
+/// Builds a transfer transaction from spendable records.
+///
+/// The builder selects the input records that pay for the recipients and the fee,
+/// pads the inputs and outputs with dummy records, sends the remaining value back to
+/// the change address, and generates the noop program proofs of every record.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "C: BaseDPCComponents"), Default(bound = "C: BaseDPCComponents"))]
+pub struct TransactionBuilder<C: BaseDPCComponents> {
+    inputs: Vec<(DPCRecord<C>, AccountPrivateKey<C>)>,
+    recipients: Vec<(AccountAddress<C>, u64)>,
+    fee: u64,
+    change_address: Option<AccountAddress<C>>,
+    memorandum: Option<[u8; 32]>,
+    network_id: u8,
+}
+
+impl<C: BaseDPCComponents> TransactionBuilder<C> {
+    /// Creates an empty transaction builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a spendable record, and the private key of its owner.
+    pub fn add_input(mut self, record: DPCRecord<C>, private_key: AccountPrivateKey<C>) -> Self {
+        self.inputs.push((record, private_key));
+        self
+    }
+
+    /// Adds a recipient of the given amount.
+    pub fn add_recipient(mut self, address: AccountAddress<C>, amount: u64) -> Self {
+        self.recipients.push((address, amount));
+        self
+    }
+
+    /// Sets the fee paid to the miner. Defaults to zero.
+    pub fn fee(mut self, fee: u64) -> Self {
+        self.fee = fee;
+        self
+    }
+
+    /// Sets the address that receives the change. Defaults to the owner of the first selected input.
+    pub fn change_address(mut self, address: AccountAddress<C>) -> Self {
+        self.change_address = Some(address);
+        self
+    }
+
+    /// Sets the transaction memorandum. Defaults to a random memorandum.
+    pub fn memorandum(mut self, memorandum: [u8; 32]) -> Self {
+        self.memorandum = Some(memorandum);
+        self
+    }
+
+    /// Sets the network id. Defaults to zero.
+    pub fn network_id(mut self, network_id: u8) -> Self {
+        self.network_id = network_id;
+        self
+    }
+
+    /// Returns the indexes of the inputs that pay for the recipients and the fee, and the change.
+    ///
+    /// The largest records are selected first, which minimizes the number of input records.
+    /// At least one record is selected, even if the transaction transfers no value.
+    pub fn select_inputs(&self, noop_program_id: &[u8]) -> Result<(Vec<usize>, u64), TransactionBuilderError> {
+        if self.recipients.is_empty() {
+            return Err(TransactionBuilderError::MissingRecipients);
+        }
+
+        // The dummy records are owned by the owner of the first selected input, so there must be one.
+        if self.inputs.is_empty() {
+            return Err(TransactionBuilderError::MissingInputs);
+        }
+
+        for (index, (record, _)) in self.inputs.iter().enumerate() {
+            if record.is_dummy() {
+                return Err(TransactionBuilderError::DummyInputRecord(index));
+            }
+            if record.death_program_id() != noop_program_id {
+                return Err(TransactionBuilderError::UnsupportedDeathProgram(index));
+            }
+        }
+
+        let required = self
+            .recipients
+            .iter()
+            .try_fold(self.fee, |total, (_, amount)| total.checked_add(*amount))
+            .ok_or(TransactionBuilderError::ValueOverflow)?;
+
+        let mut candidates = (0..self.inputs.len()).collect::<Vec<_>>();
+        candidates.sort_by_key(|index| std::cmp::Reverse(self.inputs[*index].0.value()));
+
+        let mut selected = vec![];
+        let mut selected_value = 0u64;
+        for index in candidates {
+            if selected_value >= required && !selected.is_empty() {
+                break;
+            }
+            selected.push(index);
+            selected_value = selected_value
+                .checked_add(self.inputs[index].0.value())
+                .ok_or(TransactionBuilderError::ValueOverflow)?;
+        }
+
+        if selected_value < required {
+            return Err(TransactionBuilderError::InsufficientFunds(selected_value, required));
+        }
+
+        if selected.len() > C::NUM_INPUT_RECORDS {
+            return Err(TransactionBuilderError::TooManyInputRecords(
+                selected.len(),
+                C::NUM_INPUT_RECORDS,
+            ));
+        }
+
+        let change = selected_value - required;
+
+        let num_outputs = self.recipients.len() + if change > 0 { 1 } else { 0 };
+        if num_outputs > C::NUM_OUTPUT_RECORDS {
+            return Err(TransactionBuilderError::TooManyOutputRecords(
+                num_outputs,
+                C::NUM_OUTPUT_RECORDS,
+            ));
+        }
+
+        Ok((selected, change))
+    }
+
+    /// Builds the transaction, and returns the new records and the transaction.
+    pub fn build<L: LedgerScheme, R: Rng>(
+        self,
+        parameters: &PublicParameters<C>,
+        ledger: &L,
+        rng: &mut R,
+    ) -> anyhow::Result<(Vec<DPCRecord<C>>, DPCTransaction<C>)>
+    where
+        DPC<C>: DPCScheme<
+            L,
+            Account = Account<C>,
+            LocalData = LocalData<C>,
+            NetworkParameters = PublicParameters<C>,
+            Payload = RecordPayload,
+            PrivateProgramInput = PrivateProgramInput,
+            Record = DPCRecord<C>,
+            SystemParameters = SystemParameters<C>,
+            Transaction = DPCTransaction<C>,
+            TransactionKernel = TransactionKernel<C>,
+        >,
+    {
+        let system_parameters = &parameters.system_parameters;
+        let noop_program_snark_parameters = parameters.noop_program_snark_parameters();
+
+        let noop_program_id = to_bytes![C::ProgramVerificationKeyCRH::hash(
+            &system_parameters.program_verification_key_crh,
+            &to_bytes![noop_program_snark_parameters.verification_key]?
+        )?]?;
+
+        let (selected, change) = self.select_inputs(&noop_program_id)?;
+
+        // The dummy records are owned by the owner of the first selected input.
+        let (dummy_owner, dummy_private_key) = {
+            let (record, private_key) = &self.inputs[selected[0]];
+            (record.owner().clone(), private_key.clone())
+        };
+        let change_address = self.change_address.clone().unwrap_or_else(|| dummy_owner.clone());
+
+        // Construct the input records, padded with dummy records.
+        let mut old_records = Vec::with_capacity(C::NUM_INPUT_RECORDS);
+        let mut old_account_private_keys = Vec::with_capacity(C::NUM_INPUT_RECORDS);
+        for index in &selected {
+            let (record, private_key) = &self.inputs[*index];
+            old_records.push(record.clone());
+            old_account_private_keys.push(private_key.clone());
+        }
+        while old_records.len() < C::NUM_INPUT_RECORDS {
+            old_records.push(generate_dummy_record(
+                system_parameters,
+                dummy_owner.clone(),
+                &noop_program_id,
+                rng,
+            )?);
+            old_account_private_keys.push(dummy_private_key.clone());
+        }
+
+        // Construct the output records: the recipients, the change, and dummy records.
+        let mut new_record_owners = Vec::with_capacity(C::NUM_OUTPUT_RECORDS);
+        let mut new_is_dummy_flags = Vec::with_capacity(C::NUM_OUTPUT_RECORDS);
+        let mut new_values = Vec::with_capacity(C::NUM_OUTPUT_RECORDS);
+        for (address, amount) in &self.recipients {
+            new_record_owners.push(address.clone());
+            new_is_dummy_flags.push(false);
+            new_values.push(*amount);
+        }
+        if change > 0 {
+            new_record_owners.push(change_address.clone());
+            new_is_dummy_flags.push(false);
+            new_values.push(change);
+        }
+        while new_record_owners.len() < C::NUM_OUTPUT_RECORDS {
+            new_record_owners.push(change_address.clone());
+            new_is_dummy_flags.push(true);
+            new_values.push(0);
+        }
+
+        let memorandum = match self.memorandum {
+            Some(memorandum) => memorandum,
+            None => rng.gen(),
+        };
+
+        execute_with_noop_programs(
+            parameters,
+            &noop_program_id,
+            old_records,
+            old_account_private_keys,
+            new_record_owners,
+            &new_is_dummy_flags,
+            &new_values,
+            memorandum,
+            self.network_id,
+            ledger,
+            rng,
+        )
+    }
+}
+
+/// Returns a dummy record owned by the given address, spendable by the noop program.
+pub(crate) fn generate_dummy_record<C: BaseDPCComponents, R: Rng>(
+    system_parameters: &SystemParameters<C>,
+    owner: AccountAddress<C>,
+    noop_program_id: &[u8],
+    rng: &mut R,
+) -> anyhow::Result<DPCRecord<C>> {
+    let sn_nonce_input: [u8; 32] = rng.gen();
+    let sn_nonce = C::SerialNumberNonceCRH::hash(&system_parameters.serial_number_nonce, &sn_nonce_input)?;
+
+    Ok(DPC::<C>::generate_record(
+        system_parameters,
+        sn_nonce,
+        owner,
+        true,
+        0,
+        RecordPayload::default(),
+        noop_program_id.to_vec(),
+        noop_program_id.to_vec(),
+        rng,
+    )?)
+}
+
+/// Executes a transaction whose records are all born and killed by the noop program,
+/// and returns the new records and the transaction.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn execute_with_noop_programs<C: BaseDPCComponents, L: LedgerScheme, R: Rng>(
+    parameters: &PublicParameters<C>,
+    noop_program_id: &[u8],
+    old_records: Vec<DPCRecord<C>>,
+    old_account_private_keys: Vec<AccountPrivateKey<C>>,
+    new_record_owners: Vec<AccountAddress<C>>,
+    new_is_dummy_flags: &[bool],
+    new_values: &[u64],
+    memorandum: [u8; 32],
+    network_id: u8,
+    ledger: &L,
+    rng: &mut R,
+) -> anyhow::Result<(Vec<DPCRecord<C>>, DPCTransaction<C>)>
+where
+    DPC<C>: DPCScheme<
+        L,
+        Account = Account<C>,
+        LocalData = LocalData<C>,
+        NetworkParameters = PublicParameters<C>,
+        Payload = RecordPayload,
+        PrivateProgramInput = PrivateProgramInput,
+        Record = DPCRecord<C>,
+        SystemParameters = SystemParameters<C>,
+        Transaction = DPCTransaction<C>,
+        TransactionKernel = TransactionKernel<C>,
+    >,
+{
+    let system_parameters = &parameters.system_parameters;
+    let noop_program_snark_parameters = parameters.noop_program_snark_parameters();
+    let noop_program_id = noop_program_id.to_vec();
+
+    let transaction_kernel = <DPC<C> as DPCScheme<L>>::execute_offline(
+        system_parameters.clone(),
+        old_records,
+        old_account_private_keys,
+        new_record_owners,
+        new_is_dummy_flags,
+        new_values,
+        vec![RecordPayload::default(); C::NUM_OUTPUT_RECORDS],
+        vec![noop_program_id.clone(); C::NUM_OUTPUT_RECORDS],
+        vec![noop_program_id.clone(); C::NUM_OUTPUT_RECORDS],
+        memorandum,
+        network_id,
+        rng,
+    )?;
+
+    // Generate the noop program proofs for every input and output record.
+    let local_data = transaction_kernel.into_local_data();
+    let noop_program = NoopProgram::<C, C::NoopProgramSNARK>::new(noop_program_id);
+
+    let mut program_proofs = Vec::with_capacity(C::NUM_INPUT_RECORDS + C::NUM_OUTPUT_RECORDS);
+    for position in 0..(C::NUM_INPUT_RECORDS + C::NUM_OUTPUT_RECORDS) {
+        program_proofs.push(noop_program.execute(
+            &noop_program_snark_parameters.proving_key,
+            &noop_program_snark_parameters.verification_key,
+            &local_data,
+            position as u8,
+            rng,
+        )?);
+    }
+    let new_birth_program_proofs = program_proofs.split_off(C::NUM_INPUT_RECORDS);
+    let old_death_program_proofs = program_proofs;
+
+    <DPC<C> as DPCScheme<L>>::execute_online(
+        parameters,
+        transaction_kernel,
+        old_death_program_proofs,
+        new_birth_program_proofs,
+        ledger,
+        rng,
+    )
+}
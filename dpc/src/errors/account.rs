@@ -39,6 +39,36 @@ pub enum AccountError {
     #[error("invalid character length: {}", _0)]
     InvalidCharacterLength(usize),
 
+    #[error("invalid derivation path: {}", _0)]
+    InvalidDerivationPath(String),
+
+    #[error("invalid keystore: {}", _0)]
+    InvalidKeystore(String),
+
+    #[error("incorrect keystore password or corrupted keystore")]
+    InvalidKeystorePassword,
+
+    #[error("invalid keystore salt length: {}", _0)]
+    InvalidKeystoreSaltLength(usize),
+
+    #[error("keystore scrypt log_n {} exceeds the maximum of {}", _0, _1)]
+    InvalidKeystoreScryptLogN(u8, u8),
+
+    #[error("keystore scrypt p {} exceeds the maximum of {}", _0, _1)]
+    InvalidKeystoreScryptP(u32, u32),
+
+    #[error("keystore scrypt r {} exceeds the maximum of {}", _0, _1)]
+    InvalidKeystoreScryptR(u32, u32),
+
+    #[error("invalid mnemonic checksum")]
+    InvalidMnemonicChecksum,
+
+    #[error("unknown mnemonic word: {}", _0)]
+    InvalidMnemonicWord(String),
+
+    #[error("invalid mnemonic word count: {}", _0)]
+    InvalidMnemonicWordCount(usize),
+
     #[error("invalid prefix: {:?}", _0)]
     InvalidPrefix(String),
 
@@ -51,11 +81,17 @@ pub enum AccountError {
     #[error("{}", _0)]
     Message(String),
 
+    #[error("non-hardened derivation index {} is not supported", _0)]
+    NonHardenedDerivationIndex(u32),
+
     #[error("{}", _0)]
     PRFError(PRFError),
 
     #[error("{}", _0)]
     SignatureError(SignatureError),
+
+    #[error("unsupported keystore version: {}", _0)]
+    UnsupportedKeystoreVersion(u32),
 }
 
 impl From<CommitmentError> for AccountError {
@@ -100,6 +136,18 @@ impl From<bech32::Error> for AccountError {
     }
 }
 
+impl From<hex::FromHexError> for AccountError {
+    fn from(error: hex::FromHexError) -> Self {
+        AccountError::Crate("hex", format!("{:?}", error))
+    }
+}
+
+impl From<serde_json::Error> for AccountError {
+    fn from(error: serde_json::Error) -> Self {
+        AccountError::Crate("serde_json", format!("{:?}", error))
+    }
+}
+
 impl From<std::io::Error> for AccountError {
     fn from(error: std::io::Error) -> Self {
         AccountError::Crate("std::io", format!("{:?}", error))
@@ -0,0 +1,56 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+/// The reason a transaction builder can't build a transaction.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum TransactionBuilderError {
+    #[error("input record {} is a dummy record", _0)]
+    DummyInputRecord(usize),
+
+    #[error("insufficient funds: {} available, {} required", _0, _1)]
+    InsufficientFunds(u64, u64),
+
+    #[error("the transaction has no input records")]
+    MissingInputs,
+
+    #[error("the transaction has no recipients")]
+    MissingRecipients,
+
+    #[error("the transaction needs {} input records, but at most {} are allowed", _0, _1)]
+    TooManyInputRecords(usize, usize),
+
+    #[error("the transaction needs {} output records, but at most {} are allowed", _0, _1)]
+    TooManyOutputRecords(usize, usize),
+
+    #[error("input record {} can't be spent by the noop program", _0)]
+    UnsupportedDeathProgram(usize),
+
+    #[error("the transaction values overflow")]
+    ValueOverflow,
+}
+
+/// The reason a genesis builder can't build a genesis block.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum GenesisBuilderError {
+    #[error("the genesis block has no allocations")]
+    MissingAllocations,
+
+    #[error("allocation {} has a zero amount", _0)]
+    ZeroAllocation(usize),
+
+    #[error("the genesis allocations overflow")]
+    ValueOverflow,
+}
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::errors::{AccountError, LedgerError};
+use crate::errors::{AccountError, LedgerError, ProgramError};
 use snarkvm_algorithms::errors::{
     CRHError,
     CommitmentError,
@@ -25,6 +25,7 @@ use snarkvm_algorithms::errors::{
     SNARKError,
     SignatureError,
 };
+use snarkvm_objects::errors::TransactionError;
 use snarkvm_parameters::errors::ParameterError;
 
 #[derive(Debug, Error)]
@@ -47,6 +48,18 @@ pub enum DPCError {
     #[error("{}", _0)]
     EncryptionError(EncryptionError),
 
+    #[error("the inner snark proof failed to verify")]
+    InvalidInnerProof,
+
+    #[error("the program proof for the record at position {} failed to verify", _0)]
+    InvalidProgramProof(u8),
+
+    #[error("the transaction kernel signature of input record {} is invalid", _0)]
+    InvalidKernelSignature(usize),
+
+    #[error("the transaction kernel was signed against an unknown ledger digest")]
+    InvalidLedgerDigest,
+
     #[error("{}", _0)]
     LedgerError(LedgerError),
 
@@ -68,11 +81,17 @@ pub enum DPCError {
     #[error("{}", _0)]
     PRFError(PRFError),
 
+    #[error("{}", _0)]
+    ProgramError(ProgramError),
+
     #[error("{}", _0)]
     SignatureError(SignatureError),
 
     #[error("{}", _0)]
     SNARKError(SNARKError),
+
+    #[error("{}", _0)]
+    TransactionError(TransactionError),
 }
 
 impl From<AccountError> for DPCError {
@@ -123,6 +142,12 @@ impl From<PRFError> for DPCError {
     }
 }
 
+impl From<ProgramError> for DPCError {
+    fn from(error: ProgramError) -> Self {
+        DPCError::ProgramError(error)
+    }
+}
+
 impl From<SignatureError> for DPCError {
     fn from(error: SignatureError) -> Self {
         DPCError::SignatureError(error)
@@ -146,3 +171,9 @@ impl From<std::io::Error> for DPCError {
         DPCError::Crate("std::io", format!("{:?}", error))
     }
 }
+
+impl From<TransactionError> for DPCError {
+    fn from(error: TransactionError) -> Self {
+        DPCError::TransactionError(error)
+    }
+}
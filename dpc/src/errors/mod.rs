@@ -17,8 +17,17 @@
 pub mod account;
 pub use account::*;
 
+pub mod builder;
+pub use builder::*;
+
 pub mod dpc;
 pub use dpc::*;
 
 pub mod ledger;
 pub use ledger::*;
+
+pub mod program;
+pub use program::*;
+
+pub mod verification;
+pub use verification::*;
@@ -0,0 +1,28 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+/// The reason a program can't prove the record at a position of a transaction.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ProgramError {
+    #[error("the program is not the birth program of the record at position {}", _0)]
+    MismatchedBirthProgram(u8),
+
+    #[error("the program is not the death program of the record at position {}", _0)]
+    MismatchedDeathProgram(u8),
+
+    #[error("there is no record at position {}", _0)]
+    PositionOutOfBounds(u8),
+}
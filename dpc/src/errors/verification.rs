@@ -0,0 +1,61 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+/// The reason a transaction is invalid according to the ledger.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum TransactionVerificationError {
+    #[error("commitment {} is already created by an earlier transaction in the block", _0)]
+    ConflictingCm(usize),
+
+    #[error("the transaction memo is already used by an earlier transaction in the block")]
+    ConflictingMemo,
+
+    #[error("serial number {} is already spent by an earlier transaction in the block", _0)]
+    ConflictingSn(usize),
+
+    #[error("the transaction contains duplicate commitments")]
+    DuplicateCm,
+
+    #[error("the transaction contains duplicate serial numbers")]
+    DuplicateSn,
+
+    #[error("the transaction has {} encrypted records for {} new records", _0, _1)]
+    EncryptedRecordCountMismatch(usize, usize),
+
+    #[error("the ledger already contains commitment {}", _0)]
+    ExistingCm(usize),
+
+    #[error("the ledger already contains the transaction memo")]
+    ExistingMemo,
+
+    #[error("the ledger already contains serial number {}", _0)]
+    ExistingSn(usize),
+
+    #[error("transaction {} is invalid: {}", _0, _1)]
+    InvalidBlockTransaction(usize, Box<TransactionVerificationError>),
+
+    #[error("the transaction ledger digest is not a valid ledger digest")]
+    InvalidLedgerDigest,
+
+    #[error("signature {} failed to verify", _0)]
+    InvalidSignature(usize),
+
+    #[error("expected network id {}, found network id {}", _0, _1)]
+    NetworkMismatch(u8, u8),
+
+    #[error("the transaction proof failed to verify")]
+    OuterProofRejected,
+}
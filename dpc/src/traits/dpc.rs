@@ -14,7 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::traits::{AccountScheme, Record};
+use crate::{
+    errors::TransactionVerificationError,
+    traits::{AccountScheme, Record},
+};
 use snarkvm_objects::traits::{LedgerScheme, Transaction};
 
 use rand::Rng;
@@ -64,17 +67,37 @@ pub trait DPCScheme<L: LedgerScheme> {
         rng: &mut R,
     ) -> anyhow::Result<(Vec<Self::Record>, Self::Transaction)>;
 
+    /// Returns `Ok(())` iff the transaction is valid according to the ledger,
+    /// and otherwise the reason the transaction is invalid.
+    fn verify_detailed(
+        parameters: &Self::NetworkParameters,
+        transaction: &Self::Transaction,
+        ledger: &L,
+    ) -> anyhow::Result<Result<(), TransactionVerificationError>>;
+
+    /// Returns `Ok(())` iff all the transactions in the block are valid according to the ledger,
+    /// and otherwise the reason the first invalid transaction is invalid.
+    fn verify_transactions_detailed(
+        parameters: &Self::NetworkParameters,
+        block: &[Self::Transaction],
+        ledger: &L,
+    ) -> anyhow::Result<Result<(), TransactionVerificationError>>;
+
     /// Returns true iff the transaction is valid according to the ledger.
     fn verify(
         parameters: &Self::NetworkParameters,
         transaction: &Self::Transaction,
         ledger: &L,
-    ) -> anyhow::Result<bool>;
+    ) -> anyhow::Result<bool> {
+        Ok(Self::verify_detailed(parameters, transaction, ledger)?.is_ok())
+    }
 
     /// Returns true iff all the transactions in the block are valid according to the ledger.
     fn verify_transactions(
         parameters: &Self::NetworkParameters,
         block: &[Self::Transaction],
         ledger: &L,
-    ) -> anyhow::Result<bool>;
+    ) -> anyhow::Result<bool> {
+        Ok(Self::verify_transactions_detailed(parameters, block, ledger)?.is_ok())
+    }
 }
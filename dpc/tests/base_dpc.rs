@@ -23,21 +23,27 @@ use snarkvm_dpc::{
         record::record_encryption::RecordEncryption,
         record_payload::RecordPayload,
         BaseDPCComponents,
+        SignedTransactionKernel,
+        TransactionBuilder,
         DPC,
     },
-    traits::{DPCScheme, Program},
+    errors::{DPCError, TransactionVerificationError},
+    traits::{DPCScheme, Program, Record},
 };
 use snarkvm_objects::{
     dpc::DPCTransactions,
     merkle_root,
-    traits::{LedgerScheme, Transaction},
+    traits::{LedgerScheme, Storage, Transaction},
+    AleoAmount,
     Block,
     BlockHeader,
     BlockHeaderHash,
     MerkleRootHash,
+    Network,
     PedersenMerkleRootHash,
     ProofOfSuccinctWork,
 };
+use snarkvm_storage::{COL_META, KEY_NETWORK_ID};
 use snarkvm_testing::{dpc::*, storage::*};
 use snarkvm_utilities::{
     bytes::{FromBytes, ToBytes},
@@ -48,7 +54,7 @@ use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-type L = Ledger<Tx, CommitmentMerkleParameters>;
+type L = Ledger<Tx, CommitmentMerkleParameters, MemDb>;
 
 #[test]
 fn base_dpc_integration_test() {
@@ -58,7 +64,7 @@ fn base_dpc_integration_test() {
     let (ledger_parameters, parameters) = setup_or_load_parameters(false, &mut rng);
 
     // Generate accounts
-    let [genesis_account, recipient, _] = generate_test_accounts(&parameters, &mut rng);
+    let [genesis_account, recipient, account_2] = generate_test_accounts(&parameters, &mut rng);
 
     // Specify network_id
     let network_id: u8 = 0;
@@ -80,6 +86,13 @@ fn base_dpc_integration_test() {
 
     let ledger = initialize_test_blockchain::<Tx, CommitmentMerkleParameters>(ledger_parameters, genesis_block);
 
+    // The genesis block has no transactions to give the network of the ledger, so it is stored directly.
+    ledger
+        .storage
+        .put(COL_META, KEY_NETWORK_ID.as_bytes(), [network_id])
+        .unwrap();
+    assert_eq!(ledger.network_id(), Some(network_id));
+
     let noop_program_id = to_bytes![
         ProgramVerificationKeyCRH::hash(
             &parameters.system_parameters.program_verification_key_crh,
@@ -93,7 +106,7 @@ fn base_dpc_integration_test() {
     let old_account_private_keys = vec![genesis_account.private_key.clone(); NUM_INPUT_RECORDS];
     let mut old_records = vec![];
     for i in 0..NUM_INPUT_RECORDS {
-        let old_sn_nonce = SerialNumberNonce::hash(
+        let old_sn_nonce = SerialNumberNonce::<NUM_INPUT_RECORDS>::hash(
             &parameters.system_parameters.serial_number_nonce,
             &[64u8 + (i as u8); 1],
         )
@@ -178,9 +191,79 @@ fn base_dpc_integration_test() {
         new_birth_program_proofs.push(private_input);
     }
 
-    let (new_records, transaction) = InstantiatedDPC::execute_online(
+    // Sign the transaction kernel, and hand it to the prover as bytes
+    let signed_transaction_kernel = transaction_kernel.sign(ledger.digest().unwrap(), &mut rng).unwrap();
+    let signed_transaction_kernel_bytes = to_bytes![signed_transaction_kernel].unwrap();
+
+    {
+        // Check that the signed transaction kernel does not contain the account private key
+
+        let contains = |bytes: &[u8]| {
+            signed_transaction_kernel_bytes
+                .windows(bytes.len())
+                .any(|window| window == bytes)
+        };
+
+        assert!(!contains(&genesis_account.private_key.seed));
+        assert!(!contains(&to_bytes![genesis_account.private_key.sk_sig].unwrap()));
+    }
+
+    {
+        // Check that the prover cannot alter the signed transaction contents
+
+        let execute_tampered = |tamper: &dyn Fn(&mut Vec<u8>)| {
+            let mut bytes = signed_transaction_kernel_bytes.clone();
+            tamper(&mut bytes);
+
+            let kernel = SignedTransactionKernel::<Components>::read(&bytes[..]).unwrap();
+            let error = InstantiatedDPC::execute_online_signed(
+                &parameters,
+                kernel,
+                old_death_program_proofs.clone(),
+                new_birth_program_proofs.clone(),
+                &ledger,
+                &mut rng.clone(),
+            )
+            .unwrap_err();
+
+            error.downcast::<DPCError>().unwrap()
+        };
+
+        // The kernel ends with the value balance, memorandum and network id.
+        let memo_start = signed_transaction_kernel_bytes.len() - 33;
+        let value_balance_start = memo_start - 8;
+
+        let error = execute_tampered(&|bytes| bytes[memo_start..memo_start + 32].copy_from_slice(&[5u8; 32]));
+        assert!(matches!(error, DPCError::InvalidKernelSignature(0)));
+
+        let error = execute_tampered(&|bytes| bytes[value_balance_start] ^= 1);
+        assert!(matches!(error, DPCError::InvalidKernelSignature(0)));
+
+        // Raising the value of a new record is rejected by the inner SNARK.
+        let recipient_bytes = to_bytes![recipient.address].unwrap();
+        let error = execute_tampered(&|bytes| {
+            let new_record_start = bytes
+                .windows(recipient_bytes.len())
+                .position(|window| window == &recipient_bytes[..])
+                .unwrap();
+            // The record value follows the owner and the dummy flag.
+            bytes[new_record_start + recipient_bytes.len() + 1] ^= 1;
+        });
+        assert!(matches!(error, DPCError::InvalidInnerProof));
+
+        // A kernel signed against a digest that was never a ledger digest is rejected.
+        let unknown_digest_kernel = transaction_kernel.sign(Default::default(), &mut rng.clone()).unwrap();
+        let error = execute_tampered(&|bytes| *bytes = to_bytes![unknown_digest_kernel].unwrap());
+        assert!(matches!(error, DPCError::InvalidLedgerDigest));
+    }
+
+    let recovered_signed_transaction_kernel =
+        SignedTransactionKernel::<Components>::read(&signed_transaction_kernel_bytes[..]).unwrap();
+    assert_eq!(signed_transaction_kernel, recovered_signed_transaction_kernel);
+
+    let (new_records, transaction) = InstantiatedDPC::execute_online_signed(
         &parameters,
-        transaction_kernel,
+        recovered_signed_transaction_kernel,
         old_death_program_proofs,
         new_birth_program_proofs,
         &ledger,
@@ -198,10 +281,12 @@ fn base_dpc_integration_test() {
         // Check that new_records can be decrypted from the transaction
 
         let encrypted_records = transaction.encrypted_records();
-        let new_account_private_keys = vec![recipient.private_key; NUM_OUTPUT_RECORDS];
+        let new_account_private_keys = vec![recipient.private_key.clone(); NUM_OUTPUT_RECORDS];
 
-        for ((encrypted_record, private_key), new_record) in
-            encrypted_records.iter().zip(new_account_private_keys).zip(new_records)
+        for ((encrypted_record, private_key), new_record) in encrypted_records
+            .iter()
+            .zip(new_account_private_keys)
+            .zip(new_records.clone())
         {
             let account_view_key = AccountViewKey::from_private_key(
                 &parameters.system_parameters.account_signature,
@@ -247,10 +332,114 @@ fn base_dpc_integration_test() {
 
     assert!(InstantiatedDPC::verify_transactions(&parameters, &transactions.0, &ledger).unwrap());
 
+    // Check that the reason an invalid transaction is rejected is reported
+
+    let verify_detailed =
+        |transaction: &Tx| InstantiatedDPC::verify_detailed(&parameters, transaction, &ledger).unwrap();
+
+    assert_eq!(verify_detailed(&transactions.0[0]), Ok(()));
+
+    let mut invalid_transaction = transactions.0[0].clone();
+    invalid_transaction.old_serial_numbers[1] = invalid_transaction.old_serial_numbers[0];
+    assert_eq!(
+        verify_detailed(&invalid_transaction),
+        Err(TransactionVerificationError::DuplicateSn)
+    );
+
+    let mut invalid_transaction = transactions.0[0].clone();
+    invalid_transaction.memorandum = [5u8; 32];
+    assert_eq!(
+        verify_detailed(&invalid_transaction),
+        Err(TransactionVerificationError::InvalidSignature(0))
+    );
+
+    let mut invalid_transaction = transactions.0[0].clone();
+    invalid_transaction.encrypted_records.swap(0, 1);
+    assert_eq!(
+        verify_detailed(&invalid_transaction),
+        Err(TransactionVerificationError::OuterProofRejected)
+    );
+
+    let mut invalid_transaction = transactions.0[0].clone();
+    invalid_transaction.network = Network::Custom(7);
+    assert_eq!(
+        verify_detailed(&invalid_transaction),
+        Err(TransactionVerificationError::NetworkMismatch(network_id, 7))
+    );
+    assert_eq!(
+        InstantiatedDPC::verify_transactions_detailed(
+            &parameters,
+            &[transactions.0[0].clone(), invalid_transaction],
+            &ledger
+        )
+        .unwrap(),
+        Err(TransactionVerificationError::InvalidBlockTransaction(
+            1,
+            Box::new(TransactionVerificationError::NetworkMismatch(network_id, 7))
+        ))
+    );
+
+    // Check that transactions in the same block can't conflict with each other
+
+    assert_eq!(
+        InstantiatedDPC::verify_transactions_detailed(
+            &parameters,
+            &[transactions.0[0].clone(), transactions.0[0].clone()],
+            &ledger
+        )
+        .unwrap(),
+        Err(TransactionVerificationError::InvalidBlockTransaction(
+            1,
+            Box::new(TransactionVerificationError::ConflictingSn(0))
+        ))
+    );
+
+    // Check that the batch verification reports the transaction with the invalid proof
+
+    let mut invalid_transaction = transactions.0[0].clone();
+    invalid_transaction.encrypted_records.swap(0, 1);
+    assert_eq!(
+        InstantiatedDPC::verify_transactions_detailed(&parameters, &[invalid_transaction], &ledger).unwrap(),
+        Err(TransactionVerificationError::InvalidBlockTransaction(
+            0,
+            Box::new(TransactionVerificationError::OuterProofRejected)
+        ))
+    );
+
     let block = Block { header, transactions };
 
     ledger.insert_and_commit(&block).unwrap();
     assert_eq!(ledger.len(), 2);
 
+    // Check that a committed transaction can't be verified again
+
+    assert_eq!(
+        InstantiatedDPC::verify_detailed(&parameters, &block.transactions.0[0], &ledger).unwrap(),
+        Err(TransactionVerificationError::ExistingMemo)
+    );
+
+    // Check that the transaction builder spends the new records
+
+    let (builder_records, builder_transaction) = TransactionBuilder::<Components>::new()
+        .add_input(new_records[0].clone(), recipient.private_key.clone())
+        .add_input(new_records[1].clone(), recipient.private_key.clone())
+        .add_recipient(account_2.address.clone(), 15)
+        .fee(1)
+        .network_id(network_id)
+        .build(&parameters, &ledger, &mut rng)
+        .unwrap();
+
+    assert_eq!(
+        InstantiatedDPC::verify_detailed(&parameters, &builder_transaction, &ledger).unwrap(),
+        Ok(())
+    );
+    assert_eq!(builder_transaction.value_balance(), AleoAmount(1));
+
+    assert_eq!(builder_records.len(), NUM_OUTPUT_RECORDS);
+    assert_eq!(builder_records[0].owner(), &account_2.address);
+    assert_eq!(builder_records[0].value(), 15);
+    assert_eq!(builder_records[1].owner(), &recipient.address);
+    assert_eq!(builder_records[1].value(), 4);
+
     kill_storage(ledger);
 }
@@ -0,0 +1,113 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkvm_dpc::{
+    base_dpc::{instantiated::*, GenesisBuilder, TransactionBuilder},
+    errors::GenesisBuilderError,
+    traits::{DPCScheme, Record},
+};
+use snarkvm_objects::{dpc::DPCTransactions, traits::Transaction, AleoAmount, Block, BlockValidator};
+use snarkvm_testing::{dpc::*, storage::*};
+
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+#[test]
+fn genesis_builder_integration_test() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    let (ledger_parameters, parameters) = setup_or_load_parameters(false, &mut rng);
+    let [account_0, account_1, account_2] = generate_test_accounts(&parameters, &mut rng);
+
+    let network_id = 7;
+    let time = 1_600_000_000;
+
+    // A genesis block needs at least one non-zero allocation.
+    assert_eq!(
+        GenesisBuilder::<Components>::new().check_allocations(),
+        Err(GenesisBuilderError::MissingAllocations)
+    );
+    assert_eq!(
+        GenesisBuilder::<Components>::new()
+            .add_allocation(account_0.address.clone(), 0)
+            .check_allocations(),
+        Err(GenesisBuilderError::ZeroAllocation(0))
+    );
+
+    let (records, genesis_block) = GenesisBuilder::<Components>::new()
+        .network_id(network_id)
+        .time(time)
+        .add_allocation(account_0.address.clone(), 100)
+        .add_allocation(account_1.address.clone(), 50)
+        .build::<MerkleTreeLedger, _>(&parameters, &ledger_parameters, &mut rng)
+        .unwrap();
+
+    // Check the allocated records and the coinbase transaction.
+    assert_eq!(records.len(), 2);
+    assert_eq!((records[0].owner(), records[0].value()), (&account_0.address, 100));
+    assert_eq!((records[1].owner(), records[1].value()), (&account_1.address, 50));
+
+    assert_eq!(genesis_block.header.time, time);
+    assert_eq!(genesis_block.transactions.len(), 1);
+    let coinbase = &genesis_block.transactions[0];
+    assert_eq!(coinbase.value_balance(), AleoAmount(-150));
+    assert_eq!(coinbase.network_id(), network_id);
+
+    // The coinbase transaction is proven against an empty ledger.
+    let empty_ledger = initialize_test_blockchain::<Tx, CommitmentMerkleParameters>(ledger_parameters.clone(), Block {
+        header: genesis_block.header.clone(),
+        transactions: DPCTransactions::new(),
+    });
+    assert!(InstantiatedDPC::verify_transactions(&parameters, &genesis_block.transactions.0, &empty_ledger).unwrap());
+    kill_storage(empty_ledger);
+
+    // The header commits to the coinbase transaction.
+    let validator = BlockValidator::default();
+    let transaction_ids = genesis_block.transactions.to_transaction_ids().unwrap();
+    validator
+        .check_merkle_root(&genesis_block.header, &transaction_ids)
+        .unwrap();
+    validator
+        .check_pedersen_merkle_root(&genesis_block.header, &transaction_ids)
+        .unwrap();
+
+    // Open a ledger from the genesis block, and spend an allocated record.
+    let ledger = MerkleTreeLedger::open_in_memory_with_genesis(genesis_block.clone()).unwrap();
+    assert_eq!(ledger.get_latest_block().unwrap(), genesis_block);
+
+    let (new_records, transaction) = TransactionBuilder::<Components>::new()
+        .add_input(records[0].clone(), account_0.private_key.clone())
+        .add_recipient(account_2.address.clone(), 60)
+        .fee(1)
+        .network_id(network_id)
+        .build(&parameters, &ledger, &mut rng)
+        .unwrap();
+
+    assert_eq!(
+        InstantiatedDPC::verify_detailed(&parameters, &transaction, &ledger).unwrap(),
+        Ok(())
+    );
+    assert_eq!(
+        (new_records[0].owner(), new_records[0].value()),
+        (&account_2.address, 60)
+    );
+    assert_eq!(
+        (new_records[1].owner(), new_records[1].value()),
+        (&account_0.address, 39)
+    );
+
+    kill_storage(ledger);
+}
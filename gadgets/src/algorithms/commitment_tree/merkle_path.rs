@@ -39,7 +39,7 @@ pub struct CommitmentMerklePathGadget<
     HG: CRHGadget<H, F>,
     F: Field,
 > {
-    inner_hashes: (HG::OutputGadget, HG::OutputGadget),
+    inner_hashes: Vec<(HG::OutputGadget, HG::OutputGadget)>,
     leaves: (CG::OutputGadget, CG::OutputGadget),
 }
 
@@ -69,44 +69,50 @@ impl<C: CommitmentScheme, H: CRH, CG: CommitmentGadget<C, F>, HG: CRHGadget<H, F
         let right_leaf = &self.leaves.1;
 
         let leaf_is_left = AllocatedBit::alloc(&mut cs.ns(|| "leaf_is_left"), || Ok(leaf == left_leaf))?.into();
+        CG::OutputGadget::conditional_enforce_equal_or(
+            &mut cs.ns(|| "check_leaf_is_left"),
+            &leaf_is_left,
+            leaf,
+            left_leaf,
+            right_leaf,
+            should_enforce,
+        )?;
 
-        let should_enforce_left = Boolean::and(cs.ns(|| "should_enforce_left"), should_enforce, &leaf_is_left)?;
-        leaf.conditional_enforce_equal(&mut cs.ns(|| "check_leaf_is_left"), &leaf, &should_enforce_left)?;
-
-        let should_enforce_right = Boolean::and(cs.ns(|| "should_enforce_right"), should_enforce, &leaf_is_left.not())?;
-        leaf.conditional_enforce_equal(&mut cs.ns(|| "check_leaf_is_right"), &leaf, &should_enforce_right)?;
-
-        // Check that the inner hash is valid
+        // Check that each inner hash is valid
         let left_leaf_bytes = left_leaf.to_bytes(&mut cs.ns(|| "left_leaf_to_bytes"))?;
         let right_leaf_bytes = right_leaf.to_bytes(&mut cs.ns(|| "right_leaf_to_bytes"))?;
         let mut leaf_bytes = left_leaf_bytes;
         leaf_bytes.extend_from_slice(&right_leaf_bytes);
 
-        let inner_hash = HG::check_evaluation_gadget(cs.ns(|| "inner_hash"), parameters, leaf_bytes)?;
-
-        let left_inner_hash = &self.inner_hashes.0;
-        let right_inner_hash = &self.inner_hashes.1;
-
-        let inner_is_left =
-            AllocatedBit::alloc(&mut cs.ns(|| "inner_is_left"), || Ok(&inner_hash == left_inner_hash))?.into();
-        HG::OutputGadget::conditional_enforce_equal_or(
-            &mut cs.ns(|| "check_inner_hash_is_left"),
-            &inner_is_left,
-            &inner_hash,
-            left_inner_hash,
-            right_inner_hash,
-            should_enforce,
-        )?;
+        let mut current_hash = HG::check_evaluation_gadget(cs.ns(|| "inner_hash"), parameters, leaf_bytes)?;
+
+        for (i, (left_inner_hash, right_inner_hash)) in self.inner_hashes.iter().enumerate() {
+            let inner_is_left = AllocatedBit::alloc(&mut cs.ns(|| format!("inner_is_left_{}", i)), || {
+                Ok(&current_hash == left_inner_hash)
+            })?
+            .into();
+            HG::OutputGadget::conditional_enforce_equal_or(
+                &mut cs.ns(|| format!("check_inner_hash_is_left_{}", i)),
+                &inner_is_left,
+                &current_hash,
+                left_inner_hash,
+                right_inner_hash,
+                should_enforce,
+            )?;
+
+            let left_inner_hash_bytes =
+                left_inner_hash.to_bytes(&mut cs.ns(|| format!("left_inner_hash_to_bytes_{}", i)))?;
+            let right_inner_hash_bytes =
+                right_inner_hash.to_bytes(&mut cs.ns(|| format!("right_inner_hash_to_bytes_{}", i)))?;
+            let mut inner_hash_bytes = left_inner_hash_bytes;
+            inner_hash_bytes.extend_from_slice(&right_inner_hash_bytes);
+
+            current_hash =
+                HG::check_evaluation_gadget(cs.ns(|| format!("inner_hash_{}", i)), parameters, inner_hash_bytes)?;
+        }
 
         // Check that the root is valid
-        let left_inner_hash_bytes = left_inner_hash.to_bytes(&mut cs.ns(|| "left_inner_hash_to_bytes"))?;
-        let right_inner_hash_bytes = right_inner_hash.to_bytes(&mut cs.ns(|| "right_inner_hash_to_bytes"))?;
-        let mut inner_hash_bytes = left_inner_hash_bytes;
-        inner_hash_bytes.extend_from_slice(&right_inner_hash_bytes);
-
-        let declared_root = HG::check_evaluation_gadget(cs.ns(|| "root_hash"), parameters, inner_hash_bytes)?;
-
-        root.conditional_enforce_equal(&mut cs.ns(|| "check_root_is_valid"), &declared_root, should_enforce)?;
+        root.conditional_enforce_equal(&mut cs.ns(|| "check_root_is_valid"), &current_hash, should_enforce)?;
 
         Ok(())
     }
@@ -135,15 +141,17 @@ where
             Ok(commitment_merkle_path.leaves.1.clone())
         })?;
 
-        let left_hash = HGadget::OutputGadget::alloc(&mut cs.ns(|| "left inner hash"), || {
-            Ok(commitment_merkle_path.inner_hashes.0.clone())
-        })?;
-        let right_hash = HGadget::OutputGadget::alloc(&mut cs.ns(|| "right inner hash"), || {
-            Ok(commitment_merkle_path.inner_hashes.1.clone())
-        })?;
+        let mut inner_hashes = Vec::with_capacity(commitment_merkle_path.inner_hashes.len());
+        for (i, (left_hash, right_hash)) in commitment_merkle_path.inner_hashes.iter().enumerate() {
+            let left_hash =
+                HGadget::OutputGadget::alloc(&mut cs.ns(|| format!("left inner hash {}", i)), || Ok(left_hash))?;
+            let right_hash =
+                HGadget::OutputGadget::alloc(&mut cs.ns(|| format!("right inner hash {}", i)), || Ok(right_hash))?;
+
+            inner_hashes.push((left_hash, right_hash));
+        }
 
         let leaves = (left_leaf, right_leaf);
-        let inner_hashes = (left_hash, right_hash);
 
         Ok(Self { inner_hashes, leaves })
     }
@@ -162,15 +170,19 @@ where
             Ok(commitment_merkle_path.leaves.1.clone())
         })?;
 
-        let left_hash = HGadget::OutputGadget::alloc_input(&mut cs.ns(|| "left inner hash"), || {
-            Ok(commitment_merkle_path.inner_hashes.0.clone())
-        })?;
-        let right_hash = HGadget::OutputGadget::alloc_input(&mut cs.ns(|| "right inner hash"), || {
-            Ok(commitment_merkle_path.inner_hashes.1.clone())
-        })?;
+        let mut inner_hashes = Vec::with_capacity(commitment_merkle_path.inner_hashes.len());
+        for (i, (left_hash, right_hash)) in commitment_merkle_path.inner_hashes.iter().enumerate() {
+            let left_hash =
+                HGadget::OutputGadget::alloc_input(&mut cs.ns(|| format!("left inner hash {}", i)), || Ok(left_hash))?;
+            let right_hash =
+                HGadget::OutputGadget::alloc_input(&mut cs.ns(|| format!("right inner hash {}", i)), || {
+                    Ok(right_hash)
+                })?;
+
+            inner_hashes.push((left_hash, right_hash));
+        }
 
         let leaves = (left_leaf, right_leaf);
-        let inner_hashes = (left_hash, right_hash);
 
         Ok(Self { inner_hashes, leaves })
     }
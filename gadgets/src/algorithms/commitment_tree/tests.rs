@@ -66,16 +66,16 @@ pub type CG = PedersenCompressedCommitmentGadget<EdwardsBls, Fr, EdwardsBlsGadge
 fn generate_merkle_tree<C: CommitmentScheme, H: CRH, R: Rng>(
     commitment: &C,
     crh: &H,
+    num_leaves: usize,
     rng: &mut R,
 ) -> CommitmentMerkleTree<C, H> {
-    let default = <C as CommitmentScheme>::Output::default();
-    let mut leaves = [default.clone(), default.clone(), default.clone(), default];
+    let mut leaves = Vec::with_capacity(num_leaves);
 
-    for leaf in &mut leaves {
+    for _ in 0..num_leaves {
         let leaf_input: [u8; 32] = rng.gen();
         let randomness = <C as CommitmentScheme>::Randomness::rand(rng);
 
-        *leaf = commitment.commit(&leaf_input, &randomness).unwrap();
+        leaves.push(commitment.commit(&leaf_input, &randomness).unwrap());
     }
 
     CommitmentMerkleTree::new(crh.clone(), &leaves).unwrap()
@@ -90,17 +90,18 @@ fn commitment_tree_test<
     R: Rng,
 >(
     use_bad_root: bool,
+    num_leaves: usize,
     rng: &mut R,
 ) {
     let commitment = C::setup(rng);
     let crh = H::setup(rng);
 
-    let merkle_tree = generate_merkle_tree(&commitment, &crh, rng);
+    let merkle_tree = generate_merkle_tree(&commitment, &crh, num_leaves, rng);
 
     let mut satisfied = true;
     for (i, leaf) in merkle_tree.leaves().iter().enumerate() {
-        let proof = merkle_tree.generate_proof(&leaf).unwrap();
-        assert!(proof.verify(&crh, &merkle_tree.root(), &leaf).unwrap());
+        let proof = merkle_tree.generate_proof(leaf).unwrap();
+        assert!(proof.verify(&crh, &merkle_tree.root(), leaf).unwrap());
 
         let mut num_constraints = 0;
 
@@ -178,7 +179,9 @@ fn commitment_tree_test<
 fn commitment_tree_good_root_test() {
     let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
 
-    commitment_tree_test::<C, H, CG, HG, _, _>(false, rng);
+    for num_leaves in 1..=8 {
+        commitment_tree_test::<C, H, CG, HG, _, _>(false, num_leaves, rng);
+    }
 }
 
 #[should_panic]
@@ -186,5 +189,42 @@ fn commitment_tree_good_root_test() {
 fn commitment_tree_bad_root_test() {
     let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
 
-    commitment_tree_test::<C, H, CG, HG, _, _>(true, rng);
+    commitment_tree_test::<C, H, CG, HG, _, _>(true, 4, rng);
+}
+
+#[test]
+fn commitment_tree_bad_leaf_test() {
+    let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+
+    let commitment = C::setup(rng);
+    let crh = H::setup(rng);
+
+    let merkle_tree = generate_merkle_tree(&commitment, &crh, 4, rng);
+    let leaves = merkle_tree.leaves();
+    let proof = merkle_tree.generate_proof(&leaves[0]).unwrap();
+
+    let mut cs = TestConstraintSystem::<Fr>::new();
+
+    let root_gadget =
+        <HG as CRHGadget<H, _>>::OutputGadget::alloc(&mut cs.ns(|| "root"), || Ok(merkle_tree.root())).unwrap();
+    let crh_parameters =
+        <HG as CRHGadget<_, _>>::ParametersGadget::alloc(&mut cs.ns(|| "crh_parameters"), || Ok(crh.parameters()))
+            .unwrap();
+
+    // The leaf is in the tree, but not in the path.
+    let leaf_gadget =
+        <CG as CommitmentGadget<C, _>>::OutputGadget::alloc(&mut cs.ns(|| "leaf"), || Ok(&leaves[2])).unwrap();
+    let commitment_witness =
+        CommitmentMerklePathGadget::<_, _, CG, HG, _>::alloc(&mut cs.ns(|| "witness"), || Ok(proof)).unwrap();
+
+    commitment_witness
+        .check_membership(
+            &mut cs.ns(|| "witness_check"),
+            &crh_parameters,
+            &root_gadget,
+            &leaf_gadget,
+        )
+        .unwrap();
+
+    assert!(!cs.is_satisfied());
 }
@@ -14,18 +14,21 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::errors::AmountError;
 use snarkvm_utilities::bytes::{FromBytes, ToBytes};
 
 use serde::Serialize;
 use std::{
     fmt,
     io::{Read, Result as IoResult, Write},
+    str::FromStr,
 };
 
 /// Represents the amount of ALEOs in UNITS
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 pub struct AleoAmount(pub i64);
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Denomination {
     // AB
     BYTE,
@@ -46,6 +49,19 @@ impl Denomination {
     }
 }
 
+impl FromStr for Denomination {
+    type Err = AmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "AB" => Ok(Denomination::BYTE),
+            "AG" => Ok(Denomination::GATE),
+            "ALEO" => Ok(Denomination::ALEO),
+            _ => Err(AmountError::InvalidDenomination(s.to_string())),
+        }
+    }
+}
+
 impl fmt::Display for Denomination {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", match self {
@@ -97,6 +113,42 @@ impl AleoAmount {
         Self::from_bytes(self.0 - b.0)
     }
 
+    /// Add the values of two `AleoAmount`s, returning `None` on overflow.
+    pub fn checked_add(self, b: Self) -> Option<Self> {
+        self.0.checked_add(b.0).map(Self::from_bytes)
+    }
+
+    /// Subtract the value of two `AleoAmount`s, returning `None` on overflow.
+    pub fn checked_sub(self, b: Self) -> Option<Self> {
+        self.0.checked_sub(b.0).map(Self::from_bytes)
+    }
+
+    /// Multiply the amount by a scalar, returning `None` on overflow.
+    pub fn checked_mul(self, b: i64) -> Option<Self> {
+        self.0.checked_mul(b).map(Self::from_bytes)
+    }
+
+    /// Negate the amount, returning `None` on overflow.
+    pub fn checked_neg(self) -> Option<Self> {
+        self.0.checked_neg().map(Self::from_bytes)
+    }
+
+    /// Returns the amount as a decimal string in the given denomination, e.g. `1.5 ALEO`.
+    pub fn to_string_in(self, denomination: Denomination) -> String {
+        let scale = 10_u64.pow(denomination.precision());
+        let sign = if self.is_negative() { "-" } else { "" };
+        let whole = self.0.wrapping_abs() as u64 / scale;
+        let fraction = self.0.wrapping_abs() as u64 % scale;
+
+        match fraction {
+            0 => format!("{}{} {}", sign, whole, denomination),
+            _ => {
+                let fraction = format!("{:0width$}", fraction, width = denomination.precision() as usize);
+                format!("{}{}.{} {}", sign, whole, fraction.trim_end_matches('0'), denomination)
+            }
+        }
+    }
+
     /// Returns `true` the amount is positive and `false` if the amount is zero or
     /// negative.
     pub const fn is_positive(self) -> bool {
@@ -110,6 +162,50 @@ impl AleoAmount {
     }
 }
 
+impl FromStr for AleoAmount {
+    type Err = AmountError;
+
+    /// Parses an amount such as `1.5 ALEO`, `1500 AG` or `1500000 AB`.
+    /// An amount without a denomination is read in AB.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AmountError::InvalidAmount(s.to_string());
+
+        let mut parts = s.split_whitespace();
+        let value = parts.next().ok_or_else(invalid)?;
+        let denomination = match parts.next() {
+            Some(denomination) => denomination.parse::<Denomination>()?,
+            None => Denomination::BYTE,
+        };
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+
+        let (negative, value) = match value.strip_prefix('-') {
+            Some(value) => (true, value),
+            None => (false, value),
+        };
+        let (whole, fraction) = match value.find('.') {
+            Some(index) => (&value[..index], &value[index + 1..]),
+            None => (value, ""),
+        };
+
+        let precision = denomination.precision() as usize;
+        let is_digits = |digits: &str| digits.chars().all(|c| c.is_ascii_digit());
+        if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) || fraction.len() > precision {
+            return Err(invalid());
+        }
+
+        // The sign is parsed along with the digits, as i64::MIN has no positive counterpart.
+        let sign = if negative { "-" } else { "" };
+        let out_of_bounds = || AmountError::AmountOutOfBounds(s.to_string(), i64::MAX.to_string());
+        let bytes = format!("{}{}{:0<width$}", sign, whole, fraction, width = precision)
+            .parse::<i64>()
+            .map_err(|_| out_of_bounds())?;
+
+        Ok(Self::from_bytes(bytes))
+    }
+}
+
 impl ToBytes for AleoAmount {
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
         self.0.write(&mut writer)
@@ -249,6 +345,84 @@ mod tests {
         }
     }
 
+    mod denominations {
+        use super::*;
+
+        const TEST_STRINGS: [(i64, &str, &str, &str); 5] = [
+            (0, "0 AB", "0 AG", "0 ALEO"),
+            (1, "1 AB", "0.001 AG", "0.000001 ALEO"),
+            (1_500_000, "1500000 AB", "1500 AG", "1.5 ALEO"),
+            (-1_234_567, "-1234567 AB", "-1234.567 AG", "-1.234567 ALEO"),
+            (150_000_000, "150000000 AB", "150000 AG", "150 ALEO"),
+        ];
+
+        #[test]
+        fn test_to_string_in() {
+            for (bytes, byte, gate, aleo) in TEST_STRINGS.iter() {
+                let amount = AleoAmount(*bytes);
+                assert_eq!(*byte, amount.to_string_in(Denomination::BYTE));
+                assert_eq!(*gate, amount.to_string_in(Denomination::GATE));
+                assert_eq!(*aleo, amount.to_string_in(Denomination::ALEO));
+            }
+        }
+
+        #[test]
+        fn test_from_str() {
+            for (bytes, byte, gate, aleo) in TEST_STRINGS.iter() {
+                assert_eq!(AleoAmount(*bytes), byte.parse().unwrap());
+                assert_eq!(AleoAmount(*bytes), gate.parse().unwrap());
+                assert_eq!(AleoAmount(*bytes), aleo.parse().unwrap());
+            }
+
+            assert_eq!(AleoAmount(42), "42".parse().unwrap());
+            assert_eq!(AleoAmount(1_500_000), "1.50 ALEO".parse().unwrap());
+            assert_eq!(AleoAmount(i64::MIN), "-9223372036854775808 AB".parse().unwrap());
+            assert_eq!(AleoAmount(i64::MAX), "9223372036854775807 AB".parse().unwrap());
+        }
+
+        #[test]
+        fn test_invalid_from_str() {
+            let invalid = [
+                "",
+                "ALEO",
+                "1.5 AB",
+                "0.0000001 ALEO",
+                "1 ALEOS",
+                "1 ALEO extra",
+                ".5 ALEO",
+                "+1 AB",
+                "1e6 AB",
+                "9223372036854775808 AB",
+                "-9223372036854775809 AB",
+                "9223372036854775807 ALEO",
+            ];
+
+            for s in invalid.iter() {
+                assert!(s.parse::<AleoAmount>().is_err(), "{} should not parse", s);
+            }
+        }
+    }
+
+    mod checked_arithmetic {
+        use super::*;
+
+        #[test]
+        fn test_checked_arithmetic() {
+            let max = AleoAmount(i64::MAX);
+            let min = AleoAmount(i64::MIN);
+
+            assert_eq!(Some(AleoAmount(3)), AleoAmount(1).checked_add(AleoAmount(2)));
+            assert_eq!(Some(AleoAmount(-1)), AleoAmount(1).checked_sub(AleoAmount(2)));
+            assert_eq!(Some(AleoAmount(6)), AleoAmount(2).checked_mul(3));
+            assert_eq!(Some(AleoAmount(-2)), AleoAmount(2).checked_neg());
+
+            assert_eq!(None, max.checked_add(AleoAmount::ONE_BYTE));
+            assert_eq!(None, min.checked_sub(AleoAmount::ONE_BYTE));
+            assert_eq!(None, max.checked_mul(2));
+            assert_eq!(None, min.checked_neg());
+        }
+    }
+
     mod test_invalid {
         use super::*;
 
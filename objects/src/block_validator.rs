@@ -0,0 +1,207 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    errors::BlockError,
+    merkle_root,
+    merkle_root_with_subroots,
+    pedersen_merkle_root,
+    traits::Transaction,
+    AleoAmount,
+    Block,
+    BlockHeader,
+    DifficultyParameters,
+    MerkleRootHash,
+    MonetaryPolicy,
+    MASKED_TREE_DEPTH,
+};
+use snarkvm_utilities::has_duplicates;
+
+use chrono::Utc;
+use std::collections::HashSet;
+
+/// The consensus parameters used to validate blocks.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsensusParameters {
+    /// The maximum size of a serialized block in bytes.
+    pub max_block_size: usize,
+    /// The maximum number of seconds a block timestamp may be ahead of the validator's clock.
+    pub max_future_block_time: i64,
+    /// The parameters of the difficulty adjustment algorithm.
+    pub difficulty: DifficultyParameters,
+    /// The block reward schedule.
+    pub monetary_policy: MonetaryPolicy,
+}
+
+impl Default for ConsensusParameters {
+    fn default() -> Self {
+        Self {
+            max_block_size: 1_000_000_000,
+            max_future_block_time: 120,
+            difficulty: DifficultyParameters::default(),
+            monetary_policy: MonetaryPolicy::default(),
+        }
+    }
+}
+
+/// Checks the consensus rules of a block that can be verified without access to the ledger.
+#[derive(Clone, Debug, Default)]
+pub struct BlockValidator {
+    parameters: ConsensusParameters,
+}
+
+impl BlockValidator {
+    /// Returns a new block validator with the given consensus parameters.
+    pub fn new(parameters: ConsensusParameters) -> Self {
+        Self { parameters }
+    }
+
+    /// Returns the consensus parameters of the validator.
+    pub fn parameters(&self) -> &ConsensusParameters {
+        &self.parameters
+    }
+
+    /// Checks every stateless rule against the given block at the given height,
+    /// using the current time.
+    pub fn validate_block<T: Transaction<ValueBalance = AleoAmount>>(
+        &self,
+        block: &Block<T>,
+        height: u32,
+    ) -> Result<(), BlockError> {
+        self.validate_block_at(block, height, Utc::now().timestamp())
+    }
+
+    /// Checks every stateless rule against the given block at the given height,
+    /// using `current_time` as the Unix timestamp of the validator's clock.
+    pub fn validate_block_at<T: Transaction<ValueBalance = AleoAmount>>(
+        &self,
+        block: &Block<T>,
+        height: u32,
+        current_time: i64,
+    ) -> Result<(), BlockError> {
+        self.check_block_size(block)?;
+        self.check_timestamp(&block.header, current_time)?;
+        self.check_coinbase_reward(height, block.transactions.as_slice())?;
+        self.check_duplicates(block.transactions.as_slice())?;
+
+        let transaction_ids = block.transactions.to_transaction_ids()?;
+        self.check_merkle_root(&block.header, &transaction_ids)?;
+        self.check_pedersen_merkle_root(&block.header, &transaction_ids)
+    }
+
+    /// Checks that the timestamp and difficulty target of the given header follow
+    /// from its `ancestors`, which are ordered from oldest to newest.
+    pub fn validate_difficulty(&self, header: &BlockHeader, ancestors: &[BlockHeader]) -> Result<(), BlockError> {
+        Ok(self
+            .parameters
+            .difficulty
+            .validate_difficulty_target(header, ancestors)?)
+    }
+
+    /// Checks that there is exactly one coinbase transaction, identified by its negative
+    /// value balance, and that at the given height it mints at most the block reward
+    /// plus the fees collected from the rest of the block.
+    pub fn check_coinbase_reward<T: Transaction<ValueBalance = AleoAmount>>(
+        &self,
+        height: u32,
+        transactions: &[T],
+    ) -> Result<(), BlockError> {
+        self.parameters
+            .monetary_policy
+            .validate_block_coinbase(height, transactions)
+    }
+
+    /// Checks that the serialized block does not exceed the maximum block size.
+    pub fn check_block_size<T: Transaction>(&self, block: &Block<T>) -> Result<(), BlockError> {
+        let block_size = block.serialize()?.len();
+        if block_size > self.parameters.max_block_size {
+            return Err(BlockError::BlockTooLarge(block_size, self.parameters.max_block_size));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the header timestamp is not too far ahead of `current_time`.
+    pub fn check_timestamp(&self, header: &BlockHeader, current_time: i64) -> Result<(), BlockError> {
+        let max_time = current_time.saturating_add(self.parameters.max_future_block_time);
+        if header.time > max_time {
+            return Err(BlockError::TimestampTooFarInFuture(header.time, max_time));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that no transaction, serial number, or commitment appears twice in the block.
+    pub fn check_duplicates<T: Transaction>(&self, transactions: &[T]) -> Result<(), BlockError> {
+        let mut transaction_ids = HashSet::with_capacity(transactions.len());
+        for transaction in transactions {
+            let transaction_id = transaction.transaction_id()?;
+            if !transaction_ids.insert(transaction_id) {
+                return Err(BlockError::DuplicateTransaction(hex::encode(transaction_id)));
+            }
+        }
+
+        if has_duplicates(transactions.iter().flat_map(|tx| tx.old_serial_numbers())) {
+            return Err(BlockError::DuplicateSerialNumbers);
+        }
+
+        if has_duplicates(transactions.iter().flat_map(|tx| tx.new_commitments())) {
+            return Err(BlockError::DuplicateCommitments);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the header's merkle root hash commits to the given transaction ids.
+    pub fn check_merkle_root(&self, header: &BlockHeader, transaction_ids: &[[u8; 32]]) -> Result<(), BlockError> {
+        if transaction_ids.is_empty() {
+            return Err(BlockError::EmptyBlock);
+        }
+
+        let expected = MerkleRootHash(merkle_root(transaction_ids));
+        if header.merkle_root_hash != expected {
+            return Err(BlockError::InvalidMerkleRoot(
+                expected.to_string(),
+                header.merkle_root_hash.to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the header's pedersen merkle root hash commits to the subroots
+    /// of the given transaction ids.
+    pub fn check_pedersen_merkle_root(
+        &self,
+        header: &BlockHeader,
+        transaction_ids: &[[u8; 32]],
+    ) -> Result<(), BlockError> {
+        if transaction_ids.is_empty() {
+            return Err(BlockError::EmptyBlock);
+        }
+
+        let (_, subroots) = merkle_root_with_subroots(transaction_ids, MASKED_TREE_DEPTH);
+        let expected = pedersen_merkle_root(&subroots);
+        if header.pedersen_merkle_root_hash != expected {
+            return Err(BlockError::InvalidPedersenMerkleRoot(
+                expected.to_string(),
+                header.pedersen_merkle_root_hash.to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,311 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{errors::DifficultyError, BlockHeader};
+
+/// The largest allowed `max_target_increase_percent`.
+pub const MAX_TARGET_INCREASE_PERCENT: u64 = 10_000;
+
+/// The parameters of the difficulty adjustment algorithm.
+///
+/// A lower `difficulty_target` is harder to mine, as a proof is only valid if its
+/// difficulty hash is at most the target. The next target is the average target over the
+/// last `averaging_window` blocks, scaled by the ratio of the observed timespan to the
+/// expected timespan of the window. Timespans are measured between the median times past
+/// at either end of the window, so a single miner cannot skew them with an outlier timestamp.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DifficultyParameters {
+    /// The desired number of seconds between blocks.
+    pub target_block_time: i64,
+    /// The number of blocks whose targets are averaged to compute the next target.
+    pub averaging_window: usize,
+    /// The number of blocks whose timestamps are used to compute the median time past.
+    pub median_time_span: usize,
+    /// The maximum percentage by which the target may increase (get easier) from one block to the next.
+    pub max_target_increase_percent: u64,
+    /// The maximum percentage by which the target may decrease (get harder) from one block to the next.
+    pub max_target_decrease_percent: u64,
+    /// The easiest allowed difficulty target.
+    pub max_target: u64,
+}
+
+impl Default for DifficultyParameters {
+    fn default() -> Self {
+        Self {
+            target_block_time: 10,
+            averaging_window: 17,
+            median_time_span: 11,
+            max_target_increase_percent: 32,
+            max_target_decrease_percent: 16,
+            max_target: u64::MAX,
+        }
+    }
+}
+
+impl DifficultyParameters {
+    /// Returns the number of ancestors needed before the target is adjusted.
+    /// With a shorter history, the parent's target is carried over unchanged.
+    pub fn required_ancestors(&self) -> usize {
+        self.averaging_window + self.median_time_span
+    }
+
+    /// Returns the median timestamp of the last `median_time_span` ancestors,
+    /// or `None` if there are no ancestors.
+    pub fn median_time_past(&self, ancestors: &[BlockHeader]) -> Option<i64> {
+        let start = ancestors.len().saturating_sub(self.median_time_span);
+        median_time(&ancestors[start..])
+    }
+
+    /// Returns the difficulty target expected of the block following `ancestors`,
+    /// which are ordered from oldest to newest and end with the parent block.
+    pub fn expected_difficulty_target(&self, ancestors: &[BlockHeader]) -> Result<u64, DifficultyError> {
+        self.check()?;
+
+        let parent = ancestors.last().ok_or(DifficultyError::EmptyWindow)?;
+        if ancestors.len() < self.required_ancestors() {
+            return Ok(parent.difficulty_target);
+        }
+
+        let window = &ancestors[ancestors.len() - self.required_ancestors()..];
+
+        // The average target over the last `averaging_window` blocks.
+        let target_sum: u128 = window[self.median_time_span..]
+            .iter()
+            .map(|header| header.difficulty_target as u128)
+            .sum();
+        let average_target = target_sum / self.averaging_window as u128;
+
+        // The median times past at the start and end of the averaging window.
+        let oldest_median_time = median_time(&window[..self.median_time_span]).unwrap();
+        let newest_median_time = median_time(&window[self.averaging_window..]).unwrap();
+
+        let expected_timespan = (self.target_block_time as u128) * (self.averaging_window as u128);
+        let actual_timespan = newest_median_time.saturating_sub(oldest_median_time).max(1) as u128;
+
+        let next_target = average_target * actual_timespan / expected_timespan;
+
+        // Clamp the change relative to the parent's target.
+        let parent_target = parent.difficulty_target as u128;
+        let lower_bound = parent_target * (100 - self.max_target_decrease_percent as u128) / 100;
+        let upper_bound = parent_target
+            .checked_mul(100 + self.max_target_increase_percent as u128)
+            .ok_or_else(|| DifficultyError::InvalidParameters("the maximum target increase overflows".into()))?
+            / 100;
+        let next_target = next_target.max(lower_bound).min(upper_bound);
+
+        Ok(next_target.min(self.max_target as u128).max(1) as u64)
+    }
+
+    /// Checks that the timestamp and difficulty target of `header` follow the
+    /// consensus rules, given its `ancestors` ordered from oldest to newest.
+    pub fn validate_difficulty_target(
+        &self,
+        header: &BlockHeader,
+        ancestors: &[BlockHeader],
+    ) -> Result<(), DifficultyError> {
+        if let Some(median_time_past) = self.median_time_past(ancestors) {
+            if header.time <= median_time_past {
+                return Err(DifficultyError::TimestampTooOld(header.time, median_time_past));
+            }
+        }
+
+        let expected_target = self.expected_difficulty_target(ancestors)?;
+        if header.difficulty_target != expected_target {
+            return Err(DifficultyError::InvalidTarget(
+                expected_target,
+                header.difficulty_target,
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn check(&self) -> Result<(), DifficultyError> {
+        if self.target_block_time <= 0 {
+            return Err(DifficultyError::InvalidParameters(
+                "the target block time must be positive".into(),
+            ));
+        }
+        if self.averaging_window == 0 || self.median_time_span == 0 {
+            return Err(DifficultyError::InvalidParameters(
+                "the averaging window and median time span must be non-empty".into(),
+            ));
+        }
+        if self.max_target_decrease_percent > 100 {
+            return Err(DifficultyError::InvalidParameters(
+                "the target cannot decrease by more than 100 percent".into(),
+            ));
+        }
+        if self.max_target_increase_percent > MAX_TARGET_INCREASE_PERCENT {
+            return Err(DifficultyError::InvalidParameters(format!(
+                "the target cannot increase by more than {} percent",
+                MAX_TARGET_INCREASE_PERCENT
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the median timestamp of the given headers.
+fn median_time(headers: &[BlockHeader]) -> Option<i64> {
+    let mut times: Vec<i64> = headers.iter().map(|header| header.time).collect();
+    times.sort_unstable();
+    times.get(times.len() / 2).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockHeaderHash, MerkleRootHash, PedersenMerkleRootHash, ProofOfSuccinctWork};
+
+    fn header(time: i64, difficulty_target: u64) -> BlockHeader {
+        BlockHeader {
+            previous_block_hash: BlockHeaderHash([0u8; 32]),
+            merkle_root_hash: MerkleRootHash([0u8; 32]),
+            pedersen_merkle_root_hash: PedersenMerkleRootHash([0u8; 32]),
+            proof: ProofOfSuccinctWork([0u8; ProofOfSuccinctWork::size()]),
+            time,
+            difficulty_target,
+            nonce: 0,
+        }
+    }
+
+    fn chain(times: &[i64], targets: &[u64]) -> Vec<BlockHeader> {
+        times
+            .iter()
+            .zip(targets)
+            .map(|(&time, &target)| header(time, target))
+            .collect()
+    }
+
+    fn parameters() -> DifficultyParameters {
+        DifficultyParameters {
+            target_block_time: 10,
+            averaging_window: 4,
+            median_time_span: 3,
+            max_target_increase_percent: 50,
+            max_target_decrease_percent: 25,
+            max_target: u64::MAX,
+        }
+    }
+
+    #[test]
+    fn test_expected_difficulty_target_vectors() {
+        let parameters = parameters();
+        let constant = [1000u64; 7];
+
+        // (timestamps, targets, expected next target)
+        let vectors: Vec<(Vec<i64>, Vec<u64>, u64)> = vec![
+            // On schedule.
+            (vec![0, 10, 20, 30, 40, 50, 60], constant.to_vec(), 1000),
+            // Slightly slow blocks ease the target.
+            (vec![0, 12, 24, 36, 48, 60, 72], constant.to_vec(), 1200),
+            // Fast blocks are clamped to the maximum decrease.
+            (vec![0, 5, 10, 15, 20, 25, 30], constant.to_vec(), 750),
+            // Slow blocks are clamped to the maximum increase.
+            (vec![0, 40, 80, 120, 160, 200, 240], constant.to_vec(), 1500),
+            // The average is taken over the last `averaging_window` targets.
+            (
+                vec![0, 10, 20, 30, 40, 50, 60],
+                vec![1, 1, 1, 800, 900, 1000, 1100],
+                950,
+            ),
+            // An outlier timestamp does not move the median time past.
+            (vec![0, 10, 20, 30, 40, 50, 1000], constant.to_vec(), 1000),
+            // The target never exceeds the maximum.
+            (vec![0, 40, 80, 120, 160, 200, 240], vec![u64::MAX; 7], u64::MAX),
+        ];
+
+        for (times, targets, expected) in vectors {
+            let ancestors = chain(&times, &targets);
+            assert_eq!(expected, parameters.expected_difficulty_target(&ancestors).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_short_history_keeps_parent_target() {
+        let parameters = parameters();
+        let ancestors = chain(&[0, 1, 2], &[10, 20, 30]);
+
+        assert_eq!(30, parameters.expected_difficulty_target(&ancestors).unwrap());
+        assert!(matches!(
+            parameters.expected_difficulty_target(&[]),
+            Err(DifficultyError::EmptyWindow)
+        ));
+    }
+
+    #[test]
+    fn test_median_time_past() {
+        let parameters = parameters();
+        let ancestors = chain(&[0, 10, 20, 50, 30], &[0; 5]);
+
+        assert_eq!(Some(30), parameters.median_time_past(&ancestors));
+        assert_eq!(Some(0), parameters.median_time_past(&ancestors[..1]));
+        assert_eq!(None, parameters.median_time_past(&[]));
+    }
+
+    #[test]
+    fn test_validate_difficulty_target() {
+        let parameters = parameters();
+        let ancestors = chain(&[0, 12, 24, 36, 48, 60, 72], &[1000; 7]);
+
+        assert!(parameters
+            .validate_difficulty_target(&header(84, 1200), &ancestors)
+            .is_ok());
+        assert!(matches!(
+            parameters.validate_difficulty_target(&header(84, 1000), &ancestors),
+            Err(DifficultyError::InvalidTarget(1200, 1000))
+        ));
+        assert!(matches!(
+            parameters.validate_difficulty_target(&header(60, 1200), &ancestors),
+            Err(DifficultyError::TimestampTooOld(60, 60))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let ancestors = chain(&[0], &[1]);
+        let parameters = DifficultyParameters {
+            averaging_window: 0,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            parameters.expected_difficulty_target(&ancestors),
+            Err(DifficultyError::InvalidParameters(_))
+        ));
+
+        let parameters = DifficultyParameters {
+            max_target_increase_percent: u64::MAX,
+            ..Default::default()
+        };
+        assert!(matches!(
+            parameters.expected_difficulty_target(&ancestors),
+            Err(DifficultyError::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn test_max_target_increase() {
+        let parameters = DifficultyParameters {
+            max_target_increase_percent: MAX_TARGET_INCREASE_PERCENT,
+            ..parameters()
+        };
+        let ancestors = chain(&[0, 40, 80, 120, 160, 200, 240], &[u64::MAX; 7]);
+
+        assert_eq!(u64::MAX, parameters.expected_difficulty_target(&ancestors).unwrap());
+    }
+}
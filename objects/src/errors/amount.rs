@@ -26,4 +26,7 @@ pub enum AmountError {
 
     #[error("invalid amount: {}", _0)]
     InvalidAmount(String),
+
+    #[error("invalid denomination: {}", _0)]
+    InvalidDenomination(String),
 }
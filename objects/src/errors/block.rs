@@ -14,18 +14,36 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::errors::TransactionError;
+use crate::errors::{AmountError, DifficultyError, TransactionError};
 
 use std::fmt::Debug;
 
 #[derive(Debug, Error)]
 pub enum BlockError {
+    #[error("{}", _0)]
+    AmountError(AmountError),
+
     #[error("block already exists {}", _0)]
     BlockExists(String),
 
     #[error("{}: {}", _0, _1)]
     Crate(&'static str, String),
 
+    #[error("{}", _0)]
+    DifficultyError(DifficultyError),
+
+    #[error("the block contains duplicate commitments")]
+    DuplicateCommitments,
+
+    #[error("the block contains duplicate serial numbers")]
+    DuplicateSerialNumbers,
+
+    #[error("the block contains a duplicate transaction {}", _0)]
+    DuplicateTransaction(String),
+
+    #[error("the block contains no transactions")]
+    EmptyBlock,
+
     #[error("{}", _0)]
     Message(String),
 
@@ -40,6 +58,24 @@ pub enum BlockError {
 
     #[error("the given block {} is not a canonical or sidechain block", _0)]
     IrrelevantBlock(String),
+
+    #[error("block size {} exceeds the maximum of {} bytes", _0, _1)]
+    BlockTooLarge(usize, usize),
+
+    #[error("expected exactly one coinbase transaction, found {}", _0)]
+    InvalidCoinbaseCount(usize),
+
+    #[error("coinbase value {} exceeds the maximum of {}", _0, _1)]
+    InvalidCoinbaseValue(String, String),
+
+    #[error("expected merkle root hash {} got {}", _0, _1)]
+    InvalidMerkleRoot(String, String),
+
+    #[error("expected pedersen merkle root hash {} got {}", _0, _1)]
+    InvalidPedersenMerkleRoot(String, String),
+
+    #[error("block timestamp {} is later than the maximum allowed {}", _0, _1)]
+    TimestampTooFarInFuture(i64, i64),
 }
 
 impl From<std::io::Error> for BlockError {
@@ -48,6 +84,18 @@ impl From<std::io::Error> for BlockError {
     }
 }
 
+impl From<AmountError> for BlockError {
+    fn from(error: AmountError) -> Self {
+        BlockError::AmountError(error)
+    }
+}
+
+impl From<DifficultyError> for BlockError {
+    fn from(error: DifficultyError) -> Self {
+        BlockError::DifficultyError(error)
+    }
+}
+
 impl From<TransactionError> for BlockError {
     fn from(error: TransactionError) -> Self {
         BlockError::TransactionError(error)
@@ -20,6 +20,9 @@ pub use amount::*;
 pub mod block;
 pub use block::*;
 
+pub mod difficulty;
+pub use difficulty::*;
+
 pub mod storage;
 pub use storage::*;
 
@@ -43,9 +43,15 @@ pub enum StorageError {
     #[error("existing transaction memo {:?}", _0)]
     ExistingMemo(Vec<u8>),
 
+    #[error("transaction {} already exists in the memory pool", _0)]
+    ExistingMemoryPoolTransaction(String),
+
     #[error("existing serial number {:?}", _0)]
     ExistingSn(Vec<u8>),
 
+    #[error("expected genesis block {} got {}", _0, _1)]
+    GenesisBlockMismatch(String, String),
+
     #[error("Can't decommit the genesis block")]
     InvalidBlockDecommit,
 
@@ -58,12 +64,27 @@ pub enum StorageError {
     #[error("invalid column family {}", _0)]
     InvalidColumnFamily(u32),
 
+    #[error("invalid ledger digest {}", _0)]
+    InvalidDigest(String),
+
+    #[error("invalid value for ledger digest {}", _0)]
+    InvalidDigestValue(String),
+
+    #[error("invalid ledger snapshot: {}", _0)]
+    InvalidSnapshot(String),
+
     #[error("missing outpoint with transaction with id {} and index {}", _0, _1)]
     InvalidOutpoint(String, usize),
 
     #[error("missing transaction with id {}", _0)]
     InvalidTransactionId(String),
 
+    #[error("transaction {} conflicts with the ledger", _0)]
+    LedgerConflict(String),
+
+    #[error("transaction {} conflicts with a transaction in the memory pool", _0)]
+    MemoryPoolConflict(String),
+
     #[error("{}", _0)]
     Message(String),
 
@@ -82,6 +103,12 @@ pub enum StorageError {
     #[error("missing child block hashes value for block hash {}", _0)]
     MissingChildBlock(String),
 
+    #[error("missing commitment {}", _0)]
+    MissingCm(String),
+
+    #[error("missing commitment index value given ledger digest {}", _0)]
+    MissingDigestCmIndex(String),
+
     #[error("missing current commitment index")]
     MissingCurrentCmIndex,
 
@@ -109,12 +136,42 @@ pub enum StorageError {
     #[error("missing genesis serial number")]
     MissingGenesisSn,
 
+    #[error("missing record value for record commitment {}", _0)]
+    MissingRecord(String),
+
     #[error("missing transaction meta value for transaction id {}", _0)]
     MissingTransactionMeta(String),
 
     #[error("missing value given key {}", _0)]
     MissingValue(String),
 
+    #[error(
+        "the ledger has schema version {}, which must be migrated to schema version {} first",
+        _0,
+        _1
+    )]
+    OutdatedSchemaVersion(u32, u32),
+
+    #[error("pruned block transactions value for block hash {}", _0)]
+    PrunedBlock(String),
+
+    #[error(
+        "can't revert to block {}, the transactions of the blocks before block {} are pruned",
+        _0,
+        _1
+    )]
+    PrunedRevert(u32, u32),
+
+    #[error("the ledger view is not on top of the latest ledger state")]
+    StaleLedgerView,
+
+    #[error(
+        "the ledger has schema version {}, which is newer than the supported schema version {}",
+        _0,
+        _1
+    )]
+    UnsupportedSchemaVersion(u32, u32),
+
     #[error("Null Error {:?}", _0)]
     NullError(()),
 
@@ -29,6 +29,12 @@ pub use block_header::*;
 pub mod block_header_hash;
 pub use block_header_hash::*;
 
+pub mod block_validator;
+pub use block_validator::*;
+
+pub mod difficulty;
+pub use difficulty::*;
+
 pub mod dpc;
 pub use dpc::*;
 
@@ -41,6 +47,9 @@ pub use merkle_root_hash::*;
 pub mod merkle_tree;
 pub use merkle_tree::*;
 
+pub mod monetary_policy;
+pub use monetary_policy::*;
+
 pub mod network;
 pub use network::*;
 
@@ -0,0 +1,218 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    errors::{AmountError, BlockError},
+    traits::Transaction,
+    AleoAmount,
+    Denomination,
+};
+
+use std::convert::TryFrom;
+
+/// The schedule by which new coins are minted in coinbase transactions.
+///
+/// The block reward starts at `initial_block_reward` and halves every
+/// `halving_interval` blocks, until it rounds down to zero.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MonetaryPolicy {
+    /// The block reward of the first era.
+    pub initial_block_reward: AleoAmount,
+    /// The number of blocks between reward halvings.
+    pub halving_interval: u32,
+}
+
+impl Default for MonetaryPolicy {
+    fn default() -> Self {
+        Self {
+            initial_block_reward: AleoAmount::from_aleo(150),
+            // About four years of blocks at a 10 second block time.
+            halving_interval: 12_614_400,
+        }
+    }
+}
+
+impl MonetaryPolicy {
+    /// Returns the block reward for the block at the given height.
+    pub fn block_reward(&self, height: u32) -> AleoAmount {
+        let halvings = height.checked_div(self.halving_interval).unwrap_or(0);
+        match halvings {
+            0..=62 => AleoAmount(self.initial_block_reward.0 >> halvings),
+            _ => AleoAmount::ZERO,
+        }
+    }
+
+    /// Returns the total amount minted by the blocks from genesis up to and including the given height.
+    pub fn total_supply(&self, height: u32) -> Result<AleoAmount, AmountError> {
+        let mut remaining_blocks = height as u128 + 1;
+        let mut supply = 0i128;
+        let mut era_start = 0u32;
+
+        while remaining_blocks > 0 {
+            let reward = self.block_reward(era_start);
+            if reward == AleoAmount::ZERO {
+                break;
+            }
+
+            let era_blocks = match self.halving_interval {
+                0 => remaining_blocks,
+                interval => remaining_blocks.min(interval as u128),
+            };
+            supply += reward.0 as i128 * era_blocks as i128;
+            remaining_blocks -= era_blocks;
+            era_start = era_start.saturating_add(self.halving_interval);
+        }
+
+        i64::try_from(supply)
+            .map(AleoAmount)
+            .map_err(|_| AmountError::AmountOutOfBounds(supply.to_string(), i64::MAX.to_string()))
+    }
+
+    /// Returns the total amount that will ever be minted.
+    pub fn max_supply(&self) -> Result<AleoAmount, AmountError> {
+        self.total_supply(u32::MAX)
+    }
+
+    /// Returns the sum of the fees paid by the given transactions, which is
+    /// the sum of their non-negative value balances.
+    pub fn collected_fees<T: Transaction<ValueBalance = AleoAmount>>(
+        &self,
+        transactions: &[T],
+    ) -> Result<AleoAmount, AmountError> {
+        transactions
+            .iter()
+            .map(|transaction| transaction.value_balance())
+            .filter(|value_balance| !value_balance.is_negative())
+            .try_fold(AleoAmount::ZERO, |total, fee| {
+                total
+                    .checked_add(fee)
+                    .ok_or_else(|| AmountError::AmountOutOfBounds(fee.to_string(), i64::MAX.to_string()))
+            })
+    }
+
+    /// Checks that the coinbase transaction at the given height mints at most
+    /// the block reward plus the fees collected from the rest of the block.
+    pub fn validate_coinbase<T: Transaction<ValueBalance = AleoAmount>>(
+        &self,
+        height: u32,
+        coinbase: &T,
+        fees: AleoAmount,
+    ) -> Result<(), BlockError> {
+        let value_balance = coinbase.value_balance();
+        let minted = value_balance
+            .checked_neg()
+            .ok_or_else(|| AmountError::AmountOutOfBounds(value_balance.to_string(), i64::MAX.to_string()))?;
+        let allowed = self
+            .block_reward(height)
+            .checked_add(fees)
+            .ok_or_else(|| AmountError::AmountOutOfBounds(fees.to_string(), i64::MAX.to_string()))?;
+
+        if minted > allowed {
+            return Err(BlockError::InvalidCoinbaseValue(
+                minted.to_string_in(Denomination::ALEO),
+                allowed.to_string_in(Denomination::ALEO),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the given block transactions at the given height contain exactly one
+    /// coinbase transaction, and that it mints at most the block reward plus the collected fees.
+    pub fn validate_block_coinbase<T: Transaction<ValueBalance = AleoAmount>>(
+        &self,
+        height: u32,
+        transactions: &[T],
+    ) -> Result<(), BlockError> {
+        let coinbases: Vec<&T> = transactions
+            .iter()
+            .filter(|transaction| transaction.value_balance().is_negative())
+            .collect();
+
+        match coinbases.as_slice() {
+            [coinbase] => self.validate_coinbase(height, *coinbase, self.collected_fees(transactions)?),
+            _ => Err(BlockError::InvalidCoinbaseCount(coinbases.len())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> MonetaryPolicy {
+        MonetaryPolicy {
+            initial_block_reward: AleoAmount(1000),
+            halving_interval: 10,
+        }
+    }
+
+    #[test]
+    fn test_block_reward_schedule() {
+        let policy = policy();
+
+        // (height, expected block reward)
+        let vectors = [
+            (0, 1000),
+            (9, 1000),
+            (10, 500),
+            (19, 500),
+            (20, 250),
+            (35, 125),
+            (99, 1),
+            (100, 0),
+        ];
+
+        for (height, expected) in vectors.iter() {
+            assert_eq!(AleoAmount(*expected), policy.block_reward(*height));
+        }
+        assert_eq!(AleoAmount::ZERO, policy.block_reward(u32::MAX));
+    }
+
+    #[test]
+    fn test_total_supply() {
+        let policy = policy();
+
+        assert_eq!(AleoAmount(1000), policy.total_supply(0).unwrap());
+        assert_eq!(AleoAmount(10_000), policy.total_supply(9).unwrap());
+        assert_eq!(AleoAmount(10_500), policy.total_supply(10).unwrap());
+        assert_eq!(AleoAmount(15_000), policy.total_supply(19).unwrap());
+        // 10 * (1000 + 500 + 250 + 125 + 62 + 31 + 15 + 7 + 3 + 1)
+        assert_eq!(AleoAmount(19_940), policy.max_supply().unwrap());
+        assert_eq!(policy.max_supply().unwrap(), policy.total_supply(100).unwrap());
+    }
+
+    #[test]
+    fn test_default_max_supply() {
+        let policy = MonetaryPolicy::default();
+        let max_supply = policy.max_supply().unwrap();
+
+        assert_eq!(AleoAmount::from_aleo(150), policy.block_reward(0));
+        assert_eq!(AleoAmount::from_aleo(75), policy.block_reward(policy.halving_interval));
+        assert!(max_supply < AleoAmount::from_aleo(2 * 150 * policy.halving_interval as i64));
+        assert!(max_supply > AleoAmount::from_aleo(2 * 149 * policy.halving_interval as i64));
+    }
+
+    #[test]
+    fn test_supply_out_of_bounds() {
+        let policy = MonetaryPolicy {
+            initial_block_reward: AleoAmount(i64::MAX),
+            halving_interval: 10,
+        };
+
+        assert!(matches!(policy.max_supply(), Err(AmountError::AmountOutOfBounds(_, _))));
+    }
+}
@@ -38,6 +38,9 @@ pub trait LedgerScheme: Sized {
     /// Return the parameters used to construct the ledger Merkle tree.
     fn parameters(&self) -> &Self::MerkleParameters;
 
+    /// Returns the id of the network of the ledger, if it is known.
+    fn network_id(&self) -> Option<u8>;
+
     /// Return a digest of the latest ledger Merkle tree.
     fn digest(&self) -> Option<Self::MerkleTreeDigest>;
 
@@ -57,6 +60,10 @@ pub trait LedgerScheme: Sized {
     /// for a given commitment, if it exists in the ledger.
     fn prove_cm(&self, cm: &Self::Commitment) -> anyhow::Result<Self::MerklePath>;
 
+    /// Returns the Merkle path to the given past ledger digest
+    /// for a given commitment, if it existed in the ledger at that digest.
+    fn prove_cm_at(&self, cm: &Self::Commitment, digest: &Self::MerkleTreeDigest) -> anyhow::Result<Self::MerklePath>;
+
     /// Returns true if the given Merkle path is a valid witness for
     /// the given ledger digest and commitment.
     fn verify_cm(
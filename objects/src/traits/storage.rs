@@ -37,6 +37,9 @@ where
     /// Returns all the keys belonging to the given column.
     fn get_keys(&self, col: u32) -> Result<Vec<Box<[u8]>>, StorageError>;
 
+    /// Returns the keys belonging to the given column that start with the given prefix, in ascending order.
+    fn get_keys_with_prefix(&self, col: u32, prefix: &[u8]) -> Result<Vec<Box<[u8]>>, StorageError>;
+
     /// Stores the given key and value in the specified column.
     fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(&self, col: u32, key: K, value: V) -> Result<(), StorageError>;
 
@@ -60,6 +63,22 @@ pub enum Op {
     Delete { col: u32, key: Vec<u8> },
 }
 
+impl Op {
+    pub fn key(&self) -> &[u8] {
+        match self {
+            Op::Insert { key, .. } => &key,
+            Op::Delete { key, .. } => &key,
+        }
+    }
+
+    pub fn col(&self) -> u32 {
+        match self {
+            Op::Insert { col, .. } => *col,
+            Op::Delete { col, .. } => *col,
+        }
+    }
+}
+
 /// Batched transaction of database operations.
 #[derive(Default, Clone, PartialEq)]
 pub struct DatabaseTransaction(pub Vec<Op>);
@@ -70,6 +89,20 @@ impl DatabaseTransaction {
         Self(vec![])
     }
 
+    /// Add a key value pair under a specific col.
+    pub fn add(&mut self, col: u32, key: &[u8], value: &[u8]) {
+        self.0.push(Op::Insert {
+            col,
+            key: key.to_vec(),
+            value: value.to_vec(),
+        })
+    }
+
+    /// Delete a value given a col and key.
+    pub fn delete(&mut self, col: u32, key: &[u8]) {
+        self.0.push(Op::Delete { col, key: key.to_vec() })
+    }
+
     /// Add an operation.
     pub fn push(&mut self, op: Op) {
         self.0.push(op)
@@ -79,4 +112,9 @@ impl DatabaseTransaction {
     pub fn push_vec(&mut self, ops: Vec<Op>) {
         self.0.extend(ops)
     }
+
+    /// Add another database transaction.
+    pub fn extend(&mut self, database_transaction: DatabaseTransaction) {
+        self.0.extend(database_transaction.0)
+    }
 }
@@ -0,0 +1,211 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkvm_dpc::base_dpc::instantiated::Tx;
+use snarkvm_objects::{
+    merkle_root_with_subroots,
+    pedersen_merkle_root,
+    AleoAmount,
+    Block,
+    BlockError,
+    BlockValidator,
+    ConsensusParameters,
+    MerkleRootHash,
+    MonetaryPolicy,
+    MASKED_TREE_DEPTH,
+};
+use snarkvm_parameters::{traits::Genesis, GenesisBlock};
+
+/// Returns the genesis block with its header committing to its transactions,
+/// as the baked-in genesis header leaves both merkle roots zeroed.
+fn genesis_block() -> Block<Tx> {
+    let mut block: Block<Tx> = Block::deserialize(&GenesisBlock::load_bytes()).unwrap();
+
+    let transaction_ids = block.transactions.to_transaction_ids().unwrap();
+    let (root, subroots) = merkle_root_with_subroots(&transaction_ids, MASKED_TREE_DEPTH);
+    block.header.merkle_root_hash = MerkleRootHash(root);
+    block.header.pedersen_merkle_root_hash = pedersen_merkle_root(&subroots);
+
+    block
+}
+
+#[test]
+fn test_valid_genesis_block() {
+    let block = genesis_block();
+    let validator = BlockValidator::default();
+
+    validator.validate_block_at(&block, 0, block.header.time).unwrap();
+}
+
+#[test]
+fn test_invalid_merkle_roots() {
+    let validator = BlockValidator::default();
+
+    let mut block = genesis_block();
+    block.header.merkle_root_hash.0[0] ^= 1;
+    assert!(matches!(
+        validator.validate_block_at(&block, 0, block.header.time),
+        Err(BlockError::InvalidMerkleRoot(_, _))
+    ));
+
+    let mut block = genesis_block();
+    block.header.pedersen_merkle_root_hash.0[0] ^= 1;
+    assert!(matches!(
+        validator.validate_block_at(&block, 0, block.header.time),
+        Err(BlockError::InvalidPedersenMerkleRoot(_, _))
+    ));
+}
+
+#[test]
+fn test_timestamp_too_far_in_future() {
+    let block = genesis_block();
+    let validator = BlockValidator::default();
+    let max_future_block_time = validator.parameters().max_future_block_time;
+
+    assert!(validator
+        .check_timestamp(&block.header, block.header.time - max_future_block_time)
+        .is_ok());
+    assert!(matches!(
+        validator.validate_block_at(&block, 0, block.header.time - max_future_block_time - 1),
+        Err(BlockError::TimestampTooFarInFuture(_, _))
+    ));
+}
+
+#[test]
+fn test_block_too_large() {
+    let block = genesis_block();
+    let validator = BlockValidator::new(ConsensusParameters {
+        max_block_size: block.serialize().unwrap().len() - 1,
+        ..Default::default()
+    });
+
+    assert!(matches!(
+        validator.validate_block_at(&block, 0, block.header.time),
+        Err(BlockError::BlockTooLarge(_, _))
+    ));
+}
+
+#[test]
+fn test_invalid_coinbase() {
+    let block = genesis_block();
+    let coinbase = block.transactions[0].clone();
+    let minted = coinbase.value_balance.checked_neg().unwrap();
+
+    let validator = BlockValidator::default();
+    assert!(matches!(
+        validator.check_coinbase_reward::<Tx>(0, &[]),
+        Err(BlockError::InvalidCoinbaseCount(0))
+    ));
+    assert!(matches!(
+        validator.check_coinbase_reward(0, &[coinbase.clone(), coinbase]),
+        Err(BlockError::InvalidCoinbaseCount(2))
+    ));
+
+    let validator = BlockValidator::new(ConsensusParameters {
+        monetary_policy: MonetaryPolicy {
+            initial_block_reward: minted.sub(AleoAmount::ONE_BYTE),
+            halving_interval: 10,
+        },
+        ..Default::default()
+    });
+    assert!(matches!(
+        validator.validate_block_at(&block, 0, block.header.time),
+        Err(BlockError::InvalidCoinbaseValue(_, _))
+    ));
+
+    // The block reward depends on the height of the block.
+    let validator = BlockValidator::new(ConsensusParameters {
+        monetary_policy: MonetaryPolicy {
+            initial_block_reward: minted,
+            halving_interval: 10,
+        },
+        ..Default::default()
+    });
+    validator.validate_block_at(&block, 9, block.header.time).unwrap();
+    assert!(matches!(
+        validator.validate_block_at(&block, 10, block.header.time),
+        Err(BlockError::InvalidCoinbaseValue(_, _))
+    ));
+
+    // A value balance of i64::MIN has no positive counterpart.
+    let mut coinbase = block.transactions[0].clone();
+    coinbase.value_balance = AleoAmount(i64::MIN);
+    assert!(matches!(
+        BlockValidator::default().check_coinbase_reward(0, &[coinbase]),
+        Err(BlockError::AmountError(_))
+    ));
+}
+
+#[test]
+fn test_duplicate_transactions() {
+    let block = genesis_block();
+    let transaction = block.transactions[0].clone();
+    let validator = BlockValidator::default();
+
+    assert!(matches!(
+        validator.check_duplicates(&[transaction.clone(), transaction]),
+        Err(BlockError::DuplicateTransaction(_))
+    ));
+}
+
+#[test]
+fn test_empty_block() {
+    let mut block = genesis_block();
+    block.transactions.0.clear();
+    let validator = BlockValidator::default();
+
+    assert!(matches!(
+        validator.check_merkle_root(&block.header, &[]),
+        Err(BlockError::EmptyBlock)
+    ));
+    assert!(matches!(
+        validator.validate_block_at(&block, 0, block.header.time),
+        Err(BlockError::InvalidCoinbaseCount(0))
+    ));
+}
+
+#[test]
+fn test_coinbase_reward() {
+    let block = genesis_block();
+    let coinbase = block.transactions[0].clone();
+    let minted = coinbase.value_balance.checked_neg().unwrap();
+
+    let validator = BlockValidator::new(ConsensusParameters {
+        monetary_policy: MonetaryPolicy {
+            initial_block_reward: minted,
+            halving_interval: 10,
+        },
+        ..Default::default()
+    });
+    assert!(validator.check_coinbase_reward(0, &block.transactions).is_ok());
+    assert!(matches!(
+        validator.check_coinbase_reward(10, &block.transactions),
+        Err(BlockError::InvalidCoinbaseValue(_, _))
+    ));
+
+    // Fees collected from the rest of the block may be minted on top of the block reward.
+    let mut fee_transaction = coinbase.clone();
+    fee_transaction.value_balance = minted.sub(validator.parameters().monetary_policy.block_reward(10));
+    assert!(validator
+        .check_coinbase_reward(10, &[coinbase.clone(), fee_transaction.clone()])
+        .is_ok());
+
+    fee_transaction.value_balance = fee_transaction.value_balance.sub(AleoAmount::ONE_BYTE);
+    assert!(matches!(
+        validator.check_coinbase_reward(10, &[coinbase, fee_transaction]),
+        Err(BlockError::InvalidCoinbaseValue(_, _))
+    ));
+}
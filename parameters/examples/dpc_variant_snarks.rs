@@ -0,0 +1,154 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkvm_algorithms::{
+    crh::sha256::sha256,
+    traits::{MerkleParameters, CRH, SNARK},
+};
+use snarkvm_dpc::{
+    base_dpc::{
+        inner_circuit::InnerCircuit,
+        instantiated::ComponentsWithArity,
+        outer_circuit::OuterCircuit,
+        parameters::{NoopProgramSNARKParameters, SystemParameters},
+        program::{NoopCircuit, PrivateProgramInput},
+        BaseDPCComponents,
+    },
+    errors::DPCError,
+};
+use snarkvm_parameters::{traits::Parameter, LedgerMerkleTreeParameters};
+use snarkvm_utilities::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes,
+};
+
+use rand::thread_rng;
+use std::{env, path::PathBuf};
+
+mod utils;
+use utils::store;
+
+/// Generates the inner and outer SNARK parameters of the DPC with `C::NUM_INPUT_RECORDS` input records
+/// and `C::NUM_OUTPUT_RECORDS` output records.
+///
+/// The outer SNARK parameters depend on the inner SNARK verification key, so both are generated together,
+/// along with any CRH parameters that depend on the number of records.
+pub fn setup<C: BaseDPCComponents>() -> Result<Vec<(&'static str, Vec<u8>)>, DPCError> {
+    let rng = &mut thread_rng();
+    let mut system_parameters = SystemParameters::<C>::load_unchecked()?;
+    let mut parameters = vec![];
+
+    // The serial number nonce CRH and the inner SNARK verification key CRH grow with the number of records,
+    // so variants which exceed the default windows require their own CRH parameters.
+    if C::NUM_INPUT_RECORDS > 3 {
+        system_parameters.serial_number_nonce = C::SerialNumberNonceCRH::setup(rng);
+        parameters.push(("serial_number_nonce_crh", to_bytes![system_parameters
+            .serial_number_nonce
+            .parameters()]?));
+    }
+    if C::NUM_INPUT_RECORDS + C::NUM_OUTPUT_RECORDS > 4 {
+        system_parameters.inner_snark_verification_key_crh = C::InnerSNARKVerificationKeyCRH::setup(rng);
+        parameters.push(("inner_snark_vk_crh", to_bytes![system_parameters
+            .inner_snark_verification_key_crh
+            .parameters()]?));
+    }
+
+    system_parameters.check_arity()?;
+
+    let merkle_tree_hash_parameters: <C::MerkleParameters as MerkleParameters>::H =
+        From::from(FromBytes::read(&LedgerMerkleTreeParameters::load_bytes()?[..])?);
+    let ledger_merkle_tree_parameters = From::from(merkle_tree_hash_parameters);
+
+    let inner_circuit = InnerCircuit::blank(&system_parameters, &ledger_merkle_tree_parameters);
+    let (inner_snark_pk, inner_snark_pvk) = C::InnerSNARK::setup(&inner_circuit, rng)?;
+    let inner_snark_vk: <C::InnerSNARK as SNARK>::VerificationParameters = inner_snark_pvk.into();
+    let inner_snark_proof = C::InnerSNARK::prove(&inner_snark_pk, &inner_circuit, rng)?;
+
+    let noop_program_snark_parameters = NoopProgramSNARKParameters::<C>::load()?;
+    let program_snark_proof = C::NoopProgramSNARK::prove(
+        &noop_program_snark_parameters.proving_key,
+        &NoopCircuit::blank(&system_parameters),
+        rng,
+    )?;
+    let private_program_input = PrivateProgramInput {
+        verification_key: to_bytes![noop_program_snark_parameters.verification_key]?,
+        proof: to_bytes![program_snark_proof]?,
+    };
+
+    let (outer_snark_pk, outer_snark_pvk) = C::OuterSNARK::setup(
+        &OuterCircuit::blank(
+            system_parameters,
+            ledger_merkle_tree_parameters,
+            inner_snark_vk.clone(),
+            inner_snark_proof,
+            private_program_input,
+        ),
+        rng,
+    )?;
+    let outer_snark_vk: <C::OuterSNARK as SNARK>::VerificationParameters = outer_snark_pvk.into();
+
+    parameters.extend(vec![
+        ("inner_snark_pk", to_bytes![inner_snark_pk]?),
+        ("inner_snark_vk", to_bytes![inner_snark_vk]?),
+        ("outer_snark_pk", to_bytes![outer_snark_pk]?),
+        ("outer_snark_vk", to_bytes![outer_snark_vk]?),
+    ]);
+
+    for (name, bytes) in &parameters {
+        println!("{}.params\n\tsize - {}", name, bytes.len());
+    }
+    Ok(parameters)
+}
+
+fn versioned_filename(name: &str, variant: &str, checksum: &str) -> String {
+    match checksum.get(0..7) {
+        Some(sum) => format!("{}_{}-{}.params", name, variant, sum),
+        _ => format!("{}_{}.params", name, variant),
+    }
+}
+
+fn store_variant<C: BaseDPCComponents>() {
+    let variant = format!("{}x{}", C::NUM_INPUT_RECORDS, C::NUM_OUTPUT_RECORDS);
+
+    for (name, bytes) in setup::<C>().unwrap() {
+        let checksum = hex::encode(sha256(&bytes));
+        store(
+            &PathBuf::from(&versioned_filename(name, &variant, &checksum)),
+            &PathBuf::from(format!("{}_{}.checksum", name, variant)),
+            &bytes,
+        )
+        .unwrap();
+    }
+}
+
+/// Usage: `cargo run --release --example dpc_variant_snarks -- <NUM_INPUT_RECORDS> <NUM_OUTPUT_RECORDS>`
+pub fn main() {
+    let args: Vec<String> = env::args().collect();
+    let (num_inputs, num_outputs) = match &args[1..] {
+        [num_inputs, num_outputs] => (num_inputs.as_str(), num_outputs.as_str()),
+        _ => panic!("Usage: dpc_variant_snarks <NUM_INPUT_RECORDS> <NUM_OUTPUT_RECORDS>"),
+    };
+
+    match (num_inputs, num_outputs) {
+        ("1", "1") => store_variant::<ComponentsWithArity<1, 1>>(),
+        ("2", "2") => store_variant::<ComponentsWithArity<2, 2>>(),
+        ("4", "2") => store_variant::<ComponentsWithArity<4, 2>>(),
+        _ => panic!(
+            "Unsupported DPC variant: {} inputs and {} outputs",
+            num_inputs, num_outputs
+        ),
+    }
+}
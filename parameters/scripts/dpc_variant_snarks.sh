@@ -0,0 +1,5 @@
+# This script will run the inner and outer SNARK setup for a DPC variant with the given
+# number of input and output records, e.g. `./dpc_variant_snarks.sh 4 2`.
+# The resulting `.params` and `.checksum` files are left in the current directory.
+
+cargo run --release --example dpc_variant_snarks -- $1 $2
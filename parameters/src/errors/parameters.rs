@@ -24,11 +24,17 @@ pub enum ParameterError {
     #[error("{}: {}", _0, _1)]
     Crate(&'static str, String),
 
+    #[error("invalid parameter mirror {}, expected a file:// URL or a local directory", _0)]
+    InvalidMirror(String),
+
     #[error("{}", _0)]
     Message(String),
 
     #[error("Remote fetch is disabled, enable compiler flag for feature")]
     RemoteFetchDisabled,
+
+    #[error("expected size of {} bytes, found size of {} bytes", _0, _1)]
+    SizeMismatch(u64, u64),
 }
 
 #[cfg(any(test, feature = "remote"))]
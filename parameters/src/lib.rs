@@ -28,5 +28,8 @@ pub use genesis::*;
 pub mod params;
 pub use params::*;
 
+pub mod resolver;
+pub use resolver::*;
+
 pub mod traits;
 pub use traits::*;
@@ -14,17 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{errors::ParameterError, traits::Parameter};
+use crate::{errors::ParameterError, traits::Parameter, ParameterResolver, ParameterSource};
 use snarkvm_algorithms::crh::sha256::sha256;
 
-use std::{
-    fs::{
-        File,
-        {self},
-    },
-    io::Write,
-    path::{Path, PathBuf},
-};
+use std::path::PathBuf;
 
 #[cfg(any(test, feature = "remote"))]
 use curl::easy::Easy;
@@ -70,58 +63,66 @@ macro_rules! impl_params_remote {
             const SIZE: u64 = $size;
 
             fn load_bytes() -> Result<Vec<u8>, ParameterError> {
-                // Compose the correct file path for the parameter file.
+                Ok(Self::load_bytes_with_source()?.0)
+            }
+        }
+
+        impl $name {
+            /// Loads the parameters with the current `ParameterResolver`,
+            /// and returns the location they were loaded from.
+            pub fn load_bytes_with_source() -> Result<(Vec<u8>, ParameterSource), ParameterError> {
+                Self::load_bytes_with_resolver(&ParameterResolver::current())
+            }
+
+            /// Loads the parameters with the given resolver, and returns the location they were loaded from.
+            /// If the parameters are downloaded, they are stored in the first directory of the resolver,
+            /// or else in the `params` directory of this crate.
+            pub fn load_bytes_with_resolver(
+                resolver: &ParameterResolver,
+            ) -> Result<(Vec<u8>, ParameterSource), ParameterError> {
+                // Compose the correct directory path for the parameter file.
                 let filename = Self::versioned_filename();
-                let mut file_path = PathBuf::from(file!());
-                file_path.pop();
-                file_path.push("params/");
-                file_path.push(&filename);
+                let mut directory = PathBuf::from(file!());
+                directory.pop();
+                directory.push("params/");
 
                 // Compute the relative path.
-                let relative_path = if file_path.strip_prefix("parameters").is_ok() {
-                    file_path.strip_prefix("parameters")?
+                let relative_directory = if directory.strip_prefix("parameters").is_ok() {
+                    directory.strip_prefix("parameters")?.to_path_buf()
                 } else {
-                    &file_path
+                    directory.clone()
                 };
 
                 // Compute the absolute path.
-                let mut absolute_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-                absolute_path.push(&relative_path);
-
-                let buffer = if relative_path.exists() {
-                    // Attempts to load the parameter file locally with a relative path.
-                    fs::read(relative_path)?
-                } else if absolute_path.exists() {
-                    // Attempts to load the parameter file locally with an absolute path.
-                    fs::read(absolute_path)?
-                } else {
-                    // Downloads the missing parameters and stores it in the local directory for use.
-                    eprintln!(
-                        "\nWARNING - \"{}\" does not exist. snarkVM will download this file remotely and store it locally. Please ensure \"{}\" is stored in {:?}.\n",
-                        filename, filename, file_path
-                    );
-                    let output = Self::load_remote()?;
-                    match Self::store_bytes(&output, &relative_path, &absolute_path, &file_path) {
-                        Ok(()) => output,
-                        Err(_) => {
-                            eprintln!(
-                                "\nWARNING - Failed to store \"{}\" locally. Please download this file manually and ensure it is stored in {:?}.\n",
-                                filename, file_path
-                            );
-                            output
-                        }
+                let mut absolute_directory = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+                absolute_directory.push(&relative_directory);
+
+                let default_directories = [relative_directory, absolute_directory];
+                let storage_directory = resolver.directories().first().unwrap_or(&directory);
+                let (buffer, source) =
+                    resolver.resolve(&filename, Self::CHECKSUM, Self::SIZE, &default_directories, || {
+                        // Downloads the missing parameters.
+                        eprintln!(
+                            "\nWARNING - \"{}\" does not exist. snarkVM will download this file remotely and store it locally. Please ensure \"{}\" is stored in {:?}.\n",
+                            filename, filename, storage_directory
+                        );
+                        Self::fetch_remote()
+                    })?;
+
+                // Stores the downloaded parameters in the local directory for use.
+                if let ParameterSource::Remote(_) = source {
+                    match resolver.store(&filename, &buffer, &default_directories) {
+                        Ok(file_path) => println!("{} - Stored parameters ({:?})", module_path!(), file_path),
+                        Err(_) => eprintln!(
+                            "\nWARNING - Failed to store \"{}\" locally. Please download this file manually and ensure it is stored in {:?}.\n",
+                            filename, storage_directory.join(&filename)
+                        ),
                     }
-                };
-
-                let checksum = hex::encode(sha256(&buffer));
-                match Self::CHECKSUM == checksum {
-                    true => Ok(buffer),
-                    false => Err(ParameterError::ChecksumMismatch(Self::CHECKSUM.into(), checksum)),
                 }
+
+                Ok((buffer, source))
             }
-        }
 
-        impl $name {
             #[cfg(any(test, feature = "remote"))]
             pub fn load_remote() -> Result<Vec<u8>, ParameterError> {
                 println!("{} - Downloading parameters...", module_path!());
@@ -143,6 +144,16 @@ macro_rules! impl_params_remote {
                 Err(ParameterError::RemoteFetchDisabled)
             }
 
+            #[cfg(any(test, feature = "remote"))]
+            fn fetch_remote() -> Result<(Vec<u8>, String), ParameterError> {
+                Ok((Self::load_remote()?, Self::remote_url()))
+            }
+
+            #[cfg(not(any(test, feature = "remote")))]
+            fn fetch_remote() -> Result<(Vec<u8>, String), ParameterError> {
+                Err(ParameterError::RemoteFetchDisabled)
+            }
+
             fn versioned_filename() -> String {
                 match Self::CHECKSUM.get(0..7) {
                     Some(sum) => format!("{}-{}.params", $fname, sum),
@@ -155,22 +166,6 @@ macro_rules! impl_params_remote {
                 format!("{}/{}", REMOTE_URL, Self::versioned_filename())
             }
 
-            fn store_bytes(
-                buffer: &[u8],
-                relative_path: &Path,
-                absolute_path: &Path,
-                file_path: &Path,
-            ) -> Result<(), ParameterError> {
-                println!("{} - Storing parameters ({:?})", module_path!(), file_path);
-                // Attempt to write the parameter buffer to a file.
-                if let Ok(mut file) = File::create(relative_path) {
-                    file.write_all(&buffer)?;
-                } else if let Ok(mut file) = File::create(absolute_path) {
-                    file.write_all(&buffer)?;
-                }
-                Ok(())
-            }
-
             #[cfg(any(test, feature = "remote"))]
             fn remote_fetch(buffer: &mut Vec<u8>, url: &str) -> Result<(), ParameterError> {
                 let mut easy = Easy::new();
@@ -0,0 +1,436 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::errors::ParameterError;
+use snarkvm_algorithms::crh::sha256::sha256;
+
+use once_cell::sync::Lazy;
+use std::{
+    env,
+    fmt,
+    fs,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+/// The environment variable listing additional directories to search for parameter files,
+/// separated in the same way as the `PATH` environment variable.
+pub const PARAMETERS_PATH_ENV: &str = "SNARKVM_PARAMETERS_PATH";
+
+/// The environment variable holding a `file://` URL or a local directory
+/// that mirrors the remote parameter files.
+pub const PARAMETERS_MIRROR_ENV: &str = "SNARKVM_PARAMETERS_MIRROR";
+
+/// The resolver installed with `ParameterResolver::install`.
+static INSTALLED_RESOLVER: Lazy<RwLock<Option<ParameterResolver>>> = Lazy::new(|| RwLock::new(None));
+
+/// The location a parameter file was loaded from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParameterSource {
+    /// A file in one of the searched directories.
+    Directory(PathBuf),
+    /// A file in the local mirror.
+    Mirror(PathBuf),
+    /// A file downloaded from the given URL.
+    Remote(String),
+}
+
+impl fmt::Display for ParameterSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParameterSource::Directory(path) => write!(f, "directory {}", path.display()),
+            ParameterSource::Mirror(path) => write!(f, "mirror {}", path.display()),
+            ParameterSource::Remote(url) => write!(f, "remote {}", url),
+        }
+    }
+}
+
+/// Finds parameter files that are too large to be included in the crate.
+///
+/// A parameter file is loaded from the first of these locations that holds a file of the
+/// expected size and checksum:
+/// 1. the directories added to the resolver, in order,
+/// 2. the directories in `SNARKVM_PARAMETERS_PATH`, if the resolver is read from the environment,
+/// 3. the default directories of the parameter, such as the `params` directory of this crate,
+/// 4. the mirror of the resolver, or else the mirror in `SNARKVM_PARAMETERS_MIRROR`,
+/// 5. the remote, if the `remote` feature is enabled.
+///
+/// Downloaded files are stored in the first directory of the resolver, if it has any.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParameterResolver {
+    directories: Vec<PathBuf>,
+    mirror: Option<PathBuf>,
+}
+
+impl ParameterResolver {
+    /// Creates a resolver without directories or a mirror.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a resolver from `SNARKVM_PARAMETERS_PATH` and `SNARKVM_PARAMETERS_MIRROR`.
+    /// An invalid mirror is ignored with a warning.
+    pub fn from_env() -> Self {
+        Self::default().with_env()
+    }
+
+    /// Returns the installed resolver, extended with the directories and mirror of the environment.
+    pub fn current() -> Self {
+        let installed = INSTALLED_RESOLVER
+            .read()
+            .expect("the parameter resolver lock is poisoned");
+        installed.clone().unwrap_or_default().with_env()
+    }
+
+    /// Installs the resolver used to load parameters.
+    pub fn install(self) {
+        *INSTALLED_RESOLVER
+            .write()
+            .expect("the parameter resolver lock is poisoned") = Some(self);
+    }
+
+    /// Adds a directory to search for parameter files.
+    pub fn add_directory<P: Into<PathBuf>>(mut self, directory: P) -> Self {
+        self.directories.push(directory.into());
+        self
+    }
+
+    /// Sets the mirror, given as a `file://` URL or a local directory.
+    pub fn mirror(mut self, mirror: &str) -> Result<Self, ParameterError> {
+        self.mirror = Some(parse_mirror(mirror)?);
+        Ok(self)
+    }
+
+    /// Returns the directories searched for parameter files.
+    pub fn directories(&self) -> &[PathBuf] {
+        &self.directories
+    }
+
+    /// Returns the directory of the mirror, if one is set.
+    pub fn mirror_directory(&self) -> Option<&Path> {
+        self.mirror.as_deref()
+    }
+
+    /// Loads the first file with the given name whose size and checksum match, searching the
+    /// resolver directories, then `default_directories`, then the mirror, and finally calling
+    /// `remote`, which returns the downloaded bytes and their URL.
+    pub fn resolve<F>(
+        &self,
+        filename: &str,
+        checksum: &str,
+        size: u64,
+        default_directories: &[PathBuf],
+        remote: F,
+    ) -> Result<(Vec<u8>, ParameterSource), ParameterError>
+    where
+        F: FnOnce() -> Result<(Vec<u8>, String), ParameterError>,
+    {
+        let local_candidates = self
+            .directories
+            .iter()
+            .chain(default_directories)
+            .map(|directory| ParameterSource::Directory(directory.join(filename)));
+        let mirror_candidates = self
+            .mirror
+            .iter()
+            .map(|mirror| ParameterSource::Mirror(mirror.join(filename)));
+
+        let mut error = None;
+        for source in local_candidates.chain(mirror_candidates) {
+            let path = match &source {
+                ParameterSource::Directory(path) | ParameterSource::Mirror(path) => path,
+                ParameterSource::Remote(_) => unreachable!(),
+            };
+            if !path.is_file() {
+                continue;
+            }
+
+            match read_verified(path, checksum, size) {
+                Ok(buffer) => return Ok((buffer, source)),
+                Err(err) => {
+                    eprintln!("\nWARNING - Skipping parameters in {}: {}\n", source, err);
+                    error = Some(err);
+                }
+            }
+        }
+
+        let (buffer, url) = match remote() {
+            Ok(remote) => remote,
+            Err(err) => return Err(error.unwrap_or(err)),
+        };
+        verify(&buffer, checksum, size)?;
+
+        Ok((buffer, ParameterSource::Remote(url)))
+    }
+
+    /// Stores downloaded parameters in the first resolver directory, creating it if needed, or
+    /// else in the first of `default_directories` the file can be created in. Returns the path
+    /// of the stored file.
+    pub fn store(
+        &self,
+        filename: &str,
+        buffer: &[u8],
+        default_directories: &[PathBuf],
+    ) -> Result<PathBuf, ParameterError> {
+        if let Some(directory) = self.directories.first() {
+            fs::create_dir_all(directory)?;
+            let path = directory.join(filename);
+            fs::write(&path, buffer)?;
+            return Ok(path);
+        }
+
+        let mut error = None;
+        for directory in default_directories {
+            let path = directory.join(filename);
+            match fs::write(&path, buffer) {
+                Ok(()) => return Ok(path),
+                Err(err) => error = Some(err),
+            }
+        }
+
+        Err(match error {
+            Some(err) => err.into(),
+            None => ParameterError::Message(format!("no directory to store {} in", filename)),
+        })
+    }
+
+    /// Appends the directories of `SNARKVM_PARAMETERS_PATH`, and uses the mirror
+    /// of `SNARKVM_PARAMETERS_MIRROR` if no mirror is set.
+    fn with_env(mut self) -> Self {
+        if let Some(paths) = env::var_os(PARAMETERS_PATH_ENV) {
+            self.directories
+                .extend(env::split_paths(&paths).filter(|path| !path.as_os_str().is_empty()));
+        }
+
+        if self.mirror.is_none() {
+            if let Ok(mirror) = env::var(PARAMETERS_MIRROR_ENV) {
+                match parse_mirror(&mirror) {
+                    Ok(mirror) => self.mirror = Some(mirror),
+                    Err(err) => eprintln!("\nWARNING - Ignoring {}: {}\n", PARAMETERS_MIRROR_ENV, err),
+                }
+            }
+        }
+
+        self
+    }
+}
+
+/// Returns the directory of a mirror given as a `file://` URL or a local directory.
+fn parse_mirror(mirror: &str) -> Result<PathBuf, ParameterError> {
+    let path = match mirror.strip_prefix("file://") {
+        Some(path) => path,
+        None if mirror.contains("://") => {
+            return Err(ParameterError::InvalidMirror(mirror.to_string()));
+        }
+        None => mirror,
+    };
+
+    match path.is_empty() {
+        true => Err(ParameterError::InvalidMirror(mirror.to_string())),
+        false => Ok(PathBuf::from(path)),
+    }
+}
+
+/// Reads the file at the given path, if it has the expected size and checksum.
+fn read_verified(path: &Path, checksum: &str, size: u64) -> Result<Vec<u8>, ParameterError> {
+    // Check the size first, to avoid reading large files that can't match.
+    let file_size = fs::metadata(path)?.len();
+    if file_size != size {
+        return Err(ParameterError::SizeMismatch(size, file_size));
+    }
+
+    let buffer = fs::read(path)?;
+    verify(&buffer, checksum, size)?;
+    Ok(buffer)
+}
+
+/// Checks that the given bytes have the expected size and checksum.
+fn verify(buffer: &[u8], checksum: &str, size: u64) -> Result<(), ParameterError> {
+    if buffer.len() as u64 != size {
+        return Err(ParameterError::SizeMismatch(size, buffer.len() as u64));
+    }
+
+    let buffer_checksum = hex::encode(sha256(buffer));
+    match checksum == buffer_checksum {
+        true => Ok(()),
+        false => Err(ParameterError::ChecksumMismatch(checksum.into(), buffer_checksum)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{thread_rng, Rng};
+
+    const FILENAME: &str = "test_parameters.params";
+
+    fn test_directory() -> PathBuf {
+        let mut path = env::temp_dir();
+        path.push(format!("test_params-{}", thread_rng().gen::<usize>()));
+        fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    fn test_parameters() -> (Vec<u8>, String) {
+        let buffer: Vec<u8> = (0..1024).map(|_| thread_rng().gen()).collect();
+        let checksum = hex::encode(sha256(&buffer));
+        (buffer, checksum)
+    }
+
+    fn no_remote() -> Result<(Vec<u8>, String), ParameterError> {
+        Err(ParameterError::RemoteFetchDisabled)
+    }
+
+    #[test]
+    fn test_resolve_order() {
+        let (buffer, checksum) = test_parameters();
+        let size = buffer.len() as u64;
+
+        let [first, second, default, mirror] = [test_directory(), test_directory(), test_directory(), test_directory()];
+        fs::write(second.join(FILENAME), &buffer).unwrap();
+        fs::write(default.join(FILENAME), &buffer).unwrap();
+        fs::write(mirror.join(FILENAME), &buffer).unwrap();
+
+        let resolver = ParameterResolver::new()
+            .add_directory(&first)
+            .add_directory(&second)
+            .mirror(&format!("file://{}", mirror.display()))
+            .unwrap();
+        let resolve = |defaults: &[PathBuf]| {
+            resolver
+                .resolve(FILENAME, &checksum, size, defaults, no_remote)
+                .unwrap()
+        };
+
+        // The resolver directories are searched before the default directories.
+        let (loaded, source) = resolve(std::slice::from_ref(&default));
+        assert_eq!(loaded, buffer);
+        assert_eq!(source, ParameterSource::Directory(second.join(FILENAME)));
+
+        // The default directories are searched before the mirror.
+        fs::remove_file(second.join(FILENAME)).unwrap();
+        assert_eq!(
+            resolve(std::slice::from_ref(&default)).1,
+            ParameterSource::Directory(default.join(FILENAME))
+        );
+
+        // The mirror is used when no directory has the file.
+        assert_eq!(resolve(&[]).1, ParameterSource::Mirror(mirror.join(FILENAME)));
+
+        // The remote is used when no other location has the file.
+        fs::remove_file(mirror.join(FILENAME)).unwrap();
+        let remote = || Ok((buffer.clone(), "https://example.com/test".to_string()));
+        let (loaded, source) = resolver.resolve(FILENAME, &checksum, size, &[], remote).unwrap();
+        assert_eq!(loaded, buffer);
+        assert_eq!(source, ParameterSource::Remote("https://example.com/test".into()));
+
+        for directory in [first, second, default, mirror].iter() {
+            fs::remove_dir_all(directory).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_resolve_skips_invalid_files() {
+        let (buffer, checksum) = test_parameters();
+        let size = buffer.len() as u64;
+
+        let [truncated, corrupted, valid] = [test_directory(), test_directory(), test_directory()];
+        fs::write(truncated.join(FILENAME), &buffer[1..]).unwrap();
+        let mut corrupted_buffer = buffer.clone();
+        corrupted_buffer[0] ^= 1;
+        fs::write(corrupted.join(FILENAME), &corrupted_buffer).unwrap();
+
+        let resolver = ParameterResolver::new()
+            .add_directory(&truncated)
+            .add_directory(&corrupted);
+
+        // Without a valid file, the last verification error is returned.
+        assert!(matches!(
+            resolver.resolve(FILENAME, &checksum, size, &[], no_remote),
+            Err(ParameterError::ChecksumMismatch(_, _))
+        ));
+        assert!(matches!(
+            ParameterResolver::new()
+                .add_directory(&truncated)
+                .resolve(FILENAME, &checksum, size, &[], no_remote),
+            Err(ParameterError::SizeMismatch(_, _))
+        ));
+
+        // A valid file in a later directory is used.
+        fs::write(valid.join(FILENAME), &buffer).unwrap();
+        let resolver = resolver.add_directory(&valid);
+        let (loaded, source) = resolver.resolve(FILENAME, &checksum, size, &[], no_remote).unwrap();
+        assert_eq!(loaded, buffer);
+        assert_eq!(source, ParameterSource::Directory(valid.join(FILENAME)));
+
+        // A remote download is verified too.
+        let remote = || Ok((corrupted_buffer.clone(), "https://example.com/test".to_string()));
+        assert!(matches!(
+            ParameterResolver::new().resolve(FILENAME, &checksum, size, &[], remote),
+            Err(ParameterError::ChecksumMismatch(_, _))
+        ));
+
+        for directory in [truncated, corrupted, valid].iter() {
+            fs::remove_dir_all(directory).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_store() {
+        let (buffer, _) = test_parameters();
+        let [configured, default] = [test_directory(), test_directory()];
+        let missing = configured.join("missing");
+
+        // Without resolver directories, the parameters are stored in the first default directory.
+        let path = ParameterResolver::new()
+            .store(FILENAME, &buffer, &[missing.clone(), default.clone()])
+            .unwrap();
+        assert_eq!(path, default.join(FILENAME));
+        assert_eq!(fs::read(&path).unwrap(), buffer);
+        fs::remove_file(&path).unwrap();
+
+        // Otherwise, they are stored in the first resolver directory, which is created if needed.
+        let resolver = ParameterResolver::new().add_directory(&missing).add_directory(&configured);
+        let path = resolver.store(FILENAME, &buffer, std::slice::from_ref(&default)).unwrap();
+        assert_eq!(path, missing.join(FILENAME));
+        assert_eq!(fs::read(&path).unwrap(), buffer);
+        assert!(!default.join(FILENAME).exists());
+
+        assert!(ParameterResolver::new().store(FILENAME, &buffer, &[]).is_err());
+
+        for directory in [configured, default].iter() {
+            fs::remove_dir_all(directory).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_parse_mirror() {
+        assert_eq!(
+            parse_mirror("file:///srv/params").unwrap(),
+            PathBuf::from("/srv/params")
+        );
+        assert_eq!(parse_mirror("/srv/params").unwrap(), PathBuf::from("/srv/params"));
+        assert_eq!(parse_mirror("params").unwrap(), PathBuf::from("params"));
+
+        assert!(matches!(
+            parse_mirror("https://example.com/params"),
+            Err(ParameterError::InvalidMirror(_))
+        ));
+        assert!(matches!(parse_mirror("file://"), Err(ParameterError::InvalidMirror(_))));
+        assert!(matches!(parse_mirror(""), Err(ParameterError::InvalidMirror(_))));
+    }
+}
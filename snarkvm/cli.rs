@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
+use std::path::PathBuf;
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
@@ -33,6 +34,9 @@ pub struct CLI {
 
 #[derive(StructOpt, Debug)]
 pub enum Command {
+    /// Manage a ledger
+    Ledger(LedgerCommand),
+
     /// Update snarkVM to the latest version
     Update {
         /// Lists all available versions of snarkVM
@@ -44,3 +48,17 @@ pub enum Command {
         quiet: bool,
     },
 }
+
+#[derive(StructOpt, Debug)]
+pub enum LedgerCommand {
+    /// Check the integrity of a ledger
+    Check {
+        /// The path to the ledger storage
+        #[structopt(parse(from_os_str))]
+        path: PathBuf,
+
+        /// Repair the inconsistent derived values of the ledger
+        #[structopt(short = "r", long)]
+        repair: bool,
+    },
+}
@@ -14,10 +14,18 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{cli::Command, updater::Updater};
+use crate::{
+    cli::{Command, LedgerCommand},
+    updater::Updater,
+};
+use snarkvm_dpc::base_dpc::instantiated::{CommitmentMerkleParameters, Tx};
+use snarkvm_storage::{Ledger, RocksDb};
+
+use std::path::Path;
 
 pub fn parse(command: Command) -> anyhow::Result<String> {
     match command {
+        Command::Ledger(LedgerCommand::Check { path, repair }) => check_ledger(&path, repair),
         Command::Update { list, quiet } => match list {
             true => match Updater::show_available_releases() {
                 Ok(output) => Ok(output),
@@ -45,3 +53,85 @@ pub fn parse(command: Command) -> anyhow::Result<String> {
         }, // _ => Err(anyhow!("\nUnknown command\n")),
     }
 }
+
+/// Checks the integrity of the ledger at the given path, and optionally repairs it.
+fn check_ledger(path: &Path, repair: bool) -> anyhow::Result<String> {
+    // Opening a ledger at a new path would create one.
+    if !path.exists() {
+        return Err(anyhow!("\nThere is no ledger at {}\n", path.display()));
+    }
+
+    // A plain check opens the ledger as a secondary instance, so that it doesn't write to the
+    // ledger and can run while a node holds the primary instance.
+    let ledger = match repair {
+        true => Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path(path)?,
+        false => Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_secondary_at_path(path)?,
+    };
+    let inconsistencies = match repair {
+        true => ledger.repair_integrity()?,
+        false => ledger.check_integrity()?,
+    };
+
+    if inconsistencies.is_empty() {
+        return Ok(format!("\nThe ledger at {} is consistent", path.display()));
+    }
+
+    let mut output = format!(
+        "\nFound {} inconsistencies in the ledger at {}\n",
+        inconsistencies.len(),
+        path.display()
+    );
+    for inconsistency in inconsistencies {
+        let status = match (inconsistency.is_repairable(), repair) {
+            (true, true) => "repaired",
+            (true, false) => "repairable",
+            (false, _) => "not repairable",
+        };
+        output += &format!("  - {} ({})\n", inconsistency, status);
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_objects::Storage;
+    use snarkvm_storage::{COL_META, KEY_CURR_CM_INDEX, NUM_COLS};
+    use snarkvm_testing::storage::random_storage_path;
+
+    #[test]
+    fn test_check_ledger_is_read_only() {
+        let mut path = std::env::temp_dir();
+        path.push(random_storage_path());
+
+        // Corrupt a repairable value.
+        let ledger = Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path(&path).unwrap();
+        ledger
+            .storage
+            .put(COL_META, KEY_CURR_CM_INDEX.as_bytes(), &u32::MAX.to_le_bytes())
+            .unwrap();
+        drop(ledger);
+
+        // Reopening the ledger flushes the corrupted value to the files that a secondary instance
+        // reads. The primary instance stays open, as it would in a running node.
+        let ledger = Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path(&path).unwrap();
+
+        let columns = |ledger: &Ledger<Tx, CommitmentMerkleParameters, RocksDb>| {
+            (0..NUM_COLS)
+                .map(|col| ledger.storage.get_col(col).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let expected = columns(&ledger);
+
+        let output = check_ledger(&path, false).unwrap();
+        assert!(output.contains("(repairable)"));
+        assert_eq!(columns(&ledger), expected);
+
+        drop(ledger);
+        let mut secondary_path = path.clone().into_os_string();
+        secondary_path.push("_secondary");
+        std::fs::remove_dir_all(secondary_path).unwrap();
+        Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::destroy_storage(path).unwrap();
+    }
+}
@@ -14,10 +14,22 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
+pub use snarkvm_objects::{DatabaseTransaction, Op};
 use snarkvm_utilities::bytes::{FromBytes, ToBytes};
 
 use std::io::{Read, Result as IoResult, Write};
 
+/// The version of the layout of the ledger storage. Ledgers with an older schema version
+/// are migrated when opened, and ledgers with a newer schema version are refused.
+///
+/// - 1: the layout that predates the schema versions.
+/// - 2: each ledger digest stores its commitment index, used by `prove_cm_at`,
+///   and the commitment merkle tree frontier is stored.
+/// - 3: the memory pool transactions are stored individually in `COL_MEMORY_POOL`.
+/// - 4: the stored records are indexed by owner and by birth and death program id.
+/// - 5: the network id of the genesis transactions is stored.
+pub const SCHEMA_VERSION: u32 = 5;
+
 pub const COL_META: u32 = 0; // MISC Values
 pub const COL_BLOCK_HEADER: u32 = 1; // Block hash -> block header
 pub const COL_BLOCK_TRANSACTIONS: u32 = 2; // Block hash -> block transactions
@@ -26,12 +38,19 @@ pub const COL_TRANSACTION_LOCATION: u32 = 4; // Transaction Hash -> (block hash
 pub const COL_COMMITMENT: u32 = 5; // Commitment -> index
 pub const COL_SERIAL_NUMBER: u32 = 6; // SN -> index
 pub const COL_MEMO: u32 = 7; // Memo -> index
-pub const COL_DIGEST: u32 = 8; // Ledger digest -> index
+pub const COL_DIGEST: u32 = 8; // Ledger digest -> block num && commitment index
 pub const COL_RECORDS: u32 = 9; // commitment -> record bytes
 pub const COL_CHILD_HASHES: u32 = 10; // block hash -> vector of potential child hashes
-pub const NUM_COLS: u32 = 11;
-
+pub const COL_RECORDS_BY_OWNER: u32 = 11; // owner && commitment -> ()
+pub const COL_RECORDS_BY_BIRTH_PROGRAM_ID: u32 = 12; // birth program id && commitment -> ()
+pub const COL_RECORDS_BY_DEATH_PROGRAM_ID: u32 = 13; // death program id && commitment -> ()
+pub const COL_RECORD_SERIAL_NUMBER: u32 = 14; // commitment -> serial number
+pub const COL_MEMORY_POOL: u32 = 15; // transaction id -> transaction bytes
+pub const NUM_COLS: u32 = 16;
+
+pub const KEY_SCHEMA_VERSION: &str = "SCHEMA_VERSION";
 pub const KEY_BEST_BLOCK_NUMBER: &str = "BEST_BLOCK_NUMBER";
+/// The memory pool as opaque bytes, which is replaced by `COL_MEMORY_POOL` in schema version 3.
 pub const KEY_MEMORY_POOL: &str = "MEMORY_POOL";
 pub const KEY_PEER_BOOK: &str = "PEER_BOOK";
 
@@ -39,9 +58,14 @@ pub const KEY_CURR_CM_INDEX: &str = "CURRENT_CM_INDEX";
 pub const KEY_CURR_SN_INDEX: &str = "CURRENT_SN_INDEX";
 pub const KEY_CURR_MEMO_INDEX: &str = "CURRENT_MEMO_INDEX";
 pub const KEY_CURR_DIGEST: &str = "CURRENT_DIGEST";
+pub const KEY_CURR_CM_FRONTIER: &str = "CURRENT_CM_FRONTIER";
+
+pub const KEY_PRUNED_BLOCK_NUMBER: &str = "PRUNED_BLOCK_NUMBER";
+pub const KEY_RECORDS_UNINDEXED: &str = "RECORDS_UNINDEXED";
+pub const KEY_NETWORK_ID: &str = "NETWORK_ID";
 
 /// Represents address of certain transaction within block
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct TransactionLocation {
     /// Transaction index within the block
     pub index: u32,
@@ -67,69 +91,6 @@ impl FromBytes for TransactionLocation {
     }
 }
 
-/// Database operation.
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub enum Op {
-    Insert { col: u32, key: Vec<u8>, value: Vec<u8> },
-    Delete { col: u32, key: Vec<u8> },
-}
-
-impl Op {
-    pub fn key(&self) -> &[u8] {
-        match self {
-            Op::Insert { key, .. } => &key,
-            Op::Delete { key, .. } => &key,
-        }
-    }
-
-    pub fn col(&self) -> u32 {
-        match self {
-            Op::Insert { col, .. } => *col,
-            Op::Delete { col, .. } => *col,
-        }
-    }
-}
-
-/// Batched transaction of database operations.
-#[derive(Default, Clone, PartialEq)]
-pub struct DatabaseTransaction(pub Vec<Op>);
-
-impl DatabaseTransaction {
-    /// Create new transaction.
-    pub fn new() -> Self {
-        Self(vec![])
-    }
-
-    /// Add a key value pair under a specific col.
-    pub fn add(&mut self, col: u32, key: &[u8], value: &[u8]) {
-        self.0.push(Op::Insert {
-            col,
-            key: key.to_vec(),
-            value: value.to_vec(),
-        })
-    }
-
-    /// Delete a value given a col and key.
-    pub fn delete(&mut self, col: u32, key: &[u8]) {
-        self.0.push(Op::Delete { col, key: key.to_vec() })
-    }
-
-    /// Add an operation.
-    pub fn push(&mut self, op: Op) {
-        self.0.push(op)
-    }
-
-    /// Add a vector of operations.
-    pub fn push_vec(&mut self, ops: Vec<Op>) {
-        self.0.extend(ops)
-    }
-
-    /// Add another database transaction.
-    pub fn extend(&mut self, database_transaction: DatabaseTransaction) {
-        self.0.extend(database_transaction.0)
-    }
-}
-
 pub fn bytes_to_u32(bytes: Vec<u8>) -> u32 {
     let mut num_bytes = [0u8; 4];
     num_bytes.copy_from_slice(&bytes);
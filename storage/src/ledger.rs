@@ -18,7 +18,7 @@ use crate::*;
 use snarkvm_algorithms::{merkle_tree::MerkleTree, traits::LoadableMerkleParameters};
 use snarkvm_objects::{
     errors::StorageError,
-    traits::{LedgerScheme, Transaction},
+    traits::{LedgerScheme, Storage, Transaction},
     Block,
 };
 use snarkvm_parameters::{
@@ -36,32 +36,57 @@ use std::{
     sync::Arc,
 };
 
-pub struct Ledger<T: Transaction, P: LoadableMerkleParameters> {
+pub struct Ledger<T: Transaction, P: LoadableMerkleParameters, S: Storage> {
     pub latest_block_height: RwLock<u32>,
     pub ledger_parameters: P,
     pub cm_merkle_tree: RwLock<MerkleTree<P>>,
-    pub storage: Arc<Storage>,
+    pub pruning_mode: PruningMode,
+    pub storage: Arc<S>,
     pub _transaction: PhantomData<T>,
 }
 
-impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> Ledger<T, P, S> {
     /// Open the blockchain storage at a particular path.
     pub fn open_at_path<PATH: AsRef<Path>>(path: PATH) -> Result<Self, StorageError> {
         fs::create_dir_all(path.as_ref()).map_err(|err| StorageError::Message(err.to_string()))?;
 
-        Self::load_ledger_state(path, true)
+        Self::load_ledger_state(Some(path.as_ref()), true, PruningMode::Archive, None)
+    }
+
+    /// Open the blockchain storage at a particular path, starting from the given genesis block
+    /// instead of the genesis block in `snarkvm-parameters`. If the storage already exists,
+    /// its genesis block must match the given one.
+    pub fn open_at_path_with_genesis<PATH: AsRef<Path>>(
+        path: PATH,
+        genesis_block: Block<T>,
+    ) -> Result<Self, StorageError> {
+        fs::create_dir_all(path.as_ref()).map_err(|err| StorageError::Message(err.to_string()))?;
+
+        Self::load_ledger_state(Some(path.as_ref()), true, PruningMode::Archive, Some(genesis_block))
+    }
+
+    /// Open the blockchain storage at a particular path, pruning the transactions
+    /// of old blocks according to the given pruning mode.
+    pub fn open_at_path_with_pruning<PATH: AsRef<Path>>(
+        path: PATH,
+        pruning_mode: PruningMode,
+    ) -> Result<Self, StorageError> {
+        fs::create_dir_all(path.as_ref()).map_err(|err| StorageError::Message(err.to_string()))?;
+
+        Self::load_ledger_state(Some(path.as_ref()), true, pruning_mode, None)
     }
 
     /// Open the blockchain storage at a particular path as a secondary read-only instance.
     pub fn open_secondary_at_path<PATH: AsRef<Path>>(path: PATH) -> Result<Self, StorageError> {
         fs::create_dir_all(path.as_ref()).map_err(|err| StorageError::Message(err.to_string()))?;
 
-        Self::load_ledger_state(path, false)
+        Self::load_ledger_state(Some(path.as_ref()), false, PruningMode::Archive, None)
     }
 
     /// Returns true if there are no blocks in the ledger.
     pub fn is_empty(&self) -> bool {
-        self.get_latest_block().is_err()
+        // The transactions of the latest block may have been pruned, so only the block hash is checked.
+        self.get_block_hash(self.get_latest_block_height()).is_err()
     }
 
     /// Get the latest block height of the chain.
@@ -86,45 +111,54 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
             key: KEY_PEER_BOOK.as_bytes().to_vec(),
             value: peers_serialized,
         };
-        self.storage.write(DatabaseTransaction(vec![op]))
-    }
-
-    /// Destroy the storage given a path.
-    pub fn destroy_storage(path: PathBuf) -> Result<(), StorageError> {
-        Storage::destroy_storage(path)
+        self.storage.batch(DatabaseTransaction(vec![op]))
     }
 
     /// Returns a `Ledger` with the latest state loaded from storage at a given path as
     /// a primary or secondary ledger. A secondary ledger runs as a read-only instance.
-    fn load_ledger_state<PATH: AsRef<Path>>(path: PATH, primary: bool) -> Result<Self, StorageError> {
-        let mut secondary_path_os_string = path.as_ref().to_path_buf().into_os_string();
-        secondary_path_os_string.push("_secondary");
-
-        let secondary_path = PathBuf::from(secondary_path_os_string);
-
-        let latest_block_number = {
-            let storage = match primary {
-                true => Storage::open_cf(path.as_ref(), NUM_COLS)?,
-                false => Storage::open_secondary_cf(path.as_ref(), &secondary_path, NUM_COLS)?,
-            };
-            storage.get(COL_META, KEY_BEST_BLOCK_NUMBER.as_bytes())?
+    ///
+    /// A new ledger starts from the given genesis block, or from the genesis block in
+    /// `snarkvm-parameters` if none is given. An existing ledger must have been started
+    /// from the given genesis block.
+    fn load_ledger_state(
+        path: Option<&Path>,
+        primary: bool,
+        pruning_mode: PruningMode,
+        genesis_block: Option<Block<T>>,
+    ) -> Result<Self, StorageError> {
+        let secondary_path = path.map(|path| {
+            let mut secondary_path_os_string = path.to_path_buf().into_os_string();
+            secondary_path_os_string.push("_secondary");
+
+            PathBuf::from(secondary_path_os_string)
+        });
+
+        let storage = match primary {
+            true => S::open(path, None)?,
+            false => S::open(path, secondary_path.as_deref())?,
         };
 
+        // An in-memory storage has no primary instance to follow.
+        if !primary && storage.in_memory() {
+            return Err(StorageError::Message(
+                "in-memory storage can't be opened as a secondary instance".into(),
+            ));
+        }
+
         let crh = P::H::from(FromBytes::read(&LedgerMerkleTreeParameters::load_bytes()?[..])?);
         let ledger_parameters = P::from(crh);
 
+        Self::migrate_schema(&storage, &ledger_parameters, primary)?;
+
+        let latest_block_number = storage.get(COL_META, KEY_BEST_BLOCK_NUMBER.as_bytes())?;
+
         match latest_block_number {
             Some(val) => {
-                let storage = match primary {
-                    true => Storage::open_cf(path.as_ref(), NUM_COLS)?,
-                    false => Storage::open_secondary_cf(path.as_ref(), &secondary_path, NUM_COLS)?,
-                };
-
                 // Build commitment merkle tree
 
                 let mut cm_and_indices = vec![];
 
-                for (commitment_key, index_value) in storage.get_iter(COL_COMMITMENT) {
+                for (commitment_key, index_value) in storage.get_col(COL_COMMITMENT)? {
                     let commitment: T::Commitment = FromBytes::read(&commitment_key[..])?;
                     let index = bytes_to_u32(index_value.to_vec()) as usize;
 
@@ -136,28 +170,55 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
 
                 let merkle_tree = MerkleTree::new(ledger_parameters.clone(), &commitments)?;
 
-                Ok(Self {
+                let ledger_storage = Self {
                     latest_block_height: RwLock::new(bytes_to_u32(val)),
                     storage: Arc::new(storage),
                     cm_merkle_tree: RwLock::new(merkle_tree),
                     ledger_parameters,
+                    pruning_mode,
                     _transaction: PhantomData,
-                })
+                };
+
+                if let Some(genesis_block) = genesis_block {
+                    let expected = genesis_block.header.get_hash();
+                    let found = ledger_storage.get_block_hash(0)?;
+                    if found != expected {
+                        return Err(StorageError::GenesisBlockMismatch(
+                            expected.to_string(),
+                            found.to_string(),
+                        ));
+                    }
+                }
+
+                // Prune the blocks that have fallen out of the pruning window since the last time.
+                if primary {
+                    ledger_storage.prune_blocks()?;
+                }
+
+                Ok(ledger_storage)
             }
             None => {
                 // Add genesis block to database
 
-                let genesis_block: Block<T> = FromBytes::read(GenesisBlock::load_bytes().as_slice())?;
+                let genesis_block: Block<T> = match genesis_block {
+                    Some(genesis_block) => genesis_block,
+                    None => FromBytes::read(GenesisBlock::load_bytes().as_slice())?,
+                };
+
+                // Release the storage handle before the ledger opens it as a primary instance.
+                drop(storage);
 
-                let ledger_storage = Self::new(Some(&path.as_ref()), ledger_parameters, genesis_block)
-                    .expect("Ledger could not be instantiated");
+                let mut ledger_storage = Self::new(path, ledger_parameters, genesis_block)
+                    .map_err(|error| StorageError::Message(error.to_string()))?;
 
                 // If there did not exist a primary ledger at the path,
                 // then create one and then open the secondary instance.
                 if !primary {
-                    return Self::load_ledger_state(path, primary);
+                    return Self::load_ledger_state(path, primary, pruning_mode, None);
                 }
 
+                ledger_storage.pruning_mode = pruning_mode;
+
                 Ok(ledger_storage)
             }
         }
@@ -166,7 +227,7 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
     /// Attempt to catch the secondary read-only storage instance with the primary instance.
     pub fn catch_up_secondary(&self, update_merkle_tree: bool) -> Result<(), StorageError> {
         // Sync the secondary and primary instances
-        if self.storage.db.try_catch_up_with_primary().is_ok() {
+        if self.storage.try_catch_up_with_primary().is_ok() {
             let latest_block_height_bytes = self.get(COL_META, &KEY_BEST_BLOCK_NUMBER.as_bytes().to_vec())?;
             let new_latest_block_height = bytes_to_u32(latest_block_height_bytes);
             let mut latest_block_height = self.latest_block_height.write();
@@ -198,3 +259,23 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
         }
     }
 }
+
+impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P, MemDb> {
+    /// Open a new blockchain storage in memory, starting from the genesis block.
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Self::load_ledger_state(None, true, PruningMode::Archive, None)
+    }
+
+    /// Open a new blockchain storage in memory, starting from the given genesis block
+    /// instead of the genesis block in `snarkvm-parameters`.
+    pub fn open_in_memory_with_genesis(genesis_block: Block<T>) -> Result<Self, StorageError> {
+        Self::load_ledger_state(None, true, PruningMode::Archive, Some(genesis_block))
+    }
+}
+
+impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P, RocksDb> {
+    /// Destroy the storage given a path.
+    pub fn destroy_storage(path: PathBuf) -> Result<(), StorageError> {
+        RocksDb::destroy_storage(path)
+    }
+}
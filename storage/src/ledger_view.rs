@@ -0,0 +1,374 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+use snarkvm_algorithms::{
+    merkle_tree::{MerkleFrontier, MerklePath, MerkleTreeDigest},
+    traits::LoadableMerkleParameters,
+};
+use snarkvm_dpc::errors::LedgerError;
+use snarkvm_objects::{
+    errors::StorageError,
+    traits::{LedgerScheme, Storage, Transaction},
+    Block,
+    BlockHeader,
+    DPCTransactions,
+};
+use snarkvm_utilities::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes,
+};
+
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+/// The pending ledger state of transactions that are applied on top of the committed ledger state.
+pub(crate) struct LedgerOverlay<T: Transaction, P: LoadableMerkleParameters> {
+    /// The pending serial numbers and their ledger index.
+    serial_numbers: HashMap<Vec<u8>, usize>,
+    /// The pending commitments and their ledger index.
+    commitments: HashMap<Vec<u8>, usize>,
+    /// The pending memos and their ledger index.
+    memos: HashMap<Vec<u8>, usize>,
+
+    /// The pending commitments, in the order they are appended to the commitment merkle tree.
+    new_commitments: Vec<T::Commitment>,
+
+    /// The committed serial number, commitment and memo indexes.
+    sn_index: usize,
+    cm_index: usize,
+    memo_index: usize,
+
+    /// The commitment merkle tree frontier including the pending commitments.
+    cm_frontier: MerkleFrontier<P>,
+    /// The ledger digest including the pending commitments.
+    digest: MerkleTreeDigest<P>,
+}
+
+impl<T: Transaction, P: LoadableMerkleParameters> LedgerOverlay<T, P> {
+    /// Returns an empty overlay on top of the committed state of the given ledger.
+    pub(crate) fn new<S: Storage>(ledger: &Ledger<T, P, S>) -> Result<Self, StorageError> {
+        Ok(Self {
+            serial_numbers: HashMap::new(),
+            commitments: HashMap::new(),
+            memos: HashMap::new(),
+            new_commitments: vec![],
+            sn_index: ledger.current_sn_index()?,
+            cm_index: ledger.current_cm_index()?,
+            memo_index: ledger.current_memo_index()?,
+            cm_frontier: ledger.current_cm_frontier()?,
+            digest: FromBytes::read(&ledger.current_digest()?[..])?,
+        })
+    }
+
+    /// Applies the serial numbers, commitments and memo of the given transaction to the overlay,
+    /// if none of them exist in the ledger or in the overlay. Otherwise, the overlay is unchanged.
+    pub(crate) fn apply<S: Storage>(&mut self, ledger: &Ledger<T, P, S>, transaction: &T) -> Result<(), StorageError> {
+        let mut serial_numbers = Vec::with_capacity(transaction.old_serial_numbers().len());
+        for sn in transaction.old_serial_numbers() {
+            let sn_bytes = to_bytes![sn]?;
+            if self.serial_numbers.contains_key(&sn_bytes)
+                || serial_numbers.contains(&sn_bytes)
+                || ledger.get_sn_index(&sn_bytes)?.is_some()
+            {
+                return Err(StorageError::ExistingSn(sn_bytes));
+            }
+            serial_numbers.push(sn_bytes);
+        }
+
+        let mut commitments = Vec::with_capacity(transaction.new_commitments().len());
+        for cm in transaction.new_commitments() {
+            let cm_bytes = to_bytes![cm]?;
+            if self.commitments.contains_key(&cm_bytes)
+                || commitments.contains(&cm_bytes)
+                || ledger.get_cm_index(&cm_bytes)?.is_some()
+            {
+                return Err(StorageError::ExistingCm(cm_bytes));
+            }
+            commitments.push(cm_bytes);
+        }
+
+        let memo_bytes = to_bytes![transaction.memorandum()]?;
+        if self.memos.contains_key(&memo_bytes) || ledger.get_memo_index(&memo_bytes)?.is_some() {
+            return Err(StorageError::ExistingMemo(memo_bytes));
+        }
+
+        self.cm_frontier
+            .extend(&ledger.ledger_parameters, transaction.new_commitments())?;
+        self.digest = self.cm_frontier.root(&ledger.ledger_parameters)?;
+
+        for sn_bytes in serial_numbers {
+            self.serial_numbers
+                .insert(sn_bytes, self.sn_index + self.serial_numbers.len());
+        }
+        for cm_bytes in commitments {
+            self.commitments
+                .insert(cm_bytes, self.cm_index + self.commitments.len());
+        }
+        self.memos.insert(memo_bytes, self.memo_index + self.memos.len());
+        self.new_commitments.extend_from_slice(transaction.new_commitments());
+
+        Ok(())
+    }
+
+    /// Returns the pending commitments, in the order they are appended to the commitment merkle tree.
+    pub(crate) fn new_commitments(&self) -> &[T::Commitment] {
+        &self.new_commitments
+    }
+
+    /// Returns the ledger digest including the pending commitments.
+    pub(crate) fn digest(&self) -> &MerkleTreeDigest<P> {
+        &self.digest
+    }
+
+    /// Returns the database operations that commit the overlay as the state of the given block number.
+    pub(crate) fn ops(&self, block_number: u32) -> Result<Vec<Op>, StorageError> {
+        let mut ops = Vec::with_capacity(self.serial_numbers.len() + self.commitments.len() + self.memos.len() + 6);
+
+        for (col, entries) in &[
+            (COL_SERIAL_NUMBER, &self.serial_numbers),
+            (COL_COMMITMENT, &self.commitments),
+            (COL_MEMO, &self.memos),
+        ] {
+            for (key, index) in entries.iter() {
+                ops.push(Op::Insert {
+                    col: *col,
+                    key: key.clone(),
+                    value: (*index as u32).to_le_bytes().to_vec(),
+                });
+            }
+        }
+
+        // Update the database state for current indexes
+
+        let sn_index = self.sn_index + self.serial_numbers.len();
+        let cm_index = self.cm_index + self.commitments.len();
+        let memo_index = self.memo_index + self.memos.len();
+
+        ops.push(Op::Insert {
+            col: COL_META,
+            key: KEY_CURR_SN_INDEX.as_bytes().to_vec(),
+            value: (sn_index as u32).to_le_bytes().to_vec(),
+        });
+        ops.push(Op::Insert {
+            col: COL_META,
+            key: KEY_CURR_CM_INDEX.as_bytes().to_vec(),
+            value: (cm_index as u32).to_le_bytes().to_vec(),
+        });
+        ops.push(Op::Insert {
+            col: COL_META,
+            key: KEY_CURR_MEMO_INDEX.as_bytes().to_vec(),
+            value: (memo_index as u32).to_le_bytes().to_vec(),
+        });
+
+        // Update the ledger digest and the commitment merkle tree frontier
+
+        ops.push(Op::Insert {
+            col: COL_DIGEST,
+            key: to_bytes![self.digest]?.to_vec(),
+            value: [block_number.to_le_bytes(), (cm_index as u32).to_le_bytes()].concat(),
+        });
+        ops.push(Op::Insert {
+            col: COL_META,
+            key: KEY_CURR_DIGEST.as_bytes().to_vec(),
+            value: to_bytes![self.digest]?.to_vec(),
+        });
+        ops.push(Op::Insert {
+            col: COL_META,
+            key: KEY_CURR_CM_FRONTIER.as_bytes().to_vec(),
+            value: to_bytes![self.cm_frontier]?.to_vec(),
+        });
+
+        Ok(ops)
+    }
+
+    /// Returns true if the overlay was created on top of the current committed state of the given ledger.
+    pub(crate) fn is_current<S: Storage>(&self, ledger: &Ledger<T, P, S>) -> Result<bool, StorageError> {
+        Ok(self.sn_index == ledger.current_sn_index()?
+            && self.cm_index == ledger.current_cm_index()?
+            && self.memo_index == ledger.current_memo_index()?)
+    }
+}
+
+/// A speculative view of a ledger, which applies transactions in memory on top of the committed ledger state.
+///
+/// The view answers ledger queries including the effects of the applied transactions, so that a transaction
+/// can be checked against the transactions before it in the same block. The view is either discarded,
+/// or flushed into the ledger as a new block in a single database transaction.
+pub struct LedgerView<T: Transaction, P: LoadableMerkleParameters, S: Storage> {
+    ledger: Arc<Ledger<T, P, S>>,
+    overlay: LedgerOverlay<T, P>,
+    transactions: Vec<T>,
+}
+
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> LedgerView<T, P, S> {
+    /// Returns an empty view on top of the committed state of the given ledger.
+    pub fn new(ledger: Arc<Ledger<T, P, S>>) -> Result<Self, StorageError> {
+        let overlay = LedgerOverlay::new(&ledger)?;
+
+        Ok(Self {
+            ledger,
+            overlay,
+            transactions: vec![],
+        })
+    }
+
+    /// Returns the underlying ledger.
+    pub fn ledger(&self) -> &Arc<Ledger<T, P, S>> {
+        &self.ledger
+    }
+
+    /// Returns the transactions applied to the view, in order.
+    pub fn transactions(&self) -> &[T] {
+        &self.transactions
+    }
+
+    /// Applies the given transaction to the view. Returns an error and leaves the view unchanged
+    /// if the transaction conflicts with the ledger or with a transaction applied before it.
+    pub fn apply_transaction(&mut self, transaction: &T) -> Result<(), StorageError> {
+        self.overlay.apply(&self.ledger, transaction)?;
+        self.transactions.push(transaction.clone());
+
+        Ok(())
+    }
+
+    /// Discards the transactions applied to the view, leaving the ledger unchanged.
+    pub fn discard(self) {}
+
+    /// Inserts and commits a block with the given header and the applied transactions to the ledger,
+    /// in a single database transaction. Returns the committed block.
+    pub fn flush(self, header: BlockHeader) -> Result<Block<T>, StorageError> {
+        // The view is only valid on top of the ledger state that it was created from.
+        if header.previous_block_hash != self.ledger.get_latest_block()?.header.get_hash()
+            || !self.overlay.is_current(&self.ledger)?
+        {
+            return Err(StorageError::StaleLedgerView);
+        }
+
+        let block = Block {
+            header,
+            transactions: DPCTransactions(self.transactions),
+        };
+
+        let database_transaction = DatabaseTransaction(self.ledger.insert_ops(&block)?);
+        self.ledger.commit_overlay(&block, self.overlay, database_transaction)?;
+
+        Ok(block)
+    }
+}
+
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> LedgerScheme for LedgerView<T, P, S> {
+    type Block = Block<Self::Transaction>;
+    type Commitment = T::Commitment;
+    type MerkleParameters = P;
+    type MerklePath = MerklePath<Self::MerkleParameters>;
+    type MerkleTreeDigest = MerkleTreeDigest<Self::MerkleParameters>;
+    type SerialNumber = T::SerialNumber;
+    type Transaction = T;
+
+    /// Instantiates a new ledger with a genesis block, and returns an empty view of it.
+    fn new(
+        path: Option<&Path>,
+        parameters: Self::MerkleParameters,
+        genesis_block: Self::Block,
+    ) -> anyhow::Result<Self> {
+        let ledger = Ledger::<T, P, S>::new(path, parameters, genesis_block)?;
+
+        Ok(Self::new(Arc::new(ledger))?)
+    }
+
+    /// Returns the number of blocks including the genesis block
+    fn len(&self) -> usize {
+        self.ledger.len()
+    }
+
+    /// Return the parameters used to construct the ledger Merkle tree.
+    fn parameters(&self) -> &Self::MerkleParameters {
+        self.ledger.parameters()
+    }
+
+    /// Returns the id of the network of the ledger.
+    fn network_id(&self) -> Option<u8> {
+        self.ledger.network_id()
+    }
+
+    /// Return a digest of the ledger Merkle tree including the applied transactions.
+    fn digest(&self) -> Option<Self::MerkleTreeDigest> {
+        Some(self.overlay.digest().clone())
+    }
+
+    /// Check that st_{ts} is a valid digest for some (past) ledger state.
+    fn validate_digest(&self, digest: &Self::MerkleTreeDigest) -> bool {
+        digest == self.overlay.digest() || self.ledger.validate_digest(digest)
+    }
+
+    /// Returns true if the given commitment exists in the ledger or in the applied transactions.
+    fn contains_cm(&self, cm: &Self::Commitment) -> bool {
+        self.overlay.commitments.contains_key(&to_bytes![cm].unwrap()) || self.ledger.contains_cm(cm)
+    }
+
+    /// Returns true if the given serial number exists in the ledger or in the applied transactions.
+    fn contains_sn(&self, sn: &Self::SerialNumber) -> bool {
+        self.overlay.serial_numbers.contains_key(&to_bytes![sn].unwrap()) || self.ledger.contains_sn(sn)
+    }
+
+    /// Returns true if the given memo exists in the ledger or in the applied transactions.
+    fn contains_memo(&self, memo: &<Self::Transaction as Transaction>::Memorandum) -> bool {
+        self.overlay.memos.contains_key(&to_bytes![memo].unwrap()) || self.ledger.contains_memo(memo)
+    }
+
+    /// Returns the Merkle path to the ledger digest including the applied transactions
+    /// for a given commitment, if it exists in the ledger or in the applied transactions.
+    fn prove_cm(&self, cm: &Self::Commitment) -> anyhow::Result<Self::MerklePath> {
+        if self.overlay.new_commitments().is_empty() {
+            return self.ledger.prove_cm(cm);
+        }
+
+        let cm_bytes = to_bytes![cm]?;
+        let cm_index = match self.overlay.commitments.get(&cm_bytes) {
+            Some(cm_index) => *cm_index,
+            None => self
+                .ledger
+                .get_cm_index(&cm_bytes)?
+                .ok_or(LedgerError::InvalidCmIndex)?,
+        };
+
+        // The committed commitment merkle tree is extended with the pending commitments on a copy.
+        let mut cm_merkle_tree = self.ledger.cm_merkle_tree.read().clone();
+        cm_merkle_tree.extend(self.overlay.new_commitments())?;
+
+        Ok(cm_merkle_tree.generate_proof(cm_index, cm)?)
+    }
+
+    /// Returns the Merkle path to the given past ledger digest, or to the ledger digest
+    /// including the applied transactions, for a given commitment.
+    fn prove_cm_at(&self, cm: &Self::Commitment, digest: &Self::MerkleTreeDigest) -> anyhow::Result<Self::MerklePath> {
+        match digest == self.overlay.digest() {
+            true => self.prove_cm(cm),
+            false => Ok(self.ledger.prove_cm_at(cm, digest)?),
+        }
+    }
+
+    /// Returns true if the given Merkle path is a valid witness for
+    /// the given ledger digest and commitment.
+    fn verify_cm(
+        parameters: &Self::MerkleParameters,
+        digest: &Self::MerkleTreeDigest,
+        cm: &Self::Commitment,
+        witness: &Self::MerklePath,
+    ) -> bool {
+        Ledger::<T, P, S>::verify_cm(parameters, digest, cm, witness)
+    }
+}
@@ -17,11 +17,17 @@
 pub mod ledger;
 pub use ledger::*;
 
+pub mod ledger_view;
+pub use ledger_view::*;
+
 pub mod key_value;
 pub use key_value::*;
 
 pub mod objects;
 pub use objects::*;
 
-pub mod storage;
-pub use storage::*;
+pub mod mem;
+pub use mem::*;
+
+pub mod rocks;
+pub use rocks::*;
@@ -0,0 +1,131 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkvm_objects::{
+    errors::StorageError,
+    traits::{DatabaseTransaction, Op, Storage},
+};
+
+use parking_lot::RwLock;
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Bound,
+    path::Path,
+    sync::Arc,
+};
+
+type Column = BTreeMap<Box<[u8]>, Box<[u8]>>;
+
+/// An in-memory storage backend; useful for tests and short-lived ledgers
+/// that don't need to persist their state to disk.
+#[derive(Clone, Default)]
+pub struct MemDb {
+    pub cols: Arc<RwLock<HashMap<u32, Column>>>,
+}
+
+impl Storage for MemDb {
+    fn in_memory(&self) -> bool {
+        true
+    }
+
+    /// Opens a new, empty in-memory storage; the given paths are ignored.
+    fn open(_path: Option<&Path>, _secondary_path: Option<&Path>) -> Result<Self, StorageError> {
+        Ok(Self::default())
+    }
+
+    fn get(&self, col: u32, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        Ok(self
+            .cols
+            .read()
+            .get(&col)
+            .and_then(|col| col.get(key))
+            .map(|value| value.to_vec()))
+    }
+
+    fn get_col(&self, col: u32) -> Result<Vec<(Box<[u8]>, Box<[u8]>)>, StorageError> {
+        match self.cols.read().get(&col) {
+            Some(col) => Ok(col.iter().map(|(key, value)| (key.clone(), value.clone())).collect()),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn get_keys(&self, col: u32) -> Result<Vec<Box<[u8]>>, StorageError> {
+        match self.cols.read().get(&col) {
+            Some(col) => Ok(col.keys().cloned().collect()),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn get_keys_with_prefix(&self, col: u32, prefix: &[u8]) -> Result<Vec<Box<[u8]>>, StorageError> {
+        match self.cols.read().get(&col) {
+            Some(col) => Ok(col
+                .range::<[u8], _>((Bound::Included(prefix), Bound::Unbounded))
+                .map(|(key, _value)| key)
+                .take_while(|key| key.starts_with(prefix))
+                .cloned()
+                .collect()),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(&self, col: u32, key: K, value: V) -> Result<(), StorageError> {
+        self.cols
+            .write()
+            .entry(col)
+            .or_default()
+            .insert(key.as_ref().into(), value.as_ref().into());
+
+        Ok(())
+    }
+
+    fn batch(&self, transaction: DatabaseTransaction) -> Result<(), StorageError> {
+        // Hold the lock for the whole batch, so that it is applied atomically.
+        let mut cols = self.cols.write();
+
+        for operation in transaction.0 {
+            match operation {
+                Op::Insert { col, key, value } => {
+                    cols.entry(col).or_default().insert(key.into(), value.into());
+                }
+                Op::Delete { col, key } => {
+                    if let Some(col) = cols.get_mut(&col) {
+                        col.remove(&key[..]);
+                    }
+                }
+            };
+        }
+
+        Ok(())
+    }
+
+    fn exists(&self, col: u32, key: &[u8]) -> bool {
+        match self.cols.read().get(&col) {
+            Some(col) => col.contains_key(key),
+            None => false,
+        }
+    }
+
+    /// There is no primary instance to catch up with, so this is a no-op.
+    fn try_catch_up_with_primary(&self) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn destroy(&self) -> Result<(), StorageError> {
+        self.cols.write().clear();
+
+        Ok(())
+    }
+}
@@ -18,14 +18,14 @@ use crate::*;
 use snarkvm_algorithms::traits::LoadableMerkleParameters;
 use snarkvm_objects::{
     errors::{BlockError, StorageError},
-    traits::Transaction,
+    traits::{Storage, Transaction},
     Block,
     BlockHeaderHash,
     DPCTransactions,
 };
 use snarkvm_utilities::{to_bytes, FromBytes, ToBytes};
 
-impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> Ledger<T, P, S> {
     /// Get the latest block in the chain.
     pub fn get_latest_block(&self) -> Result<Block<T>, StorageError> {
         self.get_block_from_block_number(self.get_latest_block_height())
@@ -70,7 +70,10 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
     pub fn get_block_transactions(&self, block_hash: &BlockHeaderHash) -> Result<DPCTransactions<T>, StorageError> {
         match self.storage.get(COL_BLOCK_TRANSACTIONS, &block_hash.0)? {
             Some(encoded_block_transactions) => Ok(DPCTransactions::read(&encoded_block_transactions[..])?),
-            None => Err(StorageError::MissingBlockTransactions(block_hash.to_string())),
+            None => match self.is_pruned(block_hash)? {
+                true => Err(StorageError::PrunedBlock(block_hash.to_string())),
+                false => Err(StorageError::MissingBlockTransactions(block_hash.to_string())),
+            },
         }
     }
 
@@ -93,7 +96,13 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
 
         let existing_block_number = previous_block_number + 1;
 
-        if self.get_block_from_block_number(existing_block_number).is_ok() {
+        // The block transactions may have been pruned, so only the block hash is checked.
+        let existing_block_hash = match existing_block_number <= self.get_latest_block_height() {
+            true => self.get_block_hash(existing_block_number).ok(),
+            false => None,
+        };
+
+        if existing_block_hash.is_some() {
             // the storage has a conflicting block with the same previous_block_hash
             Ok(Some(existing_block_number))
         } else {
@@ -146,7 +155,7 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
             }
         }
 
-        self.storage.write(database_transaction)
+        self.storage.batch(database_transaction)
     }
 
     /// De-commit the latest block and return its header hash.
@@ -167,17 +176,10 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
             value: update_best_block_num.to_le_bytes().to_vec(),
         });
 
-        database_transaction.push(Op::Delete {
-            col: COL_DIGEST,
-            key: self.current_digest()?,
-        });
-
         let mut sn_index = self.current_sn_index()?;
         let mut cm_index = self.current_cm_index()?;
         let mut memo_index = self.current_memo_index()?;
 
-        let mut database_transaction = DatabaseTransaction::new();
-
         for transaction in self.get_block_transactions(&block_hash)?.0 {
             for sn in transaction.old_serial_numbers() {
                 database_transaction.push(Op::Delete {
@@ -202,6 +204,14 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
             memo_index -= 1;
         }
 
+        // The ledger digest only changes if the block contains new commitments
+        if cm_index != self.current_cm_index()? {
+            database_transaction.push(Op::Delete {
+                col: COL_DIGEST,
+                key: self.current_digest()?,
+            });
+        }
+
         // Update the database state for current indexes
 
         database_transaction.push(Op::Insert {
@@ -230,7 +240,7 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
             key: block_hash.0.to_vec(),
         });
 
-        self.storage.write(database_transaction)?;
+        self.storage.batch(database_transaction)?;
 
         let mut latest_block_height = self.latest_block_height.write();
         *latest_block_height -= 1;
@@ -247,11 +257,13 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
     }
 
     /// Remove the latest `num_blocks` blocks.
+    /// The removal is refused up front if any of the blocks to remove have been pruned.
     pub fn remove_latest_blocks(&self, num_blocks: u32) -> Result<(), StorageError> {
         let latest_block_height = self.get_latest_block_height();
         if num_blocks > latest_block_height {
             return Err(StorageError::InvalidBlockRemovalNum(num_blocks, latest_block_height));
         }
+        self.check_revert(latest_block_height - num_blocks)?;
 
         for _ in 0..num_blocks {
             self.remove_latest_block()?;
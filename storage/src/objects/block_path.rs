@@ -18,7 +18,7 @@ use crate::Ledger;
 use snarkvm_algorithms::traits::LoadableMerkleParameters;
 use snarkvm_objects::{
     errors::{BlockError, StorageError},
-    traits::Transaction,
+    traits::{Storage, Transaction},
     BlockHeader,
     BlockHeaderHash,
 };
@@ -44,7 +44,7 @@ pub struct SideChainPath {
     pub path: Vec<BlockHeaderHash>,
 }
 
-impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> Ledger<T, P, S> {
     /// Get the block's path/origin.
     pub fn get_block_path(&self, block_header: &BlockHeader) -> Result<BlockPath, StorageError> {
         let block_hash = block_header.get_hash();
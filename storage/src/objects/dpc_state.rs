@@ -15,8 +15,14 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::*;
-use snarkvm_algorithms::{merkle_tree::MerkleTree, traits::LoadableMerkleParameters};
-use snarkvm_objects::{errors::StorageError, traits::Transaction};
+use snarkvm_algorithms::{
+    merkle_tree::{MerkleFrontier, MerklePath, MerkleTree, MerkleTreeDigest},
+    traits::LoadableMerkleParameters,
+};
+use snarkvm_objects::{
+    errors::StorageError,
+    traits::{Storage, Transaction},
+};
 use snarkvm_utilities::{
     bytes::{FromBytes, ToBytes},
     to_bytes,
@@ -24,7 +30,7 @@ use snarkvm_utilities::{
 
 use std::collections::HashSet;
 
-impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> Ledger<T, P, S> {
     /// Get the current commitment index
     pub fn current_cm_index(&self) -> Result<usize, StorageError> {
         match self.storage.get(COL_META, KEY_CURR_CM_INDEX.as_bytes())? {
@@ -57,10 +63,18 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
         }
     }
 
+    /// Get the current commitment merkle tree frontier
+    pub fn current_cm_frontier(&self) -> Result<MerkleFrontier<P>, StorageError> {
+        match self.storage.get(COL_META, KEY_CURR_CM_FRONTIER.as_bytes())? {
+            Some(frontier_bytes) => Ok(FromBytes::read(&frontier_bytes[..])?),
+            None => Ok(self.cm_merkle_tree.read().frontier()),
+        }
+    }
+
     /// Get the set of past ledger digests
     pub fn past_digests(&self) -> Result<HashSet<Vec<u8>>, StorageError> {
         let mut digests = HashSet::new();
-        for (key, _value) in self.storage.get_iter(COL_DIGEST) {
+        for (key, _value) in self.storage.get_col(COL_DIGEST)? {
             digests.insert(key.to_vec());
         }
 
@@ -106,6 +120,60 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
         }
     }
 
+    /// Get the number of commitments in the ledger at the given past ledger digest
+    pub fn get_digest_cm_index(&self, digest_bytes: &[u8]) -> Result<Option<usize>, StorageError> {
+        match self.storage.get(COL_DIGEST, digest_bytes)? {
+            Some(digest_value) => {
+                // The value holds the block number, followed by the commitment index.
+                if digest_value.len() < 8 {
+                    return Err(StorageError::MissingDigestCmIndex(hex::encode(digest_bytes)));
+                }
+
+                let mut cm_index = [0u8; 4];
+                cm_index.copy_from_slice(&digest_value[4..8]);
+
+                Ok(Some(u32::from_le_bytes(cm_index) as usize))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the block number in the value of the given ledger digest, which holds the block number,
+    /// followed by the commitment index since schema version 2.
+    pub(crate) fn digest_block_number(digest_bytes: &[u8], digest_value: &[u8]) -> Result<u32, StorageError> {
+        match digest_value.len() >= 4 {
+            true => Ok(bytes_to_u32(digest_value[..4].to_vec())),
+            false => Err(StorageError::InvalidDigestValue(hex::encode(digest_bytes))),
+        }
+    }
+
+    /// Returns the Merkle path to the given past ledger digest for a given commitment,
+    /// if the commitment existed in the ledger at that digest
+    pub fn prove_cm_at(&self, cm: &T::Commitment, digest: &MerkleTreeDigest<P>) -> Result<MerklePath<P>, StorageError> {
+        let cm_bytes = to_bytes![cm]?;
+        let cm_index = self
+            .get_cm_index(&cm_bytes)?
+            .ok_or_else(|| StorageError::MissingCm(hex::encode(&cm_bytes)))?;
+
+        let digest_bytes = to_bytes![digest]?;
+        let num_cms = self
+            .get_digest_cm_index(&digest_bytes)?
+            .ok_or_else(|| StorageError::InvalidDigest(hex::encode(&digest_bytes)))?;
+
+        if cm_index >= num_cms {
+            return Err(StorageError::MissingCm(hex::encode(&cm_bytes)));
+        }
+
+        let path = self.cm_merkle_tree.read().generate_proof_at(num_cms, cm_index, cm)?;
+
+        // The digest may be left over from a block that is no longer canon.
+        if !path.verify(digest, cm)? {
+            return Err(StorageError::InvalidDigest(hex::encode(&digest_bytes)));
+        }
+
+        Ok(path)
+    }
+
     /// Build a new commitment merkle tree from the stored commitments
     pub fn build_merkle_tree(
         &self,
@@ -114,7 +182,7 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
         // TODO (raychu86) make this more efficient
         let mut cm_and_indices = additional_cms;
 
-        for (commitment_key, index_value) in self.storage.get_iter(COL_COMMITMENT) {
+        for (commitment_key, index_value) in self.storage.get_col(COL_COMMITMENT)? {
             let commitment: T::Commitment = FromBytes::read(&commitment_key[..])?;
             let index = bytes_to_u32(index_value.to_vec()) as usize;
 
@@ -127,17 +195,33 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
         Ok(MerkleTree::new(self.ledger_parameters.clone(), &commitments)?)
     }
 
+    /// Extend the current commitment merkle tree frontier with the given commitments,
+    /// without rebuilding the tree from the stored commitments
+    pub fn extend_cm_frontier(&self, additional_cms: &[T::Commitment]) -> Result<MerkleFrontier<P>, StorageError> {
+        let mut frontier = self.current_cm_frontier()?;
+        frontier.extend(&self.ledger_parameters, additional_cms)?;
+
+        Ok(frontier)
+    }
+
     /// Rebuild the stored merkle tree with the current stored commitments
     pub fn update_merkle_tree(&self) -> Result<(), StorageError> {
         let mut merkle_tree = self.cm_merkle_tree.write();
         *merkle_tree = self.build_merkle_tree(vec![])?;
 
-        let update_current_digest = DatabaseTransaction(vec![Op::Insert {
-            col: COL_META,
-            key: KEY_CURR_DIGEST.as_bytes().to_vec(),
-            value: to_bytes![merkle_tree.root()]?.to_vec(),
-        }]);
-
-        self.storage.write(update_current_digest)
+        let update_current_digest = DatabaseTransaction(vec![
+            Op::Insert {
+                col: COL_META,
+                key: KEY_CURR_DIGEST.as_bytes().to_vec(),
+                value: to_bytes![merkle_tree.root()]?.to_vec(),
+            },
+            Op::Insert {
+                col: COL_META,
+                key: KEY_CURR_CM_FRONTIER.as_bytes().to_vec(),
+                value: to_bytes![merkle_tree.frontier()]?.to_vec(),
+            },
+        ]);
+
+        self.storage.batch(update_current_digest)
     }
 }
@@ -18,76 +18,23 @@ use crate::*;
 use snarkvm_algorithms::traits::LoadableMerkleParameters;
 use snarkvm_objects::{
     errors::{BlockError, StorageError},
-    traits::Transaction,
+    traits::{Storage, Transaction},
     Block,
     BlockHeader,
     BlockHeaderHash,
 };
 use snarkvm_utilities::{bytes::ToBytes, has_duplicates, to_bytes};
 
-impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
-    /// Commit a transaction to the canon chain
-    #[allow(clippy::type_complexity)]
-    pub(crate) fn commit_transaction(
-        &self,
-        sn_index: &mut usize,
-        cm_index: &mut usize,
-        memo_index: &mut usize,
-        transaction: &T,
-    ) -> Result<(Vec<Op>, Vec<(T::Commitment, usize)>), StorageError> {
-        let old_serial_numbers = transaction.old_serial_numbers();
-        let new_commitments = transaction.new_commitments();
-
-        let mut ops = Vec::with_capacity(old_serial_numbers.len() + new_commitments.len());
-        let mut cms = Vec::with_capacity(new_commitments.len());
-
-        for sn in old_serial_numbers {
-            let sn_bytes = to_bytes![sn]?;
-            if self.get_sn_index(&sn_bytes)?.is_some() {
-                return Err(StorageError::ExistingSn(sn_bytes.to_vec()));
-            }
-
-            ops.push(Op::Insert {
-                col: COL_SERIAL_NUMBER,
-                key: sn_bytes,
-                value: (*sn_index as u32).to_le_bytes().to_vec(),
-            });
-            *sn_index += 1;
-        }
-
-        for cm in new_commitments {
-            let cm_bytes = to_bytes![cm]?;
-            if self.get_cm_index(&cm_bytes)?.is_some() {
-                return Err(StorageError::ExistingCm(cm_bytes.to_vec()));
-            }
-
-            ops.push(Op::Insert {
-                col: COL_COMMITMENT,
-                key: cm_bytes,
-                value: (*cm_index as u32).to_le_bytes().to_vec(),
-            });
-            cms.push((cm.clone(), *cm_index));
-
-            *cm_index += 1;
-        }
-
-        let memo_bytes = to_bytes![transaction.memorandum()]?;
-        if self.get_memo_index(&memo_bytes)?.is_some() {
-            return Err(StorageError::ExistingMemo(memo_bytes.to_vec()));
-        } else {
-            ops.push(Op::Insert {
-                col: COL_MEMO,
-                key: memo_bytes,
-                value: (*memo_index as u32).to_le_bytes().to_vec(),
-            });
-            *memo_index += 1;
-        }
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> Ledger<T, P, S> {
+    /// Insert a block into storage without canonizing/committing it.
+    pub fn insert_only(&self, block: &Block<T>) -> Result<(), StorageError> {
+        let database_transaction = DatabaseTransaction(self.insert_ops(block)?);
 
-        Ok((ops, cms))
+        self.storage.batch(database_transaction)
     }
 
-    /// Insert a block into storage without canonizing/committing it.
-    pub fn insert_only(&self, block: &Block<T>) -> Result<(), StorageError> {
+    /// Returns the database operations that insert a block into storage without canonizing/committing it.
+    pub(crate) fn insert_ops(&self, block: &Block<T>) -> Result<Vec<Op>, StorageError> {
         let block_hash = block.header.get_hash();
 
         // Check that the block does not already exist.
@@ -167,9 +114,7 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
             value: to_bytes![block.transactions]?.to_vec(),
         });
 
-        self.storage.write(database_transaction)?;
-
-        Ok(())
+        Ok(database_transaction.0)
     }
 
     /// Commit/canonize a particular block.
@@ -181,8 +126,6 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
             return Err(StorageError::ExistingCanonBlock(block_header_hash.to_string()));
         }
 
-        let mut database_transaction = DatabaseTransaction::new();
-
         let mut transaction_serial_numbers = Vec::with_capacity(block.transactions.0.len());
         let mut transaction_commitments = Vec::with_capacity(block.transactions.0.len());
         let mut transaction_memos = Vec::with_capacity(block.transactions.0.len());
@@ -210,38 +153,24 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
             return Err(StorageError::DuplicateMemo);
         }
 
-        let mut sn_index = self.current_sn_index()?;
-        let mut cm_index = self.current_cm_index()?;
-        let mut memo_index = self.current_memo_index()?;
-
         // Process the individual transactions
 
-        let mut transaction_cms = vec![];
+        let mut overlay = LedgerOverlay::new(self)?;
 
         for transaction in block.transactions.0.iter() {
-            let (tx_ops, cms) = self.commit_transaction(&mut sn_index, &mut cm_index, &mut memo_index, transaction)?;
-            database_transaction.push_vec(tx_ops);
-            transaction_cms.extend(cms);
+            overlay.apply(self, transaction)?;
         }
 
-        // Update the database state for current indexes
-
-        database_transaction.push(Op::Insert {
-            col: COL_META,
-            key: KEY_CURR_SN_INDEX.as_bytes().to_vec(),
-            value: (sn_index as u32).to_le_bytes().to_vec(),
-        });
-        database_transaction.push(Op::Insert {
-            col: COL_META,
-            key: KEY_CURR_CM_INDEX.as_bytes().to_vec(),
-            value: (cm_index as u32).to_le_bytes().to_vec(),
-        });
-        database_transaction.push(Op::Insert {
-            col: COL_META,
-            key: KEY_CURR_MEMO_INDEX.as_bytes().to_vec(),
-            value: (memo_index as u32).to_le_bytes().to_vec(),
-        });
+        self.commit_overlay(&block, overlay, DatabaseTransaction::new())
+    }
 
+    /// Commit/canonize a particular block, with the ledger state of its transactions in the given overlay.
+    pub(crate) fn commit_overlay(
+        &self,
+        block: &Block<T>,
+        overlay: LedgerOverlay<T, P>,
+        mut database_transaction: DatabaseTransaction,
+    ) -> Result<(), StorageError> {
         // Update the best block number
 
         let is_genesis = block.header.previous_block_hash == BlockHeaderHash([0u8; 32])
@@ -273,25 +202,24 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
             value: block.header.get_hash().0.to_vec(),
         });
 
-        // Rebuild the new commitment merkle tree
-        let new_merkle_tree = self.build_merkle_tree(transaction_cms)?;
-        let new_digest = new_merkle_tree.root();
+        // Prune the transactions of the blocks that fall out of the pruning window
 
-        database_transaction.push(Op::Insert {
-            col: COL_DIGEST,
-            key: to_bytes![new_digest]?.to_vec(),
-            value: new_best_block_number.to_le_bytes().to_vec(),
-        });
-        database_transaction.push(Op::Insert {
-            col: COL_META,
-            key: KEY_CURR_DIGEST.as_bytes().to_vec(),
-            value: to_bytes![new_digest]?.to_vec(),
-        });
+        database_transaction.push_vec(self.prune_ops(new_best_block_number)?);
+
+        // Update the ledger state with the serial numbers, commitments and memos of the transactions
+
+        database_transaction.push_vec(overlay.ops(new_best_block_number)?);
+
+        // Evict the transactions of the block and the conflicting transactions from the memory pool
+
+        database_transaction.push_vec(self.memory_pool_eviction_ops(block)?);
 
         let mut cm_merkle_tree = self.cm_merkle_tree.write();
-        *cm_merkle_tree = new_merkle_tree;
 
-        self.storage.write(database_transaction)?;
+        self.storage.batch(database_transaction)?;
+
+        // Only rehash the paths of the new commitments in the commitment merkle tree
+        cm_merkle_tree.extend(overlay.new_commitments())?;
 
         if !is_genesis {
             *height += 1;
@@ -324,10 +252,15 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
     }
 
     /// Revert the chain to the state before the fork.
+    ///
+    /// The revert is refused up front if any of the blocks to decommit have been pruned,
+    /// so that the chain is never left partially reverted.
     pub fn revert_for_fork(&self, side_chain_path: &SideChainPath) -> Result<(), StorageError> {
         let latest_block_height = self.get_latest_block_height();
 
         if side_chain_path.new_block_number > latest_block_height {
+            self.check_revert(side_chain_path.shared_block_number)?;
+
             // Decommit all blocks on canon chain up to the shared block number with the side chain.
             for _ in (side_chain_path.shared_block_number)..latest_block_height {
                 self.decommit_latest_block()?;
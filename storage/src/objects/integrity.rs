@@ -0,0 +1,486 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+use snarkvm_algorithms::{merkle_tree::MerkleTree, traits::LoadableMerkleParameters};
+use snarkvm_objects::{
+    errors::StorageError,
+    traits::{Storage, Transaction},
+    BlockHeader,
+    BlockHeaderHash,
+    DPCTransactions,
+};
+use snarkvm_utilities::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes,
+};
+
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// An inconsistency between the stored values of a `Ledger`, as found by `Ledger::check_integrity`.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum LedgerInconsistency {
+    #[error("missing block hash for block number {}", _0)]
+    MissingBlockHash(u32),
+
+    #[error("missing block header for block number {} with hash {}", _0, _1)]
+    MissingBlockHeader(u32, String),
+
+    #[error("missing block transactions for block number {} with hash {}", _0, _1)]
+    MissingBlockTransactions(u32, String),
+
+    #[error(
+        "block number {} is stored with hash {}, but its header hashes to {}",
+        block_number,
+        stored,
+        computed
+    )]
+    InvalidBlockHash {
+        block_number: u32,
+        stored: String,
+        computed: String,
+    },
+
+    #[error(
+        "block number {} has previous block hash {}, expected {}",
+        block_number,
+        found,
+        expected
+    )]
+    InvalidPreviousBlockHash {
+        block_number: u32,
+        expected: String,
+        found: String,
+    },
+
+    #[error(
+        "block hash {} is located at block number {:?}, expected {}",
+        block_hash,
+        found,
+        expected
+    )]
+    InvalidBlockNumber {
+        block_hash: String,
+        expected: u32,
+        found: Option<u32>,
+    },
+
+    #[error("the best block number is {:?}, expected {}", found, expected)]
+    InvalidBestBlockNumber { expected: u32, found: Option<u32> },
+
+    #[error("transaction {} is located at {:?}, expected {:?}", transaction_id, found, expected)]
+    InvalidTransactionLocation {
+        transaction_id: String,
+        expected: TransactionLocation,
+        found: Option<TransactionLocation>,
+    },
+
+    #[error("{} {} has index {:?}, expected {}", kind, key, found, expected)]
+    InvalidIndex {
+        kind: &'static str,
+        key: String,
+        expected: usize,
+        found: Option<usize>,
+    },
+
+    #[error("{} {} does not belong to any canon transaction", kind, key)]
+    UnexpectedEntry { kind: &'static str, key: String },
+
+    #[error("the current {} is {:?}, expected {}", kind, found, expected)]
+    InvalidCurrentIndex {
+        kind: &'static str,
+        expected: usize,
+        found: Option<usize>,
+    },
+
+    #[error("the current ledger digest is {:?}, expected {}", found, expected)]
+    InvalidCurrentDigest { expected: String, found: Option<String> },
+
+    #[error("missing past ledger digest {}", _0)]
+    MissingPastDigest(String),
+
+    #[error("the commitment merkle tree frontier does not match the commitments")]
+    InvalidCmFrontier,
+
+    #[error("the in-memory commitment merkle tree does not match the commitments")]
+    InvalidCmMerkleTree,
+}
+
+impl LedgerInconsistency {
+    /// Returns true if the inconsistency is in a derived value,
+    /// which can be repaired by `Ledger::repair_integrity`.
+    pub fn is_repairable(&self) -> bool {
+        !matches!(
+            self,
+            LedgerInconsistency::MissingBlockHash(_)
+                | LedgerInconsistency::MissingBlockHeader(..)
+                | LedgerInconsistency::MissingBlockTransactions(..)
+                | LedgerInconsistency::InvalidBlockHash { .. }
+                | LedgerInconsistency::InvalidPreviousBlockHash { .. }
+        )
+    }
+}
+
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> Ledger<T, P, S> {
+    /// Walks the canon chain and returns the inconsistencies between the stored block headers,
+    /// block locators, transaction locations, DPC state indexes and ledger digest.
+    pub fn check_integrity(&self) -> Result<Vec<LedgerInconsistency>, StorageError> {
+        Ok(self
+            .find_inconsistencies()?
+            .into_iter()
+            .map(|(inconsistency, _)| inconsistency)
+            .collect())
+    }
+
+    /// Walks the canon chain, repairs the inconsistent derived values, and returns all
+    /// of the inconsistencies that were found, including the ones that can't be repaired.
+    pub fn repair_integrity(&self) -> Result<Vec<LedgerInconsistency>, StorageError> {
+        let mut inconsistencies = vec![];
+        let mut database_transaction = DatabaseTransaction::new();
+        for (inconsistency, repair_ops) in self.find_inconsistencies()? {
+            database_transaction.push_vec(repair_ops);
+            inconsistencies.push(inconsistency);
+        }
+
+        let mut latest_block_height = self.latest_block_height.write();
+        let mut merkle_tree = self.cm_merkle_tree.write();
+
+        self.storage.batch(database_transaction)?;
+
+        // Reload the in-memory state from the repaired storage.
+        if let Some(best_block_number) = self.storage.get(COL_META, KEY_BEST_BLOCK_NUMBER.as_bytes())? {
+            *latest_block_height = bytes_to_u32(best_block_number);
+        }
+        *merkle_tree = self.build_merkle_tree(vec![])?;
+
+        Ok(inconsistencies)
+    }
+
+    /// Returns the inconsistencies in the ledger, along with the operations that repair them.
+    /// The operations are empty for the inconsistencies that can't be repaired.
+    fn find_inconsistencies(&self) -> Result<Vec<(LedgerInconsistency, Vec<Op>)>, StorageError> {
+        let mut inconsistencies = vec![];
+
+        // Walk the canon chain, as given by the block locators
+
+        let mut block_hashes: Vec<BlockHeaderHash> = vec![];
+        while let Ok(block_hash) = self.get_block_hash(block_hashes.len() as u32) {
+            block_hashes.push(block_hash);
+        }
+
+        if block_hashes.is_empty() {
+            inconsistencies.push((LedgerInconsistency::MissingBlockHash(0), vec![]));
+            return Ok(inconsistencies);
+        }
+
+        let latest_block_number = block_hashes.len() as u32 - 1;
+        let best_block_number = self
+            .storage
+            .get(COL_META, KEY_BEST_BLOCK_NUMBER.as_bytes())?
+            .map(bytes_to_u32);
+
+        // A gap in the block locators can't be repaired from the derived values; the blocks after
+        // the gap are still stored, so the walked chain must not be used to rewrite the ledger state.
+        if let Some(best_block_number) = best_block_number {
+            if best_block_number > latest_block_number {
+                inconsistencies.push((LedgerInconsistency::MissingBlockHash(latest_block_number + 1), vec![]));
+                return Ok(inconsistencies);
+            }
+        }
+
+        if best_block_number != Some(latest_block_number) {
+            inconsistencies.push((
+                LedgerInconsistency::InvalidBestBlockNumber {
+                    expected: latest_block_number,
+                    found: best_block_number,
+                },
+                vec![Op::Insert {
+                    col: COL_META,
+                    key: KEY_BEST_BLOCK_NUMBER.as_bytes().to_vec(),
+                    value: latest_block_number.to_le_bytes().to_vec(),
+                }],
+            ));
+        }
+
+        // Check the block headers and locators, and collect the transactions that have not been pruned
+
+        let pruned_block_number = self.get_pruned_block_number()?;
+        let mut is_complete = true;
+        let mut block_transactions = vec![];
+
+        for (block_number, block_hash) in block_hashes.iter().enumerate() {
+            let block_number = block_number as u32;
+
+            let stored_block_number = self.storage.get(COL_BLOCK_LOCATOR, &block_hash.0)?.map(bytes_to_u32);
+            if stored_block_number != Some(block_number) {
+                inconsistencies.push((
+                    LedgerInconsistency::InvalidBlockNumber {
+                        block_hash: block_hash.to_string(),
+                        expected: block_number,
+                        found: stored_block_number,
+                    },
+                    vec![Op::Insert {
+                        col: COL_BLOCK_LOCATOR,
+                        key: block_hash.0.to_vec(),
+                        value: block_number.to_le_bytes().to_vec(),
+                    }],
+                ));
+            }
+
+            let block_header = match self.storage.get(COL_BLOCK_HEADER, &block_hash.0)? {
+                Some(block_header_bytes) => BlockHeader::read(&block_header_bytes[..])?,
+                None => {
+                    inconsistencies.push((
+                        LedgerInconsistency::MissingBlockHeader(block_number, block_hash.to_string()),
+                        vec![],
+                    ));
+                    continue;
+                }
+            };
+
+            let computed_block_hash = block_header.get_hash();
+            if &computed_block_hash != block_hash {
+                inconsistencies.push((
+                    LedgerInconsistency::InvalidBlockHash {
+                        block_number,
+                        stored: block_hash.to_string(),
+                        computed: computed_block_hash.to_string(),
+                    },
+                    vec![],
+                ));
+            }
+
+            if block_number > 0 && block_header.previous_block_hash != block_hashes[block_number as usize - 1] {
+                inconsistencies.push((
+                    LedgerInconsistency::InvalidPreviousBlockHash {
+                        block_number,
+                        expected: block_hashes[block_number as usize - 1].to_string(),
+                        found: block_header.previous_block_hash.to_string(),
+                    },
+                    vec![],
+                ));
+            }
+
+            if block_number < pruned_block_number {
+                continue;
+            }
+
+            match self.storage.get(COL_BLOCK_TRANSACTIONS, &block_hash.0)? {
+                Some(transactions_bytes) => {
+                    let transactions = DPCTransactions::<T>::read(&transactions_bytes[..])?;
+                    block_transactions.push((block_hash, transactions));
+                }
+                None => {
+                    inconsistencies.push((
+                        LedgerInconsistency::MissingBlockTransactions(block_number, block_hash.to_string()),
+                        vec![],
+                    ));
+                    is_complete = false;
+                }
+            }
+        }
+
+        // Check the transaction locations and the DPC state indexes of the transactions
+
+        let current_indexes = [
+            ("commitment index", KEY_CURR_CM_INDEX, self.current_cm_index()?),
+            ("serial number index", KEY_CURR_SN_INDEX, self.current_sn_index()?),
+            ("memo index", KEY_CURR_MEMO_INDEX, self.current_memo_index()?),
+        ];
+
+        // The indexes of a pruned ledger are counted back from the current indexes.
+        let mut cm_index = 0;
+        let mut sn_index = 0;
+        let mut memo_index = 0;
+        if pruned_block_number > 0 {
+            cm_index = current_indexes[0].2;
+            sn_index = current_indexes[1].2;
+            memo_index = current_indexes[2].2;
+            for (_, transactions) in &block_transactions {
+                for transaction in &transactions.0 {
+                    cm_index = cm_index.saturating_sub(transaction.new_commitments().len());
+                    sn_index = sn_index.saturating_sub(transaction.old_serial_numbers().len());
+                    memo_index = memo_index.saturating_sub(1);
+                }
+            }
+        }
+
+        let mut commitments = vec![];
+        let mut seen_keys = [HashSet::new(), HashSet::new(), HashSet::new()];
+
+        for (block_hash, transactions) in &block_transactions {
+            for (index, transaction) in transactions.0.iter().enumerate() {
+                let transaction_id = transaction.transaction_id()?;
+                let expected_location = TransactionLocation {
+                    index: index as u32,
+                    block_hash: block_hash.0,
+                };
+                let found_location = self.get_transaction_location(&transaction_id)?;
+                if found_location.as_ref() != Some(&expected_location) {
+                    inconsistencies.push((
+                        LedgerInconsistency::InvalidTransactionLocation {
+                            transaction_id: hex::encode(transaction_id),
+                            expected: expected_location.clone(),
+                            found: found_location,
+                        },
+                        vec![Op::Insert {
+                            col: COL_TRANSACTION_LOCATION,
+                            key: transaction_id.to_vec(),
+                            value: to_bytes![expected_location]?.to_vec(),
+                        }],
+                    ));
+                }
+
+                let mut entries = vec![];
+                for cm in transaction.new_commitments() {
+                    entries.push((0, "commitment", COL_COMMITMENT, to_bytes![cm]?, cm_index));
+                    commitments.push(cm.clone());
+                    cm_index += 1;
+                }
+                for sn in transaction.old_serial_numbers() {
+                    entries.push((1, "serial number", COL_SERIAL_NUMBER, to_bytes![sn]?, sn_index));
+                    sn_index += 1;
+                }
+                entries.push((2, "memo", COL_MEMO, to_bytes![transaction.memorandum()]?, memo_index));
+                memo_index += 1;
+
+                for (i, kind, col, key, expected_index) in entries {
+                    let found_index = self.storage.get(col, &key)?.map(|index| bytes_to_u32(index) as usize);
+                    if found_index != Some(expected_index) {
+                        inconsistencies.push((
+                            LedgerInconsistency::InvalidIndex {
+                                kind,
+                                key: hex::encode(&key),
+                                expected: expected_index,
+                                found: found_index,
+                            },
+                            vec![Op::Insert {
+                                col,
+                                key: key.clone(),
+                                value: (expected_index as u32).to_le_bytes().to_vec(),
+                            }],
+                        ));
+                    }
+                    seen_keys[i].insert(key);
+                }
+            }
+        }
+
+        // The DPC state of a pruned or incomplete ledger can't be recomputed from its transactions
+
+        let fully_checked = pruned_block_number == 0 && is_complete;
+        if fully_checked {
+            for (i, &(kind, col)) in [
+                ("commitment", COL_COMMITMENT),
+                ("serial number", COL_SERIAL_NUMBER),
+                ("memo", COL_MEMO),
+            ]
+            .iter()
+            .enumerate()
+            {
+                for key in self.storage.get_keys(col)? {
+                    if !seen_keys[i].contains(&key[..]) {
+                        inconsistencies.push((
+                            LedgerInconsistency::UnexpectedEntry {
+                                kind,
+                                key: hex::encode(&key),
+                            },
+                            vec![Op::Delete { col, key: key.to_vec() }],
+                        ));
+                    }
+                }
+            }
+
+            for (&(kind, key, _), &expected_index) in current_indexes.iter().zip(&[cm_index, sn_index, memo_index]) {
+                let found_index = self
+                    .storage
+                    .get(COL_META, key.as_bytes())?
+                    .map(|index| bytes_to_u32(index) as usize);
+
+                // The current indexes are only stored once the first block is committed.
+                if found_index != Some(expected_index) && !(found_index.is_none() && expected_index == 0) {
+                    inconsistencies.push((
+                        LedgerInconsistency::InvalidCurrentIndex {
+                            kind,
+                            expected: expected_index,
+                            found: found_index,
+                        },
+                        vec![Op::Insert {
+                            col: COL_META,
+                            key: key.as_bytes().to_vec(),
+                            value: (expected_index as u32).to_le_bytes().to_vec(),
+                        }],
+                    ));
+                }
+            }
+        }
+
+        // Check the ledger digest against the commitment merkle tree
+
+        let cm_merkle_tree = match fully_checked {
+            true => MerkleTree::new(self.ledger_parameters.clone(), &commitments)?,
+            false => self.build_merkle_tree(vec![])?,
+        };
+        let digest = to_bytes![cm_merkle_tree.root()]?.to_vec();
+        let cm_frontier = cm_merkle_tree.frontier();
+
+        let stored_digest = self.storage.get(COL_META, KEY_CURR_DIGEST.as_bytes())?;
+        if stored_digest.as_ref() != Some(&digest) {
+            inconsistencies.push((
+                LedgerInconsistency::InvalidCurrentDigest {
+                    expected: hex::encode(&digest),
+                    found: stored_digest.map(hex::encode),
+                },
+                vec![Op::Insert {
+                    col: COL_META,
+                    key: KEY_CURR_DIGEST.as_bytes().to_vec(),
+                    value: digest.clone(),
+                }],
+            ));
+        }
+
+        if !self.storage.exists(COL_DIGEST, &digest) {
+            inconsistencies.push((LedgerInconsistency::MissingPastDigest(hex::encode(&digest)), vec![
+                Op::Insert {
+                    col: COL_DIGEST,
+                    key: digest.clone(),
+                    value: [
+                        latest_block_number.to_le_bytes(),
+                        (cm_merkle_tree.num_leaves() as u32).to_le_bytes(),
+                    ]
+                    .concat(),
+                },
+            ]));
+        }
+
+        let stored_cm_frontier = self.storage.get(COL_META, KEY_CURR_CM_FRONTIER.as_bytes())?;
+        if stored_cm_frontier != Some(to_bytes![cm_frontier]?) {
+            inconsistencies.push((LedgerInconsistency::InvalidCmFrontier, vec![Op::Insert {
+                col: COL_META,
+                key: KEY_CURR_CM_FRONTIER.as_bytes().to_vec(),
+                value: to_bytes![cm_frontier]?.to_vec(),
+            }]));
+        }
+
+        // The in-memory commitment merkle tree is rebuilt during a repair.
+        if self.cm_merkle_tree.read().root() != cm_merkle_tree.root() {
+            inconsistencies.push((LedgerInconsistency::InvalidCmMerkleTree, vec![]));
+        }
+
+        Ok(inconsistencies)
+    }
+}
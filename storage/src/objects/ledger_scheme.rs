@@ -18,7 +18,7 @@ use crate::*;
 use snarkvm_algorithms::{merkle_tree::*, traits::LoadableMerkleParameters};
 use snarkvm_dpc::errors::LedgerError;
 use snarkvm_objects::{
-    traits::{LedgerScheme, Transaction},
+    traits::{LedgerScheme, Storage, Transaction},
     Block,
 };
 use snarkvm_utilities::{
@@ -29,7 +29,7 @@ use snarkvm_utilities::{
 use parking_lot::RwLock;
 use std::{fs, marker::PhantomData, path::Path, sync::Arc};
 
-impl<T: Transaction, P: LoadableMerkleParameters> LedgerScheme for Ledger<T, P> {
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> LedgerScheme for Ledger<T, P, S> {
     type Block = Block<Self::Transaction>;
     type Commitment = T::Commitment;
     type MerkleParameters = P;
@@ -44,18 +44,19 @@ impl<T: Transaction, P: LoadableMerkleParameters> LedgerScheme for Ledger<T, P>
         parameters: Self::MerkleParameters,
         genesis_block: Self::Block,
     ) -> anyhow::Result<Self> {
-        let path = path.unwrap(); // temporarily always present
+        if let Some(path) = path {
+            fs::create_dir_all(path).map_err(|err| LedgerError::Message(err.to_string()))?;
+        }
 
-        fs::create_dir_all(&path).map_err(|err| LedgerError::Message(err.to_string()))?;
-        let storage = match Storage::open_cf(path, NUM_COLS) {
-            Ok(storage) => storage,
-            Err(err) => return Err(err.into()),
-        };
+        let storage = S::open(path, None)?;
 
-        if let Some(block_num) = storage.get(COL_META, KEY_BEST_BLOCK_NUMBER.as_bytes())? {
-            if bytes_to_u32(block_num) != 0 {
-                return Err(LedgerError::ExistingDatabase.into());
+        match storage.get(COL_META, KEY_BEST_BLOCK_NUMBER.as_bytes())? {
+            Some(block_num) => {
+                if bytes_to_u32(block_num) != 0 {
+                    return Err(LedgerError::ExistingDatabase.into());
+                }
             }
+            None => storage.put(COL_META, KEY_SCHEMA_VERSION.as_bytes(), SCHEMA_VERSION.to_le_bytes())?,
         }
 
         let leaves: Vec<[u8; 32]> = vec![];
@@ -66,9 +67,17 @@ impl<T: Transaction, P: LoadableMerkleParameters> LedgerScheme for Ledger<T, P>
             storage: Arc::new(storage),
             cm_merkle_tree: RwLock::new(empty_cm_merkle_tree),
             ledger_parameters: parameters,
+            pruning_mode: PruningMode::Archive,
             _transaction: PhantomData,
         };
 
+        // The network of the ledger is the network of its genesis transactions.
+        if let Some(transaction) = genesis_block.transactions.0.first() {
+            ledger_storage
+                .storage
+                .put(COL_META, KEY_NETWORK_ID.as_bytes(), [transaction.network_id()])?;
+        }
+
         ledger_storage.insert_and_commit(&genesis_block)?;
 
         Ok(ledger_storage)
@@ -84,6 +93,14 @@ impl<T: Transaction, P: LoadableMerkleParameters> LedgerScheme for Ledger<T, P>
         &self.ledger_parameters
     }
 
+    /// Returns the network id of the genesis transactions, if the ledger has any.
+    fn network_id(&self) -> Option<u8> {
+        match self.storage.get(COL_META, KEY_NETWORK_ID.as_bytes()) {
+            Ok(Some(network_id_bytes)) => network_id_bytes.first().copied(),
+            _ => None,
+        }
+    }
+
     /// Return a digest of the latest ledger Merkle tree.
     fn digest(&self) -> Option<Self::MerkleTreeDigest> {
         let digest: Self::MerkleTreeDigest = FromBytes::read(&self.current_digest().unwrap()[..]).unwrap();
@@ -119,6 +136,12 @@ impl<T: Transaction, P: LoadableMerkleParameters> LedgerScheme for Ledger<T, P>
         Ok(result)
     }
 
+    /// Returns the Merkle path to the given past ledger digest
+    /// for a given commitment, if it existed in the ledger at that digest.
+    fn prove_cm_at(&self, cm: &Self::Commitment, digest: &Self::MerkleTreeDigest) -> anyhow::Result<Self::MerklePath> {
+        Ok(Ledger::prove_cm_at(self, cm, digest)?)
+    }
+
     /// Returns true if the given Merkle path is a valid witness for
     /// the given ledger digest and commitment.
     fn verify_cm(
@@ -14,23 +14,135 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{DatabaseTransaction, Ledger, Op, COL_META, KEY_MEMORY_POOL};
+use crate::*;
 use snarkvm_algorithms::traits::LoadableMerkleParameters;
-use snarkvm_objects::{errors::StorageError, traits::Transaction};
+use snarkvm_objects::{
+    errors::StorageError,
+    traits::{Storage, Transaction},
+    AleoAmount,
+    Block,
+    DPCTransactions,
+};
+use snarkvm_utilities::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes,
+};
 
-impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
-    /// Get the stored memory pool transactions.
-    pub fn get_memory_pool(&self) -> Result<Vec<u8>, StorageError> {
-        Ok(self.get(COL_META, &KEY_MEMORY_POOL.as_bytes().to_vec())?)
+use std::cmp::Ordering;
+
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> Ledger<T, P, S> {
+    /// Get a transaction from the memory pool given the transaction id, if it exists.
+    pub fn get_memory_pool_transaction(&self, transaction_id: &[u8]) -> Result<Option<T>, StorageError> {
+        match self.storage.get(COL_MEMORY_POOL, transaction_id)? {
+            Some(transaction_bytes) => Ok(Some(FromBytes::read(&transaction_bytes[..])?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns true if the memory pool contains the transaction with the given transaction id.
+    pub fn memory_pool_contains(&self, transaction_id: &[u8]) -> bool {
+        self.storage.exists(COL_MEMORY_POOL, transaction_id)
+    }
+
+    /// Get all the transactions in the memory pool, in no particular order.
+    pub fn get_memory_pool(&self) -> Result<DPCTransactions<T>, StorageError> {
+        let mut transactions = DPCTransactions::new();
+
+        for (_transaction_id, transaction_bytes) in self.storage.get_col(COL_MEMORY_POOL)? {
+            transactions.push(FromBytes::read(&transaction_bytes[..])?);
+        }
+
+        Ok(transactions)
+    }
+
+    /// Adds a transaction to the memory pool. The transaction is rejected if it conflicts
+    /// with a transaction in the memory pool, or if it conflicts with the ledger.
+    pub fn add_to_memory_pool(&self, transaction: &T) -> Result<(), StorageError> {
+        let transaction_id = transaction.transaction_id()?;
+
+        if self.memory_pool_contains(&transaction_id) {
+            return Err(StorageError::ExistingMemoryPoolTransaction(hex::encode(transaction_id)));
+        }
+
+        if self.get_memory_pool()?.conflicts(transaction) {
+            return Err(StorageError::MemoryPoolConflict(hex::encode(transaction_id)));
+        }
+
+        if self.transcation_conflicts(transaction) {
+            return Err(StorageError::LedgerConflict(hex::encode(transaction_id)));
+        }
+
+        self.storage
+            .put(COL_MEMORY_POOL, transaction_id, to_bytes![transaction]?)
     }
 
-    /// Store the memory pool transactions.
-    pub fn store_to_memory_pool(&self, transactions_serialized: Vec<u8>) -> Result<(), StorageError> {
-        let op = Op::Insert {
-            col: COL_META,
-            key: KEY_MEMORY_POOL.as_bytes().to_vec(),
-            value: transactions_serialized,
-        };
-        self.storage.write(DatabaseTransaction(vec![op]))
+    /// Removes the transaction with the given transaction id from the memory pool.
+    pub fn remove_from_memory_pool(&self, transaction_id: &[u8]) -> Result<(), StorageError> {
+        let mut database_transaction = DatabaseTransaction::new();
+        database_transaction.push(Op::Delete {
+            col: COL_MEMORY_POOL,
+            key: transaction_id.to_vec(),
+        });
+
+        self.storage.batch(database_transaction)
     }
+
+    /// Returns the database operations that evict the transactions in the given block,
+    /// and the transactions that conflict with them, from the memory pool.
+    pub(crate) fn memory_pool_eviction_ops(&self, block: &Block<T>) -> Result<Vec<Op>, StorageError> {
+        let mut ops = vec![];
+
+        for (transaction_id, transaction_bytes) in self.storage.get_col(COL_MEMORY_POOL)? {
+            let transaction: T = FromBytes::read(&transaction_bytes[..])?;
+
+            if block.transactions.conflicts(&transaction) {
+                ops.push(Op::Delete {
+                    col: COL_MEMORY_POOL,
+                    key: transaction_id.to_vec(),
+                });
+            }
+        }
+
+        Ok(ops)
+    }
+}
+
+impl<T: Transaction<ValueBalance = AleoAmount>, P: LoadableMerkleParameters, S: Storage> Ledger<T, P, S> {
+    /// Get the transactions in the memory pool, ordered by decreasing fee per byte.
+    pub fn get_memory_pool_by_fee(&self) -> Result<Vec<T>, StorageError> {
+        let mut entries = vec![];
+        for transaction in self.get_memory_pool()?.0 {
+            let transaction_id = transaction.transaction_id()?;
+            entries.push((transaction_id, transaction));
+        }
+
+        entries.sort_by(|(id_a, a), (id_b, b)| compare_fee_per_byte(b, a).then_with(|| id_a.cmp(id_b)));
+
+        Ok(entries.into_iter().map(|(_, transaction)| transaction).collect())
+    }
+
+    /// Get the transactions with the highest fee per byte from the memory pool,
+    /// whose combined size does not exceed the given maximum size in bytes.
+    pub fn get_memory_pool_candidates(&self, max_size: usize) -> Result<Vec<T>, StorageError> {
+        let mut size = 0;
+        let mut candidates = vec![];
+
+        for transaction in self.get_memory_pool_by_fee()? {
+            if size + transaction.size() <= max_size {
+                size += transaction.size();
+                candidates.push(transaction);
+            }
+        }
+
+        Ok(candidates)
+    }
+}
+
+/// Compares the fee per byte of the given transactions, where the fee of a transaction is its value balance.
+fn compare_fee_per_byte<T: Transaction<ValueBalance = AleoAmount>>(a: &T, b: &T) -> Ordering {
+    // Compare fee_a / size_a with fee_b / size_b without dividing.
+    let fee_a = a.value_balance().0 as i128 * b.size() as i128;
+    let fee_b = b.value_balance().0 as i128 * a.size() as i128;
+
+    fee_a.cmp(&fee_b)
 }
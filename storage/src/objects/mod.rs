@@ -30,14 +30,26 @@ pub use dpc_state::*;
 pub mod insert_commit;
 pub use insert_commit::*;
 
+pub mod integrity;
+pub use integrity::*;
+
 pub mod ledger_scheme;
 pub use ledger_scheme::*;
 
 pub mod memory_pool;
 pub use memory_pool::*;
 
+pub mod pruning;
+pub use pruning::*;
+
 pub mod records;
 pub use records::*;
 
+pub mod schema;
+pub use schema::*;
+
+pub mod snapshot;
+pub use snapshot::*;
+
 pub mod transaction;
 pub use transaction::*;
@@ -0,0 +1,125 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+use snarkvm_algorithms::traits::LoadableMerkleParameters;
+use snarkvm_objects::{
+    errors::StorageError,
+    traits::{Storage, Transaction},
+    BlockHeaderHash,
+};
+
+/// The pruning mode of a `Ledger`, which determines the blocks whose transactions are kept.
+/// The block headers and the DPC state (commitments, serial numbers, memos and digests)
+/// are always kept, regardless of the pruning mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PruningMode {
+    /// Keep the transactions of every block.
+    Archive,
+    /// Keep the transactions of only the given number of latest blocks.
+    /// The transactions of the latest block are always kept.
+    ///
+    /// A block is decommitted with its transactions, so the ledger can only be reverted
+    /// to a block whose successors are all within the pruning window.
+    KeepRecent(u32),
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for PruningMode {
+    fn default() -> Self {
+        PruningMode::Archive
+    }
+}
+
+impl PruningMode {
+    /// Returns the lowest block number whose transactions are kept, given the latest block number.
+    pub fn first_retained_block_number(&self, latest_block_number: u32) -> u32 {
+        match self {
+            PruningMode::Archive => 0,
+            PruningMode::KeepRecent(num_blocks) => (latest_block_number + 1).saturating_sub((*num_blocks).max(1)),
+        }
+    }
+}
+
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> Ledger<T, P, S> {
+    /// Get the lowest block number whose transactions have not been pruned.
+    pub fn get_pruned_block_number(&self) -> Result<u32, StorageError> {
+        match self.storage.get(COL_META, KEY_PRUNED_BLOCK_NUMBER.as_bytes())? {
+            Some(block_num_bytes) => Ok(bytes_to_u32(block_num_bytes)),
+            None => Ok(0),
+        }
+    }
+
+    /// Returns true if the transactions of the given canon block have been pruned.
+    pub fn is_pruned(&self, block_hash: &BlockHeaderHash) -> Result<bool, StorageError> {
+        match self.storage.get(COL_BLOCK_LOCATOR, &block_hash.0)? {
+            Some(block_num_bytes) => Ok(bytes_to_u32(block_num_bytes) < self.get_pruned_block_number()?),
+            None => Ok(false),
+        }
+    }
+
+    /// Returns an error if the canon blocks after the given block number can't be decommitted,
+    /// because the transactions of some of them have been pruned.
+    pub fn check_revert(&self, block_number: u32) -> Result<(), StorageError> {
+        let pruned_block_number = self.get_pruned_block_number()?;
+
+        match block_number.saturating_add(1) < pruned_block_number {
+            true => Err(StorageError::PrunedRevert(block_number, pruned_block_number)),
+            false => Ok(()),
+        }
+    }
+
+    /// Prune the transactions of the canon blocks that have fallen out of the pruning window.
+    pub fn prune_blocks(&self) -> Result<(), StorageError> {
+        let prune_ops = self.prune_ops(self.get_latest_block_height())?;
+
+        match prune_ops.is_empty() {
+            true => Ok(()),
+            false => self.storage.batch(DatabaseTransaction(prune_ops)),
+        }
+    }
+
+    /// Returns the operations that prune the transactions of the canon blocks that
+    /// fall out of the pruning window, once the given block number is the latest one.
+    pub(crate) fn prune_ops(&self, latest_block_number: u32) -> Result<Vec<Op>, StorageError> {
+        let pruned_block_number = self.get_pruned_block_number()?;
+        let new_pruned_block_number = self.pruning_mode.first_retained_block_number(latest_block_number);
+
+        if new_pruned_block_number <= pruned_block_number {
+            return Ok(vec![]);
+        }
+
+        let mut ops = Vec::with_capacity((new_pruned_block_number - pruned_block_number) as usize + 1);
+
+        // The transaction locations are kept, so that pruned transactions are still known to the ledger.
+        for block_number in pruned_block_number..new_pruned_block_number {
+            let block_hash = self.get_block_hash(block_number)?;
+
+            ops.push(Op::Delete {
+                col: COL_BLOCK_TRANSACTIONS,
+                key: block_hash.0.to_vec(),
+            });
+        }
+
+        ops.push(Op::Insert {
+            col: COL_META,
+            key: KEY_PRUNED_BLOCK_NUMBER.as_bytes().to_vec(),
+            value: new_pruned_block_number.to_le_bytes().to_vec(),
+        });
+
+        Ok(ops)
+    }
+}
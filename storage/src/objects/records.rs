@@ -17,7 +17,10 @@
 use crate::*;
 use snarkvm_algorithms::traits::LoadableMerkleParameters;
 use snarkvm_dpc::traits::Record;
-use snarkvm_objects::{errors::StorageError, traits::Transaction};
+use snarkvm_objects::{
+    errors::StorageError,
+    traits::{Storage, Transaction},
+};
 use snarkvm_utilities::{
     bytes::{FromBytes, ToBytes},
     to_bytes,
@@ -25,12 +28,12 @@ use snarkvm_utilities::{
 
 // TODO (howardwu): Remove this from `Ledger` as it is not used for ledger state.
 //  This is merely for local node / miner functionality.
-impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> Ledger<T, P, S> {
     /// Get all stored record commitments of the node
     pub fn get_record_commitments(&self, limit: Option<usize>) -> Result<Vec<Vec<u8>>, StorageError> {
         let mut record_commitments = vec![];
 
-        for (commitment_key, _record) in self.storage.get_iter(COL_RECORDS) {
+        for (commitment_key, _record) in self.storage.get_col(COL_RECORDS)? {
             if let Some(limit) = limit {
                 if record_commitments.len() >= limit {
                     break;
@@ -55,42 +58,320 @@ impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
     }
 
     /// Get a transaction bytes given the transaction id.
-    pub fn store_record<R: Record>(&self, record: &R) -> Result<(), StorageError> {
+    pub fn store_record<R: Record>(&self, record: &R) -> Result<(), StorageError>
+    where
+        R::Owner: ToBytes,
+    {
+        self.store_records(std::slice::from_ref(record))
+    }
+
+    /// Get a transaction bytes given the transaction id.
+    pub fn store_records<R: Record>(&self, records: &[R]) -> Result<(), StorageError>
+    where
+        R::Owner: ToBytes,
+    {
+        let mut database_transaction = DatabaseTransaction::new();
+
+        for record in records {
+            database_transaction.push_vec(Self::store_record_ops(record)?);
+        }
+
+        self.storage.batch(database_transaction)
+    }
+
+    /// Stores a record along with its serial number, which allows querying whether the record is spent.
+    pub fn store_record_with_serial_number<R: Record>(
+        &self,
+        record: &R,
+        serial_number: &R::SerialNumber,
+    ) -> Result<(), StorageError>
+    where
+        R::Owner: ToBytes,
+    {
         let mut database_transaction = DatabaseTransaction::new();
 
+        database_transaction.push_vec(Self::store_record_ops(record)?);
         database_transaction.push(Op::Insert {
-            col: COL_RECORDS,
+            col: COL_RECORD_SERIAL_NUMBER,
             key: to_bytes![record.commitment()]?.to_vec(),
-            value: to_bytes![record]?.to_vec(),
+            value: to_bytes![serial_number]?.to_vec(),
         });
 
-        self.storage.write(database_transaction)
+        self.storage.batch(database_transaction)
     }
 
-    /// Get a transaction bytes given the transaction id.
-    pub fn store_records<R: Record>(&self, records: &[R]) -> Result<(), StorageError> {
+    /// Removes a record from storage.
+    pub fn delete_record<R: Record>(&self, record: R) -> Result<(), StorageError>
+    where
+        R::Owner: ToBytes,
+    {
         let mut database_transaction = DatabaseTransaction::new();
 
-        for record in records {
-            database_transaction.push(Op::Insert {
-                col: COL_RECORDS,
-                key: to_bytes![record.commitment()]?.to_vec(),
-                value: to_bytes![record]?.to_vec(),
-            });
+        let commitment = to_bytes![record.commitment()]?.to_vec();
+
+        for (col, key) in Self::record_index_keys(&record, &commitment)? {
+            database_transaction.push(Op::Delete { col, key });
         }
 
-        self.storage.write(database_transaction)
+        database_transaction.push(Op::Delete {
+            col: COL_RECORD_SERIAL_NUMBER,
+            key: commitment.clone(),
+        });
+
+        database_transaction.push(Op::Delete {
+            col: COL_RECORDS,
+            key: commitment,
+        });
+
+        self.storage.batch(database_transaction)
     }
 
-    /// Removes a record from storage.
-    pub fn delete_record<R: Record>(&self, record: R) -> Result<(), StorageError> {
+    /// Rebuilds the secondary record indexes from the stored records.
+    pub fn reindex_records<R: Record>(&self) -> Result<(), StorageError>
+    where
+        R::Owner: ToBytes,
+    {
         let mut database_transaction = DatabaseTransaction::new();
 
+        for col in &[
+            COL_RECORDS_BY_OWNER,
+            COL_RECORDS_BY_BIRTH_PROGRAM_ID,
+            COL_RECORDS_BY_DEATH_PROGRAM_ID,
+        ] {
+            for key in self.storage.get_keys(*col)? {
+                database_transaction.push(Op::Delete {
+                    col: *col,
+                    key: key.to_vec(),
+                });
+            }
+        }
+
+        for (commitment, record_bytes) in self.storage.get_col(COL_RECORDS)? {
+            let record: R = FromBytes::read(&record_bytes[..])?;
+
+            for (col, key) in Self::record_index_keys(&record, &commitment)? {
+                database_transaction.push(Op::Insert {
+                    col,
+                    key,
+                    value: vec![],
+                });
+            }
+        }
+
         database_transaction.push(Op::Delete {
-            col: COL_RECORDS,
-            key: to_bytes![record.commitment()]?.to_vec(),
+            col: COL_META,
+            key: KEY_RECORDS_UNINDEXED.as_bytes().to_vec(),
         });
 
-        self.storage.write(database_transaction)
+        self.storage.batch(database_transaction)
+    }
+
+    /// Get a page of the stored records owned by the given account address.
+    pub fn get_records_by_owner<R: Record>(
+        &self,
+        owner: &R::Owner,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Vec<R>, StorageError>
+    where
+        R::Owner: ToBytes,
+    {
+        self.backfill_record_indexes::<R>()?;
+        let commitments = self.get_indexed_record_commitments(COL_RECORDS_BY_OWNER, &to_bytes![owner]?)?;
+
+        self.get_records_page(commitments.into_iter(), offset, limit)
+    }
+
+    /// Get a page of the stored records with the given birth program id.
+    pub fn get_records_by_birth_program_id<R: Record>(
+        &self,
+        birth_program_id: &[u8],
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Vec<R>, StorageError>
+    where
+        R::Owner: ToBytes,
+    {
+        self.backfill_record_indexes::<R>()?;
+        let commitments = self.get_indexed_record_commitments(COL_RECORDS_BY_BIRTH_PROGRAM_ID, birth_program_id)?;
+
+        self.get_records_page(commitments.into_iter(), offset, limit)
+    }
+
+    /// Get a page of the stored records with the given death program id.
+    pub fn get_records_by_death_program_id<R: Record>(
+        &self,
+        death_program_id: &[u8],
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Vec<R>, StorageError>
+    where
+        R::Owner: ToBytes,
+    {
+        self.backfill_record_indexes::<R>()?;
+        let commitments = self.get_indexed_record_commitments(COL_RECORDS_BY_DEATH_PROGRAM_ID, death_program_id)?;
+
+        self.get_records_page(commitments.into_iter(), offset, limit)
     }
+
+    /// Returns whether the record with the given commitment is spent on the ledger,
+    /// or `None` if the serial number of the record is not stored.
+    pub fn is_record_spent(&self, record_commitment: &[u8]) -> Result<Option<bool>, StorageError> {
+        match self.storage.get(COL_RECORD_SERIAL_NUMBER, record_commitment)? {
+            Some(serial_number) => Ok(Some(self.storage.exists(COL_SERIAL_NUMBER, &serial_number))),
+            None => Ok(None),
+        }
+    }
+
+    /// Get a page of the stored records that are spent on the ledger.
+    /// Only records stored with their serial number are considered.
+    pub fn get_spent_records<R: Record>(&self, offset: usize, limit: Option<usize>) -> Result<Vec<R>, StorageError> {
+        let commitments = self.get_record_commitments_by_status(true)?;
+
+        self.get_records_page(commitments.into_iter(), offset, limit)
+    }
+
+    /// Get a page of the stored records that are not yet spent on the ledger.
+    /// Only records stored with their serial number are considered.
+    pub fn get_unspent_records<R: Record>(&self, offset: usize, limit: Option<usize>) -> Result<Vec<R>, StorageError> {
+        let commitments = self.get_record_commitments_by_status(false)?;
+
+        self.get_records_page(commitments.into_iter(), offset, limit)
+    }
+
+    /// Get a page of the stored records owned by the given account address that are not yet spent on the ledger.
+    /// Only records stored with their serial number are considered.
+    pub fn get_unspent_records_by_owner<R: Record>(
+        &self,
+        owner: &R::Owner,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Vec<R>, StorageError>
+    where
+        R::Owner: ToBytes,
+    {
+        self.backfill_record_indexes::<R>()?;
+
+        let mut unspent_commitments = vec![];
+        for commitment in self.get_indexed_record_commitments(COL_RECORDS_BY_OWNER, &to_bytes![owner]?)? {
+            if self.is_record_spent(&commitment)? == Some(false) {
+                unspent_commitments.push(commitment);
+            }
+        }
+
+        self.get_records_page(unspent_commitments.into_iter(), offset, limit)
+    }
+
+    /// Returns the operations that store the given record and its secondary index keys.
+    fn store_record_ops<R: Record>(record: &R) -> Result<Vec<Op>, StorageError>
+    where
+        R::Owner: ToBytes,
+    {
+        let commitment = to_bytes![record.commitment()]?.to_vec();
+
+        let mut ops = vec![Op::Insert {
+            col: COL_RECORDS,
+            key: commitment.clone(),
+            value: to_bytes![record]?.to_vec(),
+        }];
+        for (col, key) in Self::record_index_keys(record, &commitment)? {
+            ops.push(Op::Insert {
+                col,
+                key,
+                value: vec![],
+            });
+        }
+
+        Ok(ops)
+    }
+
+    /// Builds the secondary record indexes if the records were stored before the indexes
+    /// were introduced, as marked by the schema migration.
+    fn backfill_record_indexes<R: Record>(&self) -> Result<(), StorageError>
+    where
+        R::Owner: ToBytes,
+    {
+        match self.storage.exists(COL_META, KEY_RECORDS_UNINDEXED.as_bytes()) {
+            true => self.reindex_records::<R>(),
+            false => Ok(()),
+        }
+    }
+
+    /// Returns the secondary index keys of the given record.
+    fn record_index_keys<R: Record>(record: &R, commitment: &[u8]) -> Result<Vec<(u32, Vec<u8>)>, StorageError>
+    where
+        R::Owner: ToBytes,
+    {
+        Ok(vec![
+            (
+                COL_RECORDS_BY_OWNER,
+                record_index_key(&to_bytes![record.owner()]?, commitment),
+            ),
+            (
+                COL_RECORDS_BY_BIRTH_PROGRAM_ID,
+                record_index_key(record.birth_program_id(), commitment),
+            ),
+            (
+                COL_RECORDS_BY_DEATH_PROGRAM_ID,
+                record_index_key(record.death_program_id(), commitment),
+            ),
+        ])
+    }
+
+    /// Returns the commitments of the records with the given index value, in a stable order.
+    fn get_indexed_record_commitments(&self, col: u32, index: &[u8]) -> Result<Vec<Vec<u8>>, StorageError> {
+        let prefix = record_index_key(index, &[]);
+
+        // The keys are ordered by the commitment that follows the prefix.
+        Ok(self
+            .storage
+            .get_keys_with_prefix(col, &prefix)?
+            .into_iter()
+            .map(|key| key[prefix.len()..].to_vec())
+            .collect())
+    }
+
+    /// Returns the commitments of the records with a stored serial number and the given spent status,
+    /// in a stable order.
+    fn get_record_commitments_by_status(&self, spent: bool) -> Result<Vec<Vec<u8>>, StorageError> {
+        let mut commitments = self
+            .storage
+            .get_col(COL_RECORD_SERIAL_NUMBER)?
+            .into_iter()
+            .filter(|(_, serial_number)| self.storage.exists(COL_SERIAL_NUMBER, serial_number) == spent)
+            .map(|(commitment, _)| commitment.to_vec())
+            .collect::<Vec<_>>();
+        commitments.sort();
+
+        Ok(commitments)
+    }
+
+    /// Returns the stored records for a page of the given commitments.
+    fn get_records_page<R: Record>(
+        &self,
+        commitments: impl Iterator<Item = Vec<u8>>,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Vec<R>, StorageError> {
+        let mut records = vec![];
+
+        for commitment in commitments.skip(offset).take(limit.unwrap_or(usize::MAX)) {
+            match self.get_record(&commitment)? {
+                Some(record) => records.push(record),
+                None => return Err(StorageError::MissingRecord(hex::encode(&commitment))),
+            }
+        }
+
+        Ok(records)
+    }
+}
+
+/// Returns the key of a record in a secondary index, which is the length-prefixed index value
+/// followed by the record commitment.
+fn record_index_key(index: &[u8], commitment: &[u8]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(4 + index.len() + commitment.len());
+    key.extend_from_slice(&(index.len() as u32).to_le_bytes());
+    key.extend_from_slice(index);
+    key.extend_from_slice(commitment);
+    key
 }
@@ -0,0 +1,206 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+use snarkvm_algorithms::{merkle_tree::MerkleTree, traits::LoadableMerkleParameters};
+use snarkvm_objects::{
+    errors::StorageError,
+    traits::{Storage, Transaction},
+    DPCTransactions,
+};
+use snarkvm_utilities::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes,
+};
+
+/// A migration of the ledger storage from a schema version to the next one,
+/// which returns the database transaction that applies the migration.
+pub type Migration<S, P> = fn(&S, &P) -> Result<DatabaseTransaction, StorageError>;
+
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> Ledger<T, P, S> {
+    /// Returns the schema version of the given storage, if it contains a ledger.
+    /// A ledger without a stored schema version predates the schema versions, and has schema version 1.
+    pub fn get_schema_version(storage: &S) -> Result<Option<u32>, StorageError> {
+        match storage.get(COL_META, KEY_SCHEMA_VERSION.as_bytes())? {
+            Some(schema_version_bytes) => Ok(Some(bytes_to_u32(schema_version_bytes))),
+            None => match storage.exists(COL_META, KEY_BEST_BLOCK_NUMBER.as_bytes()) {
+                true => Ok(Some(1)),
+                false => Ok(None),
+            },
+        }
+    }
+
+    /// Returns the migration from the given schema version to the next one.
+    pub fn get_migration(schema_version: u32) -> Option<Migration<S, P>> {
+        match schema_version {
+            1 => Some(Self::migrate_v1_to_v2),
+            2 => Some(Self::migrate_v2_to_v3),
+            3 => Some(Self::migrate_v3_to_v4),
+            4 => Some(Self::migrate_v4_to_v5),
+            _ => None,
+        }
+    }
+
+    /// Brings the schema of the given storage to the current schema version, by applying
+    /// each migration in its own database transaction. A ledger with a newer schema version,
+    /// or an outdated ledger that can't be migrated, is refused.
+    pub(crate) fn migrate_schema(storage: &S, parameters: &P, primary: bool) -> Result<(), StorageError> {
+        let mut schema_version = match Self::get_schema_version(storage)? {
+            Some(schema_version) => schema_version,
+            None => return Ok(()),
+        };
+
+        if schema_version > SCHEMA_VERSION {
+            return Err(StorageError::UnsupportedSchemaVersion(schema_version, SCHEMA_VERSION));
+        }
+
+        // A secondary instance is read-only, so the primary instance has to migrate the ledger.
+        if schema_version < SCHEMA_VERSION && !primary {
+            return Err(StorageError::OutdatedSchemaVersion(schema_version, SCHEMA_VERSION));
+        }
+
+        while schema_version < SCHEMA_VERSION {
+            let migration = Self::get_migration(schema_version)
+                .ok_or(StorageError::OutdatedSchemaVersion(schema_version, SCHEMA_VERSION))?;
+
+            let mut database_transaction = migration(storage, parameters)?;
+            database_transaction.push(Op::Insert {
+                col: COL_META,
+                key: KEY_SCHEMA_VERSION.as_bytes().to_vec(),
+                value: (schema_version + 1).to_le_bytes().to_vec(),
+            });
+            storage.batch(database_transaction)?;
+
+            schema_version += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Migrates the ledger from schema version 1 to schema version 2, which stores the
+    /// commitment index of each past ledger digest and the commitment merkle tree frontier.
+    fn migrate_v1_to_v2(storage: &S, parameters: &P) -> Result<DatabaseTransaction, StorageError> {
+        let mut database_transaction = DatabaseTransaction::new();
+
+        // Count the commitments up to each canon block.
+        let mut cm_indexes = vec![];
+        let mut cm_index = 0;
+        while let Some(block_hash) = storage.get(COL_BLOCK_LOCATOR, &(cm_indexes.len() as u32).to_le_bytes())? {
+            let transactions = match storage.get(COL_BLOCK_TRANSACTIONS, &block_hash)? {
+                Some(transactions_bytes) => DPCTransactions::<T>::read(&transactions_bytes[..])?,
+                None => return Err(StorageError::MissingBlockTransactions(hex::encode(&block_hash))),
+            };
+
+            for transaction in transactions.0 {
+                cm_index += transaction.new_commitments().len();
+            }
+            cm_indexes.push(cm_index as u32);
+        }
+
+        for (digest, digest_value) in storage.get_col(COL_DIGEST)? {
+            let block_number = Self::digest_block_number(&digest, &digest_value)?;
+
+            match cm_indexes.get(block_number as usize) {
+                Some(cm_index) => database_transaction.push(Op::Insert {
+                    col: COL_DIGEST,
+                    key: digest.to_vec(),
+                    value: [block_number.to_le_bytes(), cm_index.to_le_bytes()].concat(),
+                }),
+                // The digest is left over from a block that is no longer canon.
+                None => database_transaction.push(Op::Delete {
+                    col: COL_DIGEST,
+                    key: digest.to_vec(),
+                }),
+            }
+        }
+
+        let mut cm_and_indices = vec![];
+        for (commitment_key, index_value) in storage.get_col(COL_COMMITMENT)? {
+            let commitment: T::Commitment = FromBytes::read(&commitment_key[..])?;
+            let index = bytes_to_u32(index_value.to_vec()) as usize;
+
+            cm_and_indices.push((commitment, index));
+        }
+
+        cm_and_indices.sort_by_key(|&(_, i)| i);
+        let commitments = cm_and_indices.into_iter().map(|(cm, _)| cm).collect::<Vec<_>>();
+
+        let merkle_tree = MerkleTree::new(parameters.clone(), &commitments)?;
+
+        database_transaction.push(Op::Insert {
+            col: COL_META,
+            key: KEY_CURR_CM_FRONTIER.as_bytes().to_vec(),
+            value: to_bytes![merkle_tree.frontier()]?.to_vec(),
+        });
+
+        Ok(database_transaction)
+    }
+
+    /// Migrates the ledger from schema version 2 to schema version 3, which stores the memory pool
+    /// transactions individually. The opaque memory pool bytes of earlier versions are dropped.
+    fn migrate_v2_to_v3(_storage: &S, _parameters: &P) -> Result<DatabaseTransaction, StorageError> {
+        let mut database_transaction = DatabaseTransaction::new();
+
+        database_transaction.push(Op::Delete {
+            col: COL_META,
+            key: KEY_MEMORY_POOL.as_bytes().to_vec(),
+        });
+
+        Ok(database_transaction)
+    }
+
+    /// Migrates the ledger from schema version 3 to schema version 4, which indexes the stored records.
+    /// The ledger doesn't know the type of its stored records, so the indexes are backfilled
+    /// by the first record query, which calls `reindex_records` with the record type.
+    fn migrate_v3_to_v4(storage: &S, _parameters: &P) -> Result<DatabaseTransaction, StorageError> {
+        let mut database_transaction = DatabaseTransaction::new();
+
+        if !storage.get_keys(COL_RECORDS)?.is_empty() {
+            database_transaction.push(Op::Insert {
+                col: COL_META,
+                key: KEY_RECORDS_UNINDEXED.as_bytes().to_vec(),
+                value: vec![],
+            });
+        }
+
+        Ok(database_transaction)
+    }
+
+    /// Migrates the ledger from schema version 4 to schema version 5, which stores the network id
+    /// of the genesis transactions. It is left unknown if the genesis transactions are pruned.
+    fn migrate_v4_to_v5(storage: &S, _parameters: &P) -> Result<DatabaseTransaction, StorageError> {
+        let mut database_transaction = DatabaseTransaction::new();
+
+        let genesis_transactions_bytes = match storage.get(COL_BLOCK_LOCATOR, &0u32.to_le_bytes())? {
+            Some(genesis_block_hash) => storage.get(COL_BLOCK_TRANSACTIONS, &genesis_block_hash)?,
+            None => None,
+        };
+
+        if let Some(genesis_transactions_bytes) = genesis_transactions_bytes {
+            let genesis_transactions = DPCTransactions::<T>::read(&genesis_transactions_bytes[..])?;
+
+            if let Some(transaction) = genesis_transactions.0.first() {
+                database_transaction.push(Op::Insert {
+                    col: COL_META,
+                    key: KEY_NETWORK_ID.as_bytes().to_vec(),
+                    value: vec![transaction.network_id()],
+                });
+            }
+        }
+
+        Ok(database_transaction)
+    }
+}
@@ -0,0 +1,493 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+use snarkvm_algorithms::{
+    merkle_tree::{MerkleFrontier, MerkleTree, MerkleTreeDigest},
+    traits::LoadableMerkleParameters,
+};
+use snarkvm_objects::{
+    errors::{BlockError, StorageError},
+    merkle_root,
+    traits::{Storage, Transaction},
+    BlockHeader,
+    BlockHeaderHash,
+    DPCTransactions,
+    MerkleRootHash,
+};
+use snarkvm_utilities::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes,
+};
+
+use sha2::{Digest, Sha256};
+use std::io::{Read, Result as IoResult, Write};
+
+/// The magic bytes at the start of a ledger snapshot.
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"LEDGSNAP";
+
+/// The version of the ledger snapshot format.
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+/// The maximum size of a single key or value in a ledger snapshot.
+const MAX_SNAPSHOT_ENTRY_SIZE: u32 = 1 << 16;
+
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> Ledger<T, P, S> {
+    /// Writes a snapshot of the ledger at the given block height, consisting of the block headers,
+    /// the transactions of the block at the given block height, the DPC state (commitments,
+    /// serial numbers, memos and digests) and the commitment merkle tree frontier,
+    /// followed by a SHA-256 checksum of the snapshot.
+    ///
+    /// The transactions of the blocks from the given block height must not have been pruned.
+    pub fn export_snapshot<W: Write>(&self, writer: W, block_height: u32) -> Result<(), StorageError> {
+        let latest_block_height = self.get_latest_block_height();
+        if block_height > latest_block_height {
+            return Err(StorageError::BlockError(BlockError::InvalidBlockNumber(block_height)));
+        }
+
+        // Compute the DPC state indexes at the given block height, by rolling back the later blocks.
+        let mut cm_index = self.current_cm_index()?;
+        let mut sn_index = self.current_sn_index()?;
+        let mut memo_index = self.current_memo_index()?;
+
+        for block_number in block_height + 1..=latest_block_height {
+            let block_hash = self.get_block_hash(block_number)?;
+
+            let inconsistent_index = || {
+                StorageError::Message(format!(
+                    "DPC state indexes are inconsistent with block {}",
+                    block_number
+                ))
+            };
+            for transaction in self.get_block_transactions(&block_hash)?.0 {
+                cm_index = cm_index
+                    .checked_sub(transaction.new_commitments().len())
+                    .ok_or_else(inconsistent_index)?;
+                sn_index = sn_index
+                    .checked_sub(transaction.old_serial_numbers().len())
+                    .ok_or_else(inconsistent_index)?;
+                memo_index = memo_index.checked_sub(1).ok_or_else(inconsistent_index)?;
+            }
+        }
+
+        let cm_frontier = self.cm_merkle_tree.read().frontier_at(cm_index)?;
+
+        let mut writer = ChecksumWriter::new(writer);
+
+        writer.write_all(&SNAPSHOT_MAGIC)?;
+        SNAPSHOT_VERSION.write(&mut writer)?;
+        block_height.write(&mut writer)?;
+        self.get_block_hash(block_height)?.0.write(&mut writer)?;
+
+        // Write the block headers of the canon chain
+
+        for block_number in 0..=block_height {
+            let block_hash = self.get_block_hash(block_number)?;
+            self.get_block_header(&block_hash)?.write(&mut writer)?;
+        }
+
+        // Write the transactions of the block at the given block height
+
+        let block_hash = self.get_block_hash(block_height)?;
+        self.get_block_transactions(&block_hash)?.write(&mut writer)?;
+
+        // Write the DPC state, with the commitments, serial numbers and memos ordered by their index
+
+        for &(col, num_entries) in &[
+            (COL_COMMITMENT, cm_index),
+            (COL_SERIAL_NUMBER, sn_index),
+            (COL_MEMO, memo_index),
+        ] {
+            let mut keys = vec![None; num_entries];
+            for (key, index_value) in self.storage.get_col(col)? {
+                let index = bytes_to_u32(index_value.to_vec()) as usize;
+                if index < num_entries {
+                    keys[index] = Some(key);
+                }
+            }
+
+            (num_entries as u32).write(&mut writer)?;
+            for (index, key) in keys.into_iter().enumerate() {
+                match key {
+                    Some(key) => write_entry(&mut writer, &key)?,
+                    None => return Err(StorageError::MissingValue(format!("index {} of column {}", index, col))),
+                }
+            }
+        }
+
+        // The digests are stored with the latest block number at which they were current,
+        // so the current digest at the given block height is attributed to that block height.
+        let current_digest = to_bytes![cm_frontier.root(&self.ledger_parameters)?]?.to_vec();
+        let current_digest_value = [block_height.to_le_bytes(), (cm_index as u32).to_le_bytes()].concat();
+
+        let mut digests = vec![(
+            current_digest.clone().into_boxed_slice(),
+            current_digest_value.into_boxed_slice(),
+        )];
+        for (digest, digest_value) in self.storage.get_col(COL_DIGEST)? {
+            if digest[..] != current_digest[..] && Self::digest_block_number(&digest, &digest_value)? <= block_height {
+                digests.push((digest, digest_value));
+            }
+        }
+
+        (digests.len() as u32).write(&mut writer)?;
+        for (digest, digest_value) in digests {
+            write_entry(&mut writer, &digest)?;
+            write_entry(&mut writer, &digest_value)?;
+        }
+
+        cm_frontier.write(&mut writer)?;
+
+        let (mut writer, checksum) = writer.finalize();
+        writer.write_all(&checksum)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Imports a snapshot of a ledger, which was written by `export_snapshot`, into a ledger
+    /// that only contains the genesis block. The snapshot is verified against its checksum,
+    /// and its block headers must form a chain from the genesis block to the given block hash.
+    ///
+    /// The checksum only detects corrupted snapshots. The block headers don't commit to the ledger
+    /// state, so the snapshot is authenticated by the trusted block hash and ledger digest at that
+    /// block: the transactions of the block must match the merkle root of its header, and the
+    /// commitments must match the given ledger digest.
+    ///
+    /// The transactions of the earlier blocks are not part of the snapshot, and are thus regarded as pruned.
+    pub fn import_snapshot<R: Read>(
+        &self,
+        reader: R,
+        block_hash: &BlockHeaderHash,
+        digest: &MerkleTreeDigest<P>,
+    ) -> Result<(), StorageError> {
+        if self.get_latest_block_height() != 0 {
+            return Err(StorageError::InvalidSnapshot(
+                "the ledger must only contain the genesis block".into(),
+            ));
+        }
+
+        let mut reader = ChecksumReader::new(reader);
+
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if magic != SNAPSHOT_MAGIC {
+            return Err(StorageError::InvalidSnapshot("unknown format".into()));
+        }
+
+        let version = u32::read(&mut reader)?;
+        if version != SNAPSHOT_VERSION {
+            return Err(StorageError::InvalidSnapshot(format!(
+                "unsupported version {}",
+                version
+            )));
+        }
+
+        let block_height = u32::read(&mut reader)?;
+        let snapshot_block_hash = BlockHeaderHash(<[u8; 32]>::read(&mut reader)?);
+        if &snapshot_block_hash != block_hash {
+            return Err(StorageError::InvalidSnapshot(format!(
+                "expected block hash {}, found {}",
+                block_hash, snapshot_block_hash
+            )));
+        }
+
+        let mut database_transaction = DatabaseTransaction::new();
+
+        // Read the block headers, which must form a chain from the genesis block of the ledger
+
+        let mut previous_block_hash = self.get_block_hash(0)?;
+        let mut last_block_header = None;
+        for block_number in 0..=block_height {
+            let block_header = BlockHeader::read(&mut reader)?;
+            let header_hash = block_header.get_hash();
+
+            if block_number == 0 {
+                if header_hash != previous_block_hash {
+                    return Err(StorageError::InvalidSnapshot(format!(
+                        "the genesis block {} does not match the genesis block of the ledger",
+                        header_hash
+                    )));
+                }
+            } else {
+                if block_header.previous_block_hash != previous_block_hash {
+                    return Err(StorageError::InvalidSnapshot(format!(
+                        "block {} does not extend block {}",
+                        header_hash, previous_block_hash
+                    )));
+                }
+
+                database_transaction.push(Op::Insert {
+                    col: COL_CHILD_HASHES,
+                    key: previous_block_hash.0.to_vec(),
+                    value: bincode::serialize(&vec![header_hash.clone()])?,
+                });
+            }
+
+            database_transaction.push(Op::Insert {
+                col: COL_BLOCK_HEADER,
+                key: header_hash.0.to_vec(),
+                value: to_bytes![block_header]?.to_vec(),
+            });
+            database_transaction.push(Op::Insert {
+                col: COL_BLOCK_LOCATOR,
+                key: header_hash.0.to_vec(),
+                value: block_number.to_le_bytes().to_vec(),
+            });
+            database_transaction.push(Op::Insert {
+                col: COL_BLOCK_LOCATOR,
+                key: block_number.to_le_bytes().to_vec(),
+                value: header_hash.0.to_vec(),
+            });
+
+            previous_block_hash = header_hash;
+            last_block_header = Some(block_header);
+        }
+
+        if &previous_block_hash != block_hash {
+            return Err(StorageError::InvalidSnapshot(format!(
+                "the block headers end at block {}, instead of block {}",
+                previous_block_hash, block_hash
+            )));
+        }
+
+        // Read the transactions of the block, which must match the merkle root of its header.
+        // The transactions of the genesis block are already in the ledger.
+
+        let transactions = DPCTransactions::<T>::read(&mut reader)?;
+        if block_height != 0 {
+            let transaction_ids = transactions.to_transaction_ids()?;
+            let expected_merkle_root_hash = match transaction_ids.is_empty() {
+                true => MerkleRootHash([0u8; 32]),
+                false => MerkleRootHash(merkle_root(&transaction_ids)),
+            };
+            if last_block_header.map(|header| header.merkle_root_hash) != Some(expected_merkle_root_hash) {
+                return Err(StorageError::InvalidSnapshot(format!(
+                    "the transactions do not match the merkle root of block {}",
+                    block_hash
+                )));
+            }
+
+            for (index, transaction_id) in transaction_ids.iter().enumerate() {
+                let transaction_location = TransactionLocation {
+                    index: index as u32,
+                    block_hash: block_hash.0,
+                };
+                database_transaction.push(Op::Insert {
+                    col: COL_TRANSACTION_LOCATION,
+                    key: transaction_id.to_vec(),
+                    value: to_bytes![transaction_location]?.to_vec(),
+                });
+            }
+            database_transaction.push(Op::Insert {
+                col: COL_BLOCK_TRANSACTIONS,
+                key: block_hash.0.to_vec(),
+                value: to_bytes![transactions]?.to_vec(),
+            });
+        }
+
+        // Read the DPC state
+
+        let mut indexes = [0usize; 3];
+        let mut commitments = vec![];
+        for (i, &col) in [COL_COMMITMENT, COL_SERIAL_NUMBER, COL_MEMO].iter().enumerate() {
+            let num_entries = u32::read(&mut reader)?;
+            for index in 0..num_entries {
+                let key = read_entry(&mut reader)?;
+                if col == COL_COMMITMENT {
+                    commitments.push(T::Commitment::read(&key[..])?);
+                }
+
+                database_transaction.push(Op::Insert {
+                    col,
+                    key,
+                    value: index.to_le_bytes().to_vec(),
+                });
+            }
+            indexes[i] = num_entries as usize;
+        }
+        let [cm_index, sn_index, memo_index] = indexes;
+
+        let num_digests = u32::read(&mut reader)?;
+        let mut digests = Vec::with_capacity(num_digests.min(1024) as usize);
+        for _ in 0..num_digests {
+            let digest = read_entry(&mut reader)?;
+            let digest_value = read_entry(&mut reader)?;
+            if digest_value.len() != 8 {
+                return Err(StorageError::InvalidDigestValue(hex::encode(&digest)));
+            }
+            digests.push(digest.clone());
+
+            database_transaction.push(Op::Insert {
+                col: COL_DIGEST,
+                key: digest,
+                value: digest_value,
+            });
+        }
+
+        let cm_frontier = MerkleFrontier::<P>::read(&mut reader)?;
+
+        // Verify the checksum of the snapshot
+
+        let (mut reader, checksum) = reader.finalize();
+        let expected_checksum = <[u8; 32]>::read(&mut reader)?;
+        if checksum != expected_checksum {
+            return Err(StorageError::InvalidSnapshot("checksum mismatch".into()));
+        }
+
+        // Verify that the commitments match the commitment merkle tree frontier and digest
+
+        let cm_merkle_tree = MerkleTree::new(self.ledger_parameters.clone(), &commitments)?;
+        if cm_merkle_tree.frontier() != cm_frontier {
+            return Err(StorageError::InvalidSnapshot(
+                "the commitments do not match the commitment merkle tree frontier".into(),
+            ));
+        }
+
+        if &cm_merkle_tree.root() != digest {
+            return Err(StorageError::InvalidSnapshot(format!(
+                "the commitments do not match the trusted ledger digest {}",
+                hex::encode(to_bytes![digest]?)
+            )));
+        }
+
+        let digest = to_bytes![cm_merkle_tree.root()]?.to_vec();
+        if !digests.contains(&digest) {
+            return Err(StorageError::InvalidSnapshot(format!(
+                "missing the ledger digest {}",
+                hex::encode(&digest)
+            )));
+        }
+
+        // Update the database state for the current indexes
+
+        for (key, value) in &[
+            (KEY_BEST_BLOCK_NUMBER, block_height.to_le_bytes().to_vec()),
+            (KEY_CURR_CM_INDEX, (cm_index as u32).to_le_bytes().to_vec()),
+            (KEY_CURR_SN_INDEX, (sn_index as u32).to_le_bytes().to_vec()),
+            (KEY_CURR_MEMO_INDEX, (memo_index as u32).to_le_bytes().to_vec()),
+            (KEY_CURR_DIGEST, digest),
+            (KEY_CURR_CM_FRONTIER, to_bytes![cm_frontier]?.to_vec()),
+            (KEY_PRUNED_BLOCK_NUMBER, block_height.to_le_bytes().to_vec()),
+        ] {
+            database_transaction.push(Op::Insert {
+                col: COL_META,
+                key: key.as_bytes().to_vec(),
+                value: value.clone(),
+            });
+        }
+
+        let mut latest_block_height = self.latest_block_height.write();
+        let mut merkle_tree = self.cm_merkle_tree.write();
+
+        self.storage.batch(database_transaction)?;
+
+        *latest_block_height = block_height;
+        *merkle_tree = cm_merkle_tree;
+
+        Ok(())
+    }
+}
+
+/// Writes a length-prefixed entry of a ledger snapshot.
+fn write_entry<W: Write>(mut writer: W, entry: &[u8]) -> IoResult<()> {
+    (entry.len() as u32).write(&mut writer)?;
+    writer.write_all(entry)
+}
+
+/// Reads a length-prefixed entry of a ledger snapshot.
+fn read_entry<R: Read>(mut reader: R) -> Result<Vec<u8>, StorageError> {
+    let entry_size = u32::read(&mut reader)?;
+    if entry_size > MAX_SNAPSHOT_ENTRY_SIZE {
+        return Err(StorageError::InvalidSnapshot(format!(
+            "entry size {} is too large",
+            entry_size
+        )));
+    }
+
+    let mut entry = vec![0u8; entry_size as usize];
+    reader.read_exact(&mut entry)?;
+
+    Ok(entry)
+}
+
+/// A writer that computes the SHA-256 checksum of the written bytes.
+struct ChecksumWriter<W: Write> {
+    writer: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Returns the inner writer and the checksum of the written bytes.
+    fn finalize(self) -> (W, [u8; 32]) {
+        let mut checksum = [0u8; 32];
+        checksum.copy_from_slice(&self.hasher.finalize());
+
+        (self.writer, checksum)
+    }
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        let num_bytes = self.writer.write(buf)?;
+        self.hasher.update(&buf[..num_bytes]);
+
+        Ok(num_bytes)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.writer.flush()
+    }
+}
+
+/// A reader that computes the SHA-256 checksum of the read bytes.
+struct ChecksumReader<R: Read> {
+    reader: R,
+    hasher: Sha256,
+}
+
+impl<R: Read> ChecksumReader<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Returns the inner reader and the checksum of the read bytes.
+    fn finalize(self) -> (R, [u8; 32]) {
+        let mut checksum = [0u8; 32];
+        checksum.copy_from_slice(&self.hasher.finalize());
+
+        (self.reader, checksum)
+    }
+}
+
+impl<R: Read> Read for ChecksumReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let num_bytes = self.reader.read(buf)?;
+        self.hasher.update(&buf[..num_bytes]);
+
+        Ok(num_bytes)
+    }
+}
@@ -18,7 +18,7 @@ use crate::{Ledger, TransactionLocation, COL_TRANSACTION_LOCATION};
 use snarkvm_algorithms::traits::LoadableMerkleParameters;
 use snarkvm_objects::{
     errors::StorageError,
-    traits::{LedgerScheme, Transaction},
+    traits::{LedgerScheme, Storage, Transaction},
     BlockHeaderHash,
 };
 use snarkvm_utilities::{
@@ -27,7 +27,7 @@ use snarkvm_utilities::{
     to_bytes,
 };
 
-impl<T: Transaction, P: LoadableMerkleParameters> Ledger<T, P> {
+impl<T: Transaction, P: LoadableMerkleParameters, S: Storage> Ledger<T, P, S> {
     /// Returns a transaction location given the transaction ID if it exists. Returns `None` otherwise.
     pub fn get_transaction_location(&self, transaction_id: &[u8]) -> Result<Option<TransactionLocation>, StorageError> {
         match self.storage.get(COL_TRANSACTION_LOCATION, &transaction_id)? {
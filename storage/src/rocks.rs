@@ -14,10 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{DatabaseTransaction, Op};
-use snarkvm_objects::errors::StorageError;
+use crate::NUM_COLS;
+use snarkvm_objects::{
+    errors::StorageError,
+    traits::{DatabaseTransaction, Op, Storage},
+};
 
-use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, DBIterator, IteratorMode, Options, WriteBatch, DB};
+use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, Direction, IteratorMode, Options, WriteBatch, DB};
 use std::{
     path::{Path, PathBuf},
     sync::Arc,
@@ -27,14 +30,96 @@ fn convert_err(err: rocksdb::Error) -> StorageError {
     StorageError::Crate("rocksdb", err.to_string())
 }
 
-/// A low-level struct for storing state used by the system.
+/// A low-level struct for storing state used by the system in RocksDB.
 #[derive(Clone)]
-pub struct Storage {
+pub struct RocksDb {
     pub db: Arc<DB>,
     pub cf_names: Vec<String>,
 }
 
-impl Storage {
+impl Storage for RocksDb {
+    fn in_memory(&self) -> bool {
+        false
+    }
+
+    /// Opens storage from the given path. If the secondary path is given, the storage is opened
+    /// as a read-only secondary instance of the primary storage at the first path.
+    fn open(path: Option<&Path>, secondary_path: Option<&Path>) -> Result<Self, StorageError> {
+        let path = path.ok_or_else(|| StorageError::Message("RocksDB requires a storage path".into()))?;
+
+        match secondary_path {
+            Some(secondary_path) => Self::open_secondary_cf(path, secondary_path, NUM_COLS),
+            None => Self::open_cf(path, NUM_COLS),
+        }
+    }
+
+    fn get(&self, col: u32, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        self.db.get_cf(self.get_cf_ref(col), key).map_err(convert_err)
+    }
+
+    fn get_col(&self, col: u32) -> Result<Vec<(Box<[u8]>, Box<[u8]>)>, StorageError> {
+        Ok(self.db.iterator_cf(self.get_cf_ref(col), IteratorMode::Start).collect())
+    }
+
+    fn get_keys(&self, col: u32) -> Result<Vec<Box<[u8]>>, StorageError> {
+        Ok(self
+            .db
+            .iterator_cf(self.get_cf_ref(col), IteratorMode::Start)
+            .map(|(key, _value)| key)
+            .collect())
+    }
+
+    fn get_keys_with_prefix(&self, col: u32, prefix: &[u8]) -> Result<Vec<Box<[u8]>>, StorageError> {
+        Ok(self
+            .db
+            .iterator_cf(self.get_cf_ref(col), IteratorMode::From(prefix, Direction::Forward))
+            .map(|(key, _value)| key)
+            .take_while(|key| key.starts_with(prefix))
+            .collect())
+    }
+
+    fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(&self, col: u32, key: K, value: V) -> Result<(), StorageError> {
+        self.db.put_cf(self.get_cf_ref(col), key, value).map_err(convert_err)
+    }
+
+    fn batch(&self, transaction: DatabaseTransaction) -> Result<(), StorageError> {
+        let mut batch = WriteBatch::default();
+
+        for operation in transaction.0 {
+            match operation {
+                Op::Insert { col, key, value } => {
+                    let cf = self.get_cf_ref(col);
+                    batch.put_cf(cf, &key, value);
+                }
+                Op::Delete { col, key } => {
+                    let cf = self.get_cf_ref(col);
+                    batch.delete_cf(cf, &key);
+                }
+            };
+        }
+
+        self.db.write(batch).map_err(convert_err)
+    }
+
+    fn exists(&self, col: u32, key: &[u8]) -> bool {
+        match self.db.get_cf(self.get_cf_ref(col), key) {
+            Ok(val) => val.is_some(),
+            Err(_) => false,
+        }
+    }
+
+    fn try_catch_up_with_primary(&self) -> Result<(), StorageError> {
+        self.db.try_catch_up_with_primary().map_err(convert_err)
+    }
+
+    fn destroy(&self) -> Result<(), StorageError> {
+        let path = self.db.path();
+        // drop(&self.db); FIXME: this didn't actually drop self.db
+        Self::destroy_storage(path.into())
+    }
+}
+
+impl RocksDb {
     /// Opens storage from the given path with its given names. If storage does not exists,
     /// it creates a new storage file at the given path with its given names, and opens it.
     /// If RocksDB fails to open, returns [StorageError](snarkvm_errors::storage::StorageError).
@@ -64,7 +149,7 @@ impl Storage {
 
     /// Opens a secondary storage instance from the given path with its given names.
     /// If RocksDB fails to open, returns [StorageError](snarkvm_errors::storage::StorageError).
-    pub fn open_secondary_cf<P: AsRef<Path> + Clone>(
+    pub fn open_secondary_cf<P: AsRef<Path>>(
         primary_path: P,
         secondary_path: P,
         num_cfs: u32,
@@ -98,64 +183,13 @@ impl Storage {
             .expect("the column family exists")
     }
 
-    /// Returns the value from a given key and col.
-    /// If the given key does not exist, returns [StorageError](snarkvm_errors::storage::StorageError).
-    pub(crate) fn get(&self, col: u32, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
-        Ok(self.db.get_cf(self.get_cf_ref(col), key).map_err(convert_err)?)
-    }
-
-    /// Returns the iterator from a given col.
-    /// If the given key does not exist, returns [StorageError](snarkvm_errors::storage::StorageError).
-    pub(crate) fn get_iter(&self, col: u32) -> DBIterator {
-        self.db.iterator_cf(self.get_cf_ref(col), IteratorMode::Start)
-    }
-
-    /// Returns `Ok(())` after executing a database transaction
-    /// If the any of the operations fail, returns [StorageError](snarkvm_errors::storage::StorageError).
-    pub(crate) fn write(&self, transaction: DatabaseTransaction) -> Result<(), StorageError> {
-        let mut batch = WriteBatch::default();
-
-        for operation in transaction.0 {
-            match operation {
-                Op::Insert { col, key, value } => {
-                    let cf = self.get_cf_ref(col);
-                    batch.put_cf(cf, &key, value);
-                }
-                Op::Delete { col, key } => {
-                    let cf = self.get_cf_ref(col);
-                    batch.delete_cf(cf, &key);
-                }
-            };
-        }
-
-        self.db.write(batch).map_err(convert_err)?;
-
-        Ok(())
-    }
-
-    /// Returns true if a value exists for a key and col pair.
-    pub fn exists(&self, col: u32, key: &[u8]) -> bool {
-        match self.db.get_cf(self.get_cf_ref(col), key) {
-            Ok(val) => val.is_some(),
-            Err(_) => false,
-        }
-    }
-
-    /// Returns `Ok(())` after destroying the storage
-    /// If RocksDB fails to destroy storage, returns [StorageError](snarkvm_errors::storage::StorageError).
-    pub fn destroy(&self) -> Result<(), StorageError> {
-        let path = self.db.path();
-        // drop(&self.db); FIXME: this didn't actually drop self.db
-        Self::destroy_storage(path.into())
-    }
-
     /// Returns `Ok(())` after destroying the storage of the given path.
     /// If RocksDB fails to destroy storage, returns [StorageError](snarkvm_errors::storage::StorageError).
-    pub(crate) fn destroy_storage(path: PathBuf) -> Result<(), StorageError> {
+    pub fn destroy_storage(path: PathBuf) -> Result<(), StorageError> {
         let mut storage_opts = Options::default();
         storage_opts.create_missing_column_families(true);
         storage_opts.create_if_missing(true);
 
-        Ok(DB::destroy(&storage_opts, path).map_err(convert_err)?)
+        DB::destroy(&storage_opts, path).map_err(convert_err)
     }
 }
@@ -14,23 +14,131 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
+/// Merkle tree parameters whose two-to-one CRH hashes both children of an inner node.
+/// The `MerkleTreeCRH` of the instantiated DPC only reads the left child, so the ledger
+/// digest doesn't change as commitments are appended to the right of the first leaf.
+mod wide_merkle_tree {
+    use snarkvm_algorithms::{
+        crh::{BoweHopwoodPedersenCompressedCRH, PedersenSize},
+        define_merkle_tree_parameters,
+    };
+    use snarkvm_curves::edwards_bls12::EdwardsProjective as EdwardsBls;
+
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    pub struct WideTwoToOneWindow;
+    impl PedersenSize for WideTwoToOneWindow {
+        const NUM_WINDOWS: usize = 16;
+        const WINDOW_SIZE: usize = 32;
+    }
+
+    define_merkle_tree_parameters!(
+        WideMerkleParameters,
+        BoweHopwoodPedersenCompressedCRH<EdwardsBls, WideTwoToOneWindow>,
+        32
+    );
+}
+
 mod test_storage {
+    use super::wide_merkle_tree::WideMerkleParameters;
+    use snarkvm_algorithms::traits::{LoadableMerkleParameters, MerkleParameters, CRH};
+    use snarkvm_dpc::{
+        account::Account,
+        base_dpc::{
+            instantiated::{CommitmentMerkleParameters, Components, SerialNumberNonce, Tx, NUM_INPUT_RECORDS},
+            parameters::SystemParameters,
+            record::DPCRecord,
+            record_payload::RecordPayload,
+            DPC,
+        },
+        traits::{AccountScheme, Record},
+    };
     use snarkvm_objects::{
+        errors::StorageError,
+        merkle_root,
+        traits::{LedgerScheme, Storage, Transaction},
+        AleoAmount,
         Block,
         BlockHeader,
         BlockHeaderHash,
         DPCTransactions,
+        DatabaseTransaction,
         MerkleRootHash,
         PedersenMerkleRootHash,
         ProofOfSuccinctWork,
     };
+    use snarkvm_storage::{
+        LedgerInconsistency,
+        LedgerView,
+        PruningMode,
+        SideChainPath,
+        COL_BLOCK_LOCATOR,
+        COL_DIGEST,
+        COL_META,
+        COL_RECORDS_BY_BIRTH_PROGRAM_ID,
+        COL_RECORDS_BY_DEATH_PROGRAM_ID,
+        COL_RECORDS_BY_OWNER,
+        KEY_CURR_CM_FRONTIER,
+        KEY_CURR_CM_INDEX,
+        KEY_NETWORK_ID,
+        KEY_SCHEMA_VERSION,
+        SCHEMA_VERSION,
+    };
     use snarkvm_testing::storage::*;
+    use snarkvm_utilities::{
+        bytes::{FromBytes, ToBytes},
+        to_bytes,
+    };
 
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
     use std::sync::Arc;
 
+    /// Returns a copy of the genesis transaction with new serial numbers, commitments and memorandum.
+    fn transaction_with_new_records<P: LoadableMerkleParameters, S: Storage>(
+        blockchain: &Ledger<Tx, P, S>,
+        seed: u8,
+    ) -> Tx {
+        let rng = &mut XorShiftRng::seed_from_u64(seed as u64);
+
+        let system_parameters = SystemParameters::<Components>::load().unwrap();
+        let account = Account::new(
+            &system_parameters.account_signature,
+            &system_parameters.account_commitment,
+            &system_parameters.account_encryption,
+            rng,
+        )
+        .unwrap();
+
+        let mut transaction = blockchain.get_block_from_block_number(0).unwrap().transactions.0[0].clone();
+        transaction.old_serial_numbers.clear();
+        transaction.new_commitments.clear();
+        for i in 0..2u8 {
+            let sn_nonce =
+                SerialNumberNonce::<NUM_INPUT_RECORDS>::hash(&system_parameters.serial_number_nonce, &[seed, i])
+                    .unwrap();
+            let record = DPC::generate_record(
+                &system_parameters,
+                sn_nonce,
+                account.address.clone(),
+                false,
+                10,
+                RecordPayload::default(),
+                vec![0u8; 48],
+                vec![0u8; 48],
+                rng,
+            )
+            .unwrap();
+            let (sn, _) = DPC::generate_sn(&system_parameters, &record, &account.private_key).unwrap();
+            transaction.old_serial_numbers.push(sn);
+            transaction.new_commitments.push(record.commitment());
+        }
+        transaction.memorandum = [seed; 32];
+        transaction
+    }
+
     #[test]
     pub fn test_new_blockchain() {
-        let (blockchain, _): (Arc<Store>, _) = open_test_blockchain();
+        let blockchain: Arc<Store> = open_test_blockchain();
 
         assert_eq!(blockchain.get_latest_block_height(), 0);
 
@@ -41,7 +149,7 @@ mod test_storage {
 
     #[test]
     pub fn remove_decrements_height() {
-        let (blockchain, _): (Arc<Store>, _) = open_test_blockchain();
+        let blockchain: Arc<Store> = open_test_blockchain();
 
         assert_eq!(blockchain.get_latest_block_height(), 0);
 
@@ -69,38 +177,283 @@ mod test_storage {
         kill_storage_sync(blockchain);
     }
 
+    #[test]
+    pub fn test_cm_frontier() {
+        let blockchain: Arc<Store> = open_test_blockchain();
+
+        let assert_cm_frontier_matches_rebuilt_tree = |blockchain: &Store| {
+            let rebuilt_merkle_tree = blockchain.build_merkle_tree(vec![]).unwrap();
+            let cm_frontier = blockchain.current_cm_frontier().unwrap();
+
+            assert_eq!(cm_frontier, rebuilt_merkle_tree.frontier());
+            assert_eq!(
+                cm_frontier.root(&blockchain.ledger_parameters).unwrap(),
+                rebuilt_merkle_tree.root()
+            );
+            assert_eq!(blockchain.cm_merkle_tree.read().root(), rebuilt_merkle_tree.root());
+        };
+
+        // The genesis commitments are appended to the frontier.
+        let genesis_num_leaves = blockchain.current_cm_frontier().unwrap().num_leaves();
+        assert_ne!(genesis_num_leaves, 0);
+        assert_cm_frontier_matches_rebuilt_tree(&blockchain);
+
+        let block = Block {
+            header: BlockHeader {
+                difficulty_target: 100,
+                nonce: 99,
+                merkle_root_hash: MerkleRootHash([0; 32]),
+                previous_block_hash: blockchain.get_latest_block().unwrap().header.get_hash(),
+                time: 123,
+                proof: ProofOfSuccinctWork::default(),
+                pedersen_merkle_root_hash: PedersenMerkleRootHash([0; 32]),
+            },
+            transactions: DPCTransactions(vec![
+                transaction_with_new_records(&blockchain, 1),
+                transaction_with_new_records(&blockchain, 2),
+            ]),
+        };
+
+        blockchain.insert_and_commit(&block).unwrap();
+        assert_eq!(
+            blockchain.current_cm_frontier().unwrap().num_leaves(),
+            genesis_num_leaves + 4
+        );
+        assert_cm_frontier_matches_rebuilt_tree(&blockchain);
+
+        blockchain.remove_latest_block().unwrap();
+        assert_eq!(
+            blockchain.current_cm_frontier().unwrap().num_leaves(),
+            genesis_num_leaves
+        );
+        assert_cm_frontier_matches_rebuilt_tree(&blockchain);
+
+        kill_storage_sync(blockchain);
+    }
+
+    #[test]
+    pub fn test_prove_cm_at() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+
+        // The ledger digest only changes with the appended commitments if the Merkle tree hashes both children.
+        let genesis_block = open_test_blockchain::<Tx, CommitmentMerkleParameters>()
+            .get_block_from_block_number(0)
+            .unwrap();
+        let blockchain = initialize_test_blockchain(WideMerkleParameters::setup(rng), genesis_block.clone());
+        let genesis_digest = blockchain.digest().unwrap();
+
+        // Commit two blocks with new commitments, so that the digests after the genesis block
+        // and after the first block become past ledger digests.
+        let mut blocks = vec![];
+        let mut digests = vec![];
+        for seed in 1..=2 {
+            let block = Block {
+                header: BlockHeader {
+                    difficulty_target: 100,
+                    nonce: seed as u32,
+                    merkle_root_hash: MerkleRootHash([0; 32]),
+                    previous_block_hash: blockchain.get_latest_block().unwrap().header.get_hash(),
+                    time: 123,
+                    proof: ProofOfSuccinctWork::default(),
+                    pedersen_merkle_root_hash: PedersenMerkleRootHash([0; 32]),
+                },
+                transactions: DPCTransactions(vec![transaction_with_new_records(&blockchain, seed)]),
+            };
+            blockchain.insert_and_commit(&block).unwrap();
+
+            blocks.push(block);
+            digests.push(blockchain.digest().unwrap());
+        }
+        let current_digest = blockchain.digest().unwrap();
+        assert_ne!(genesis_digest, digests[0]);
+        assert_ne!(digests[0], current_digest);
+
+        // The commitments of the first block are proven against the digest after the first block,
+        // and the witness doesn't hold for the current digest.
+        for cm in blocks[0].transactions.0[0].new_commitments() {
+            let path = blockchain.prove_cm_at(cm, &digests[0]).unwrap();
+            assert!(path.verify(&digests[0], cm).unwrap());
+            assert!(!path.verify(&current_digest, cm).unwrap());
+            assert!(blockchain.prove_cm(cm).unwrap().verify(&current_digest, cm).unwrap());
+        }
+
+        // The genesis commitments are proven against the genesis digest.
+        for cm in genesis_block.transactions.0[0].new_commitments() {
+            let path = blockchain.prove_cm_at(cm, &genesis_digest).unwrap();
+            assert!(path.verify(&genesis_digest, cm).unwrap());
+            assert!(!path.verify(&digests[0], cm).unwrap());
+        }
+
+        // A commitment is not proven against a digest that predates it.
+        let cm = &blocks[1].transactions.0[0].new_commitments()[0];
+        match blockchain.prove_cm_at(cm, &digests[0]) {
+            Err(StorageError::MissingCm(_)) => {}
+            result => panic!("expected a missing commitment, found {:?}", result.is_ok()),
+        }
+
+        // An unknown digest is rejected.
+        assert!(blockchain.prove_cm_at(cm, &Default::default()).is_err());
+
+        // A ledger view proves commitments against past ledger digests and its current digest.
+        let blockchain = Arc::new(blockchain);
+        let view = LedgerView::new(blockchain.clone()).unwrap();
+        for cm in blocks[0].transactions.0[0].new_commitments() {
+            let path = LedgerScheme::prove_cm_at(&view, cm, &digests[0]).unwrap();
+            assert!(path.verify(&digests[0], cm).unwrap());
+            let path = LedgerScheme::prove_cm_at(&view, cm, &view.digest().unwrap()).unwrap();
+            assert!(path.verify(&current_digest, cm).unwrap());
+        }
+        assert!(LedgerScheme::prove_cm_at(&view, cm, &Default::default()).is_err());
+        drop(view);
+
+        kill_storage_sync(blockchain);
+    }
+
     #[test]
     pub fn test_storage() {
-        let (blockchain, _): (Arc<Store>, _) = open_test_blockchain();
+        let blockchain: Arc<Store> = open_test_blockchain();
 
-        blockchain.storage.db.put(b"my key", b"my value").unwrap();
+        blockchain.storage.put(0, b"my key", b"my value").unwrap();
 
-        match blockchain.storage.db.get(b"my key") {
+        match blockchain.storage.get(0, b"my key") {
             Ok(Some(value)) => println!("retrieved value {}", String::from_utf8(value).unwrap()),
             Ok(None) => println!("value not found"),
             Err(e) => println!("operational problem encountered: {}", e),
         }
 
-        assert!(blockchain.storage.db.get(b"my key").is_ok());
+        assert!(blockchain.storage.get(0, b"my key").is_ok());
 
         kill_storage_sync(blockchain);
     }
 
     #[test]
     pub fn test_storage_memory_pool() {
-        let (blockchain, _): (Arc<Store>, _) = open_test_blockchain();
-        let transactions_serialized = vec![0u8];
+        let mut path = std::env::temp_dir();
+        path.push(random_storage_path());
 
-        assert!(blockchain.store_to_memory_pool(transactions_serialized.clone()).is_ok());
-        assert!(blockchain.get_memory_pool().is_ok());
-        assert_eq!(transactions_serialized, blockchain.get_memory_pool().unwrap());
+        let blockchain = Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path(&path).unwrap();
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
 
-        kill_storage_sync(blockchain);
+        // Derive fresh serial numbers and commitments for the memory pool transactions.
+        let system_parameters = SystemParameters::<Components>::load().unwrap();
+        let account = Account::new(
+            &system_parameters.account_signature,
+            &system_parameters.account_commitment,
+            &system_parameters.account_encryption,
+            rng,
+        )
+        .unwrap();
+        let mut sns = vec![];
+        let mut cms = vec![];
+        for i in 0..8 {
+            let sn_nonce =
+                SerialNumberNonce::<NUM_INPUT_RECORDS>::hash(&system_parameters.serial_number_nonce, &[i as u8; 1])
+                    .unwrap();
+            let record = DPC::generate_record(
+                &system_parameters,
+                sn_nonce,
+                account.address.clone(),
+                false,
+                10,
+                RecordPayload::default(),
+                vec![0u8; 48],
+                vec![0u8; 48],
+                rng,
+            )
+            .unwrap();
+            sns.push(
+                DPC::generate_sn(&system_parameters, &record, &account.private_key)
+                    .unwrap()
+                    .0,
+            );
+            cms.push(record.commitment());
+        }
+
+        let genesis_transaction = blockchain.get_block_from_block_number(0).unwrap().transactions.0[0].clone();
+        let transaction = |sn_indices: [usize; 2], cm_indices: [usize; 2], memo: u8, fee: i64| {
+            let mut transaction = genesis_transaction.clone();
+            transaction.old_serial_numbers = sn_indices.iter().map(|i| sns[*i]).collect();
+            transaction.new_commitments = cm_indices.iter().map(|i| cms[*i]).collect();
+            transaction.memorandum = [memo; 32];
+            transaction.value_balance = AleoAmount(fee);
+            transaction
+        };
+
+        let transaction_a = transaction([0, 1], [0, 1], 1, 10);
+        let transaction_b = transaction([2, 3], [2, 3], 2, 100);
+        let transaction_c = transaction([0, 5], [4, 5], 3, 1000);
+        let transaction_d = transaction([1, 5], [6, 7], 4, 0);
+
+        blockchain.add_to_memory_pool(&transaction_a).unwrap();
+        blockchain.add_to_memory_pool(&transaction_b).unwrap();
+
+        match blockchain.add_to_memory_pool(&transaction_a) {
+            Err(StorageError::ExistingMemoryPoolTransaction(_)) => {}
+            result => panic!("expected an existing memory pool transaction, found {:?}", result),
+        }
+        match blockchain.add_to_memory_pool(&transaction_c) {
+            Err(StorageError::MemoryPoolConflict(_)) => {}
+            result => panic!("expected a memory pool conflict, found {:?}", result),
+        }
+        match blockchain.add_to_memory_pool(&genesis_transaction) {
+            Err(StorageError::LedgerConflict(_)) => {}
+            result => panic!("expected a ledger conflict, found {:?}", result),
+        }
+
+        // Transactions are ordered by decreasing fee per byte.
+        assert_eq!(blockchain.get_memory_pool_by_fee().unwrap(), vec![
+            transaction_b.clone(),
+            transaction_a.clone()
+        ]);
+        assert_eq!(
+            blockchain.get_memory_pool_candidates(transaction_a.size()).unwrap(),
+            vec![transaction_b.clone()]
+        );
+        drop(blockchain);
+
+        // The memory pool survives restarts.
+        let blockchain = Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path(&path).unwrap();
+        assert_eq!(blockchain.get_memory_pool().unwrap().len(), 2);
+        assert!(blockchain.memory_pool_contains(&transaction_a.transaction_id().unwrap()));
+
+        // Committing a block evicts its transactions and the conflicting transactions.
+        blockchain.add_to_memory_pool(&transaction_d).unwrap_err();
+        let block = Block {
+            header: BlockHeader {
+                difficulty_target: 100,
+                nonce: 1,
+                merkle_root_hash: MerkleRootHash([0; 32]),
+                previous_block_hash: blockchain.get_latest_block().unwrap().header.get_hash(),
+                time: 123,
+                proof: ProofOfSuccinctWork::default(),
+                pedersen_merkle_root_hash: PedersenMerkleRootHash([0; 32]),
+            },
+            transactions: DPCTransactions(vec![transaction_d]),
+        };
+        blockchain.insert_and_commit(&block).unwrap();
+        assert_eq!(
+            blockchain.get_memory_pool_by_fee().unwrap(),
+            vec![transaction_b.clone()]
+        );
+
+        // A transaction that spends a serial number of the ledger is rejected.
+        match blockchain.add_to_memory_pool(&transaction_c) {
+            Err(StorageError::LedgerConflict(_)) => {}
+            result => panic!("expected a ledger conflict, found {:?}", result),
+        }
+
+        blockchain
+            .remove_from_memory_pool(&transaction_b.transaction_id().unwrap())
+            .unwrap();
+        assert!(blockchain.get_memory_pool().unwrap().is_empty());
+
+        drop(blockchain);
+        kill_storage_async::<Tx, CommitmentMerkleParameters>(path);
     }
 
     #[test]
     pub fn test_storage_peer_book() {
-        let (blockchain, _): (Arc<Store>, _) = open_test_blockchain();
+        let blockchain: Arc<Store> = open_test_blockchain();
         let peers_serialized = vec![0u8];
 
         assert!(blockchain.store_to_peer_book(peers_serialized.clone()).is_ok());
@@ -115,7 +468,830 @@ mod test_storage {
         let mut path = std::env::temp_dir();
         path.push(random_storage_path());
 
-        Store::destroy_storage(path).unwrap();
+        Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::destroy_storage(path).unwrap();
+    }
+
+    #[test]
+    pub fn test_rocksdb_blockchain() {
+        let mut path = std::env::temp_dir();
+        path.push(random_storage_path());
+
+        let blockchain = Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path(&path).unwrap();
+
+        assert_eq!(blockchain.get_latest_block_height(), 0);
+        assert!(!blockchain.storage.in_memory());
+
+        let _latest_block = blockchain.get_latest_block().unwrap();
+
+        drop(blockchain);
+        kill_storage_async::<Tx, CommitmentMerkleParameters>(path);
+    }
+
+    fn custom_genesis_block(time: i64) -> Block<Tx> {
+        Block {
+            header: BlockHeader {
+                previous_block_hash: BlockHeaderHash([0u8; 32]),
+                merkle_root_hash: MerkleRootHash([0u8; 32]),
+                pedersen_merkle_root_hash: PedersenMerkleRootHash([0u8; 32]),
+                proof: ProofOfSuccinctWork::default(),
+                time,
+                difficulty_target: 100,
+                nonce: 0,
+            },
+            transactions: DPCTransactions::new(),
+        }
+    }
+
+    #[test]
+    pub fn test_open_in_memory_with_genesis() {
+        let genesis_block = custom_genesis_block(42);
+        let blockchain = Store::open_in_memory_with_genesis(genesis_block.clone()).unwrap();
+
+        assert_eq!(blockchain.get_latest_block_height(), 0);
+        assert_eq!(blockchain.get_block_hash(0).unwrap(), genesis_block.header.get_hash());
+        assert_eq!(blockchain.get_latest_block().unwrap(), genesis_block);
+    }
+
+    #[test]
+    pub fn test_open_at_path_with_genesis() {
+        let mut path = std::env::temp_dir();
+        path.push(random_storage_path());
+
+        let genesis_block = custom_genesis_block(42);
+        let blockchain =
+            Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path_with_genesis(&path, genesis_block.clone())
+                .unwrap();
+        assert_eq!(blockchain.get_block_hash(0).unwrap(), genesis_block.header.get_hash());
+        drop(blockchain);
+
+        // Reopening with the same genesis block succeeds.
+        let blockchain =
+            Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path_with_genesis(&path, genesis_block.clone())
+                .unwrap();
+        assert_eq!(blockchain.get_latest_block().unwrap(), genesis_block);
+        drop(blockchain);
+
+        // Reopening with a different genesis block fails.
+        let result = Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path_with_genesis(
+            &path,
+            custom_genesis_block(43),
+        );
+        assert!(matches!(result, Err(StorageError::GenesisBlockMismatch(_, _))));
+
+        kill_storage_async::<Tx, CommitmentMerkleParameters>(path);
+    }
+
+    #[test]
+    pub fn test_open_with_invalid_genesis() {
+        let genesis_ledger: Arc<Store> = open_test_blockchain();
+        let transaction = genesis_ledger.get_block_from_block_number(0).unwrap().transactions.0[0].clone();
+
+        // A genesis block that repeats its transaction has duplicate serial numbers, commitments and memos.
+        let mut genesis_block = custom_genesis_block(42);
+        genesis_block.transactions = DPCTransactions(vec![transaction.clone(), transaction]);
+        let result = Store::open_in_memory_with_genesis(genesis_block);
+        assert!(matches!(result, Err(StorageError::Message(_))));
+
+        kill_storage_sync(genesis_ledger);
+    }
+
+    #[test]
+    pub fn test_pruning_mode() {
+        let mut path = std::env::temp_dir();
+        path.push(random_storage_path());
+
+        let blockchain = Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path(&path).unwrap();
+
+        for i in 0..3 {
+            let block = Block {
+                header: BlockHeader {
+                    difficulty_target: 100,
+                    nonce: i,
+                    merkle_root_hash: MerkleRootHash([0; 32]),
+                    previous_block_hash: blockchain.get_latest_block().unwrap().header.get_hash(),
+                    time: 123,
+                    proof: ProofOfSuccinctWork::default(),
+                    pedersen_merkle_root_hash: PedersenMerkleRootHash([0; 32]),
+                },
+                transactions: DPCTransactions::new(),
+            };
+            blockchain.insert_and_commit(&block).unwrap();
+        }
+        assert_eq!(blockchain.get_pruned_block_number().unwrap(), 0);
+        let digest = blockchain.current_digest().unwrap();
+        let genesis_block = blockchain.get_block_from_block_number(0).unwrap();
+        let genesis_cms = genesis_block.transactions.0[0].new_commitments().to_vec();
+        drop(blockchain);
+
+        // Reopening the ledger with a pruning mode prunes the old blocks right away.
+        let blockchain = Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path_with_pruning(
+            &path,
+            PruningMode::KeepRecent(2),
+        )
+        .unwrap();
+        assert_eq!(blockchain.get_pruned_block_number().unwrap(), 2);
+
+        let block = Block {
+            header: BlockHeader {
+                difficulty_target: 100,
+                nonce: 3,
+                merkle_root_hash: MerkleRootHash([0; 32]),
+                previous_block_hash: blockchain.get_latest_block().unwrap().header.get_hash(),
+                time: 123,
+                proof: ProofOfSuccinctWork::default(),
+                pedersen_merkle_root_hash: PedersenMerkleRootHash([0; 32]),
+            },
+            transactions: DPCTransactions::new(),
+        };
+        blockchain.insert_and_commit(&block).unwrap();
+        assert_eq!(blockchain.get_pruned_block_number().unwrap(), 3);
+
+        for block_number in 0..3 {
+            let block_hash = blockchain.get_block_hash(block_number).unwrap();
+            assert!(blockchain.is_pruned(&block_hash).unwrap());
+            assert!(blockchain.get_block_header(&block_hash).is_ok());
+            match blockchain.get_block(&block_hash) {
+                Err(StorageError::PrunedBlock(_)) => {}
+                result => panic!("expected a pruned block, found {:?}", result.map(|block| block.header)),
+            }
+        }
+        for block_number in 3..5 {
+            assert!(blockchain.get_block_from_block_number(block_number).is_ok());
+        }
+
+        // The DPC state is kept.
+        assert_eq!(blockchain.current_digest().unwrap(), digest);
+        for cm in &genesis_cms {
+            assert!(blockchain.contains_cm(cm));
+        }
+        assert!(!blockchain.is_empty());
+
+        // Reverting past the pruning window is refused, and the chain is left untouched.
+        let side_chain_path = SideChainPath {
+            shared_block_number: 1,
+            new_block_number: 5,
+            path: vec![],
+        };
+        match blockchain.revert_for_fork(&side_chain_path) {
+            Err(StorageError::PrunedRevert(1, 3)) => {}
+            result => panic!("expected a pruned revert, found {:?}", result),
+        }
+        match blockchain.remove_latest_blocks(3) {
+            Err(StorageError::PrunedRevert(1, 3)) => {}
+            result => panic!("expected a pruned revert, found {:?}", result),
+        }
+        assert_eq!(blockchain.get_latest_block_height(), 4);
+        assert_eq!(blockchain.current_digest().unwrap(), digest);
+
+        // The blocks within the pruning window can be reverted.
+        let side_chain_path = SideChainPath {
+            shared_block_number: 2,
+            new_block_number: 5,
+            path: vec![],
+        };
+        blockchain.revert_for_fork(&side_chain_path).unwrap();
+        assert_eq!(blockchain.get_latest_block_height(), 2);
+
+        drop(blockchain);
+        kill_storage_async::<Tx, CommitmentMerkleParameters>(path);
+    }
+
+    #[test]
+    pub fn test_schema_migration() {
+        let mut path = std::env::temp_dir();
+        path.push(random_storage_path());
+
+        let blockchain = Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path(&path).unwrap();
+
+        let mut past_digest = None;
+        let mut past_cm = None;
+        for i in 0..2 {
+            let block = Block {
+                header: BlockHeader {
+                    difficulty_target: 100,
+                    nonce: i,
+                    merkle_root_hash: MerkleRootHash([0; 32]),
+                    previous_block_hash: blockchain.get_latest_block().unwrap().header.get_hash(),
+                    time: 123,
+                    proof: ProofOfSuccinctWork::default(),
+                    pedersen_merkle_root_hash: PedersenMerkleRootHash([0; 32]),
+                },
+                transactions: DPCTransactions(vec![transaction_with_new_records(&blockchain, i as u8 + 1)]),
+            };
+            blockchain.insert_and_commit(&block).unwrap();
+
+            if past_digest.is_none() {
+                past_digest = Some(blockchain.digest().unwrap());
+                past_cm = Some(block.transactions.0[0].new_commitments()[0]);
+            }
+        }
+        let (past_digest, past_cm) = (past_digest.unwrap(), past_cm.unwrap());
+        let digest = blockchain.current_digest().unwrap();
+        let cm_frontier = blockchain.current_cm_frontier().unwrap();
+        let digests = blockchain.storage.get_col(COL_DIGEST).unwrap();
+        let network_id = blockchain.network_id();
+        assert!(network_id.is_some());
+        drop(blockchain);
+
+        // Rewrite the ledger into the schema version 1 layout.
+        let storage = RocksDb::open(Some(&path), None).unwrap();
+        assert_eq!(
+            Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::get_schema_version(&storage).unwrap(),
+            Some(SCHEMA_VERSION)
+        );
+        let mut database_transaction = DatabaseTransaction::new();
+        database_transaction.delete(COL_META, KEY_SCHEMA_VERSION.as_bytes());
+        database_transaction.delete(COL_META, KEY_CURR_CM_FRONTIER.as_bytes());
+        database_transaction.delete(COL_META, KEY_NETWORK_ID.as_bytes());
+        for (digest, digest_value) in &digests {
+            database_transaction.add(COL_DIGEST, digest, &digest_value[..4]);
+        }
+        storage.batch(database_transaction).unwrap();
+        assert_eq!(
+            Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::get_schema_version(&storage).unwrap(),
+            Some(1)
+        );
+        drop(storage);
+
+        // Opening the ledger migrates it to the current schema version.
+        let blockchain = Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path(&path).unwrap();
+        assert_eq!(
+            Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::get_schema_version(&blockchain.storage).unwrap(),
+            Some(SCHEMA_VERSION)
+        );
+        assert_eq!(blockchain.current_digest().unwrap(), digest);
+        assert_eq!(blockchain.current_cm_frontier().unwrap(), cm_frontier);
+        assert_eq!(blockchain.storage.get_col(COL_DIGEST).unwrap(), digests);
+        assert_eq!(blockchain.network_id(), network_id);
+        assert!(blockchain.check_integrity().unwrap().is_empty());
+
+        // The migrated digests hold the commitment index used to prove commitments against past digests.
+        let cm_path = blockchain.prove_cm_at(&past_cm, &past_digest).unwrap();
+        assert!(cm_path.verify(&past_digest, &past_cm).unwrap());
+
+        // A ledger with a newer schema version is refused.
+        blockchain
+            .storage
+            .put(
+                COL_META,
+                KEY_SCHEMA_VERSION.as_bytes(),
+                (SCHEMA_VERSION + 1).to_le_bytes(),
+            )
+            .unwrap();
+        drop(blockchain);
+        match Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path(&path) {
+            Err(StorageError::UnsupportedSchemaVersion(found, expected)) => {
+                assert_eq!(found, SCHEMA_VERSION + 1);
+                assert_eq!(expected, SCHEMA_VERSION);
+            }
+            result => panic!("expected an unsupported schema version, found {:?}", result.is_ok()),
+        }
+
+        // A malformed digest value fails the migration instead of panicking.
+        let storage = RocksDb::open(Some(&path), None).unwrap();
+        let mut database_transaction = DatabaseTransaction::new();
+        database_transaction.delete(COL_META, KEY_SCHEMA_VERSION.as_bytes());
+        database_transaction.add(COL_DIGEST, &digests[0].0, &[0u8; 2]);
+        storage.batch(database_transaction).unwrap();
+        drop(storage);
+        assert!(matches!(
+            Ledger::<Tx, CommitmentMerkleParameters, RocksDb>::open_at_path(&path),
+            Err(StorageError::InvalidDigestValue(_))
+        ));
+
+        kill_storage_async::<Tx, CommitmentMerkleParameters>(path);
+    }
+
+    #[test]
+    pub fn test_record_indexes() {
+        let blockchain: Arc<Store> = open_test_blockchain();
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+
+        let system_parameters = SystemParameters::<Components>::load().unwrap();
+        let signature_parameters = &system_parameters.account_signature;
+        let commitment_parameters = &system_parameters.account_commitment;
+        let encryption_parameters = &system_parameters.account_encryption;
+        let account_1 = Account::new(signature_parameters, commitment_parameters, encryption_parameters, rng).unwrap();
+        let account_2 = Account::new(signature_parameters, commitment_parameters, encryption_parameters, rng).unwrap();
+
+        let mut records = vec![];
+        for (i, owner) in [&account_1, &account_1, &account_1, &account_2].iter().enumerate() {
+            let sn_nonce =
+                SerialNumberNonce::<NUM_INPUT_RECORDS>::hash(&system_parameters.serial_number_nonce, &[i as u8; 1])
+                    .unwrap();
+            let record = DPC::generate_record(
+                &system_parameters,
+                sn_nonce,
+                owner.address.clone(),
+                false,
+                10,
+                RecordPayload::default(),
+                vec![i as u8 % 2; 48],
+                vec![2u8; 48],
+                rng,
+            )
+            .unwrap();
+            records.push(record);
+        }
+
+        // The first record is spent by the genesis transaction, and the second record is unspent.
+        let genesis_block = blockchain.get_block_from_block_number(0).unwrap();
+        let spent_sn = genesis_block.transactions.0[0].old_serial_numbers()[0];
+        let (unspent_sn, _) = DPC::generate_sn(&system_parameters, &records[1], &account_1.private_key).unwrap();
+        blockchain
+            .store_record_with_serial_number(
+                &records[0],
+                &FromBytes::read(&to_bytes![spent_sn].unwrap()[..]).unwrap(),
+            )
+            .unwrap();
+        blockchain
+            .store_record_with_serial_number(&records[1], &unspent_sn)
+            .unwrap();
+        blockchain.store_records(&records[2..]).unwrap();
+
+        let mut owned_records = blockchain
+            .get_records_by_owner::<DPCRecord<Components>>(&account_1.address, 0, None)
+            .unwrap();
+        assert_eq!(owned_records.len(), 3);
+        assert!(records[..3].iter().all(|record| owned_records.contains(record)));
+
+        // Pages follow a stable order.
+        let mut paged_records = blockchain
+            .get_records_by_owner::<DPCRecord<Components>>(&account_1.address, 0, Some(2))
+            .unwrap();
+        paged_records.extend(
+            blockchain
+                .get_records_by_owner::<DPCRecord<Components>>(&account_1.address, 2, Some(2))
+                .unwrap(),
+        );
+        assert_eq!(paged_records, owned_records);
+
+        let birth_records = blockchain
+            .get_records_by_birth_program_id::<DPCRecord<Components>>(&[1u8; 48], 0, None)
+            .unwrap();
+        assert_eq!(birth_records.len(), 2);
+        assert!(birth_records.contains(&records[1]) && birth_records.contains(&records[3]));
+        assert_eq!(
+            blockchain
+                .get_records_by_death_program_id::<DPCRecord<Components>>(&[2u8; 48], 0, None)
+                .unwrap()
+                .len(),
+            4
+        );
+
+        let commitment = |record: &DPCRecord<Components>| to_bytes![record.commitment()].unwrap();
+        assert_eq!(
+            blockchain.is_record_spent(&commitment(&records[0])).unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            blockchain.is_record_spent(&commitment(&records[1])).unwrap(),
+            Some(false)
+        );
+        assert_eq!(blockchain.is_record_spent(&commitment(&records[2])).unwrap(), None);
+        assert_eq!(
+            blockchain.get_spent_records::<DPCRecord<Components>>(0, None).unwrap(),
+            vec![records[0].clone()]
+        );
+        assert_eq!(
+            blockchain
+                .get_unspent_records::<DPCRecord<Components>>(0, None)
+                .unwrap(),
+            vec![records[1].clone()]
+        );
+        assert_eq!(
+            blockchain
+                .get_unspent_records_by_owner::<DPCRecord<Components>>(&account_1.address, 0, None)
+                .unwrap(),
+            vec![records[1].clone()]
+        );
+
+        // Deleting a record removes it from the indexes, and reindexing restores them.
+        blockchain.delete_record(records[1].clone()).unwrap();
+        owned_records.retain(|record| record != &records[1]);
+        assert_eq!(
+            blockchain
+                .get_records_by_owner::<DPCRecord<Components>>(&account_1.address, 0, None)
+                .unwrap(),
+            owned_records
+        );
+        assert!(blockchain
+            .get_unspent_records::<DPCRecord<Components>>(0, None)
+            .unwrap()
+            .is_empty());
+
+        let mut database_transaction = DatabaseTransaction::new();
+        for key in blockchain.storage.get_keys(COL_RECORDS_BY_OWNER).unwrap() {
+            database_transaction.delete(COL_RECORDS_BY_OWNER, &key);
+        }
+        blockchain.storage.batch(database_transaction).unwrap();
+        assert!(blockchain
+            .get_records_by_owner::<DPCRecord<Components>>(&account_1.address, 0, None)
+            .unwrap()
+            .is_empty());
+
+        blockchain.reindex_records::<DPCRecord<Components>>().unwrap();
+        assert_eq!(
+            blockchain
+                .get_records_by_owner::<DPCRecord<Components>>(&account_1.address, 0, None)
+                .unwrap(),
+            owned_records
+        );
+        assert_eq!(
+            blockchain
+                .get_records_by_owner::<DPCRecord<Components>>(&account_2.address, 0, None)
+                .unwrap(),
+            vec![records[3].clone()]
+        );
+
+        // The records of a ledger with schema version 3 are indexed by the first query after the migration.
+        let mut database_transaction = DatabaseTransaction::new();
+        for col in &[
+            COL_RECORDS_BY_OWNER,
+            COL_RECORDS_BY_BIRTH_PROGRAM_ID,
+            COL_RECORDS_BY_DEATH_PROGRAM_ID,
+        ] {
+            for key in blockchain.storage.get_keys(*col).unwrap() {
+                database_transaction.delete(*col, &key);
+            }
+        }
+        blockchain.storage.batch(database_transaction).unwrap();
+
+        let migration = Store::get_migration(3).unwrap();
+        blockchain
+            .storage
+            .batch(migration(&blockchain.storage, &blockchain.ledger_parameters).unwrap())
+            .unwrap();
+        assert_eq!(
+            blockchain
+                .get_records_by_death_program_id::<DPCRecord<Components>>(&[2u8; 48], 0, None)
+                .unwrap()
+                .len(),
+            3
+        );
+        assert_eq!(
+            blockchain
+                .get_records_by_owner::<DPCRecord<Components>>(&account_1.address, 0, None)
+                .unwrap(),
+            owned_records
+        );
+
+        kill_storage_sync(blockchain);
+    }
+
+    #[test]
+    pub fn test_ledger_view() {
+        let genesis_ledger: Arc<Store> = open_test_blockchain();
+        let transaction = genesis_ledger.get_block_from_block_number(0).unwrap().transactions.0[0].clone();
+        let sn = &transaction.old_serial_numbers()[0];
+        let cm = &transaction.new_commitments()[0];
+
+        // Start from a ledger that does not contain the genesis transaction.
+        let genesis_block = Block {
+            header: BlockHeader {
+                difficulty_target: 100,
+                nonce: 0,
+                merkle_root_hash: MerkleRootHash([0; 32]),
+                previous_block_hash: BlockHeaderHash([0; 32]),
+                time: 123,
+                proof: ProofOfSuccinctWork::default(),
+                pedersen_merkle_root_hash: PedersenMerkleRootHash([0; 32]),
+            },
+            transactions: DPCTransactions::new(),
+        };
+        let blockchain = Arc::new(initialize_test_blockchain(
+            genesis_ledger.ledger_parameters.clone(),
+            genesis_block,
+        ));
+        let digest = blockchain.digest().unwrap();
+
+        // A discarded view leaves the ledger unchanged.
+        let mut view = LedgerView::new(blockchain.clone()).unwrap();
+        view.apply_transaction(&transaction).unwrap();
+        assert!(view.contains_sn(sn));
+        assert!(view.contains_cm(cm));
+        assert!(view.contains_memo(transaction.memorandum()));
+        assert!(!blockchain.contains_sn(sn));
+        assert_ne!(view.digest().unwrap(), digest);
+
+        // A transaction conflicting with an applied transaction is rejected.
+        match view.apply_transaction(&transaction) {
+            Err(StorageError::ExistingSn(_)) => {}
+            result => panic!("expected an existing serial number, found {:?}", result),
+        }
+        assert_eq!(view.transactions().len(), 1);
+
+        view.discard();
+        assert_eq!(blockchain.digest().unwrap(), digest);
+        assert!(!blockchain.contains_cm(cm));
+
+        // A flushed view commits a block with the applied transactions.
+        let mut view = LedgerView::new(blockchain.clone()).unwrap();
+        let stale_view = LedgerView::new(blockchain.clone()).unwrap();
+        view.apply_transaction(&transaction).unwrap();
+
+        let view_digest = view.digest().unwrap();
+        let path = view.prove_cm(cm).unwrap();
+        assert!(LedgerView::<Tx, CommitmentMerkleParameters, MemDb>::verify_cm(
+            view.parameters(),
+            &view_digest,
+            cm,
+            &path
+        ));
+        assert!(view.validate_digest(&view_digest));
+        assert!(!blockchain.validate_digest(&view_digest));
+
+        let header = BlockHeader {
+            difficulty_target: 100,
+            nonce: 1,
+            merkle_root_hash: MerkleRootHash([0; 32]),
+            previous_block_hash: blockchain.get_latest_block().unwrap().header.get_hash(),
+            time: 123,
+            proof: ProofOfSuccinctWork::default(),
+            pedersen_merkle_root_hash: PedersenMerkleRootHash([0; 32]),
+        };
+        let block = view.flush(header.clone()).unwrap();
+
+        assert_eq!(blockchain.get_latest_block_height(), 1);
+        assert_eq!(blockchain.get_latest_block().unwrap(), block);
+        assert_eq!(blockchain.digest().unwrap(), view_digest);
+        assert!(blockchain.contains_sn(sn));
+        assert!(Store::verify_cm(
+            blockchain.parameters(),
+            &view_digest,
+            cm,
+            &blockchain.prove_cm(cm).unwrap()
+        ));
+        assert!(blockchain.check_integrity().unwrap().is_empty());
+
+        // A view of an outdated ledger state can't be flushed.
+        let header = BlockHeader {
+            nonce: 2,
+            previous_block_hash: block.header.get_hash(),
+            ..header
+        };
+        match stale_view.flush(header) {
+            Err(StorageError::StaleLedgerView) => {}
+            result => panic!(
+                "expected a stale ledger view, found {:?}",
+                result.map(|block| block.header)
+            ),
+        }
+
+        kill_storage_sync(blockchain);
+        kill_storage_sync(genesis_ledger);
+    }
+
+    #[test]
+    pub fn test_snapshot() {
+        let blockchain: Arc<Store> = open_test_blockchain();
+
+        let mut trusted_state = None;
+        for i in 0..3 {
+            let transactions = DPCTransactions(vec![transaction_with_new_records(&blockchain, i as u8 + 1)]);
+            let block = Block {
+                header: BlockHeader {
+                    difficulty_target: 100,
+                    nonce: i,
+                    merkle_root_hash: MerkleRootHash(merkle_root(&transactions.to_transaction_ids().unwrap())),
+                    previous_block_hash: blockchain.get_latest_block().unwrap().header.get_hash(),
+                    time: 123,
+                    proof: ProofOfSuccinctWork::default(),
+                    pedersen_merkle_root_hash: PedersenMerkleRootHash([0; 32]),
+                },
+                transactions,
+            };
+            blockchain.insert_and_commit(&block).unwrap();
+
+            if blockchain.get_latest_block_height() == 2 {
+                trusted_state = Some((blockchain.digest().unwrap(), blockchain.current_cm_index().unwrap()));
+            }
+        }
+        let (trusted_digest, trusted_cm_index) = trusted_state.unwrap();
+
+        let mut snapshot = vec![];
+        blockchain.export_snapshot(&mut snapshot, 2).unwrap();
+        let block_hash = blockchain.get_block_hash(2).unwrap();
+
+        // A snapshot with an unexpected block hash or an invalid checksum is rejected.
+        let new_blockchain: Arc<Store> = open_test_blockchain();
+        let genesis_hash = blockchain.get_block_hash(0).unwrap();
+        assert!(new_blockchain
+            .import_snapshot(&snapshot[..], &genesis_hash, &trusted_digest)
+            .is_err());
+
+        let mut corrupted_snapshot = snapshot.clone();
+        let last_byte = corrupted_snapshot.len() - 1;
+        corrupted_snapshot[last_byte] ^= 1;
+        match new_blockchain.import_snapshot(&corrupted_snapshot[..], &block_hash, &trusted_digest) {
+            Err(StorageError::InvalidSnapshot(_)) => {}
+            result => panic!("expected an invalid snapshot, found {:?}", result),
+        }
+
+        // A snapshot that doesn't match the trusted ledger digest is rejected.
+        match new_blockchain.import_snapshot(&snapshot[..], &block_hash, &Default::default()) {
+            Err(StorageError::InvalidSnapshot(_)) => {}
+            result => panic!("expected an invalid snapshot, found {:?}", result),
+        }
+        assert_eq!(new_blockchain.get_latest_block_height(), 0);
+
+        new_blockchain
+            .import_snapshot(&snapshot[..], &block_hash, &trusted_digest)
+            .unwrap();
+
+        assert_eq!(new_blockchain.get_latest_block_height(), 2);
+        assert_eq!(new_blockchain.get_block_hash(2).unwrap(), block_hash);
+        assert_eq!(new_blockchain.digest().unwrap(), trusted_digest);
+        assert_eq!(new_blockchain.current_cm_index().unwrap(), trusted_cm_index);
+        assert_eq!(new_blockchain.cm_merkle_tree.read().root(), trusted_digest);
+        assert!(new_blockchain.check_integrity().unwrap().is_empty());
+
+        // The block of the snapshot is imported with its transactions, and the earlier blocks are pruned.
+        assert_eq!(new_blockchain.get_pruned_block_number().unwrap(), 2);
+        assert_eq!(
+            new_blockchain.get_block(&block_hash).unwrap(),
+            blockchain.get_block(&block_hash).unwrap()
+        );
+        match new_blockchain.get_block(&new_blockchain.get_block_hash(1).unwrap()) {
+            Err(StorageError::PrunedBlock(_)) => {}
+            result => panic!("expected a pruned block, found {:?}", result.map(|block| block.header)),
+        }
+
+        // The imported ledger continues from the snapshot.
+        new_blockchain
+            .insert_and_commit(&blockchain.get_block_from_block_number(3).unwrap())
+            .unwrap();
+        assert_eq!(new_blockchain.get_latest_block_height(), 3);
+
+        kill_storage_sync(blockchain);
+        kill_storage_sync(new_blockchain);
+    }
+
+    #[test]
+    pub fn test_snapshot_invalid_merkle_root() {
+        let blockchain: Arc<Store> = open_test_blockchain();
+
+        // The header of the block doesn't commit to its transactions.
+        let block = Block {
+            header: BlockHeader {
+                difficulty_target: 100,
+                nonce: 0,
+                merkle_root_hash: MerkleRootHash([0; 32]),
+                previous_block_hash: blockchain.get_latest_block().unwrap().header.get_hash(),
+                time: 123,
+                proof: ProofOfSuccinctWork::default(),
+                pedersen_merkle_root_hash: PedersenMerkleRootHash([0; 32]),
+            },
+            transactions: DPCTransactions(vec![transaction_with_new_records(&blockchain, 1)]),
+        };
+        blockchain.insert_and_commit(&block).unwrap();
+
+        let mut snapshot = vec![];
+        blockchain.export_snapshot(&mut snapshot, 1).unwrap();
+
+        let new_blockchain: Arc<Store> = open_test_blockchain();
+        let result =
+            new_blockchain.import_snapshot(&snapshot[..], &block.header.get_hash(), &blockchain.digest().unwrap());
+        match result {
+            Err(StorageError::InvalidSnapshot(_)) => {}
+            result => panic!("expected an invalid snapshot, found {:?}", result),
+        }
+        assert_eq!(new_blockchain.get_latest_block_height(), 0);
+
+        kill_storage_sync(blockchain);
+        kill_storage_sync(new_blockchain);
+    }
+
+    #[test]
+    pub fn test_check_integrity() {
+        let blockchain: Arc<Store> = open_test_blockchain();
+
+        for i in 0..3 {
+            let block = Block {
+                header: BlockHeader {
+                    difficulty_target: 100,
+                    nonce: i,
+                    merkle_root_hash: MerkleRootHash([0; 32]),
+                    previous_block_hash: blockchain.get_latest_block().unwrap().header.get_hash(),
+                    time: 123,
+                    proof: ProofOfSuccinctWork::default(),
+                    pedersen_merkle_root_hash: PedersenMerkleRootHash([0; 32]),
+                },
+                transactions: DPCTransactions::new(),
+            };
+            blockchain.insert_and_commit(&block).unwrap();
+        }
+        blockchain.remove_latest_block().unwrap();
+
+        assert_eq!(blockchain.check_integrity().unwrap(), vec![]);
+
+        // Corrupt a block locator and the current commitment index.
+        let block_hash = blockchain.get_block_hash(1).unwrap();
+        let cm_index = blockchain.current_cm_index().unwrap();
+
+        let mut database_transaction = DatabaseTransaction::new();
+        database_transaction.delete(COL_BLOCK_LOCATOR, &block_hash.0);
+        database_transaction.add(
+            COL_META,
+            KEY_CURR_CM_INDEX.as_bytes(),
+            &(cm_index as u32 + 1).to_le_bytes(),
+        );
+        blockchain.storage.batch(database_transaction).unwrap();
+
+        let inconsistencies = blockchain.check_integrity().unwrap();
+        assert_eq!(inconsistencies, vec![
+            LedgerInconsistency::InvalidBlockNumber {
+                block_hash: block_hash.to_string(),
+                expected: 1,
+                found: None,
+            },
+            LedgerInconsistency::InvalidCurrentIndex {
+                kind: "commitment index",
+                expected: cm_index,
+                found: Some(cm_index + 1),
+            },
+        ]);
+        assert!(inconsistencies
+            .iter()
+            .all(|inconsistency| inconsistency.is_repairable()));
+
+        assert_eq!(blockchain.repair_integrity().unwrap(), inconsistencies);
+        assert_eq!(blockchain.check_integrity().unwrap(), vec![]);
+        assert_eq!(blockchain.get_block_number(&block_hash).unwrap(), 1);
+
+        // A gap in the block locators isn't repaired by truncating the chain.
+        let latest_block_height = blockchain.get_latest_block_height();
+        let cm_index = blockchain.current_cm_index().unwrap();
+
+        let mut database_transaction = DatabaseTransaction::new();
+        database_transaction.delete(COL_BLOCK_LOCATOR, &1u32.to_le_bytes());
+        blockchain.storage.batch(database_transaction).unwrap();
+
+        let inconsistencies = blockchain.check_integrity().unwrap();
+        assert_eq!(inconsistencies, vec![LedgerInconsistency::MissingBlockHash(1)]);
+        assert!(!inconsistencies[0].is_repairable());
+
+        assert_eq!(blockchain.repair_integrity().unwrap(), inconsistencies);
+        assert_eq!(blockchain.get_latest_block_height(), latest_block_height);
+        assert_eq!(blockchain.current_cm_index().unwrap(), cm_index);
+        assert!(blockchain.get_block_hash(latest_block_height).is_ok());
+
+        kill_storage_sync(blockchain);
+    }
+
+    #[test]
+    pub fn test_mem_db_batch() {
+        let storage = MemDb::open(None, None).unwrap();
+        assert!(storage.in_memory());
+
+        let mut database_transaction = DatabaseTransaction::new();
+        database_transaction.add(1, b"key 1", b"value 1");
+        database_transaction.add(1, b"key 2", b"value 2");
+        database_transaction.add(2, b"key 3", b"value 3");
+        database_transaction.delete(1, b"key 1");
+        storage.batch(database_transaction).unwrap();
+
+        assert!(!storage.exists(1, b"key 1"));
+        assert_eq!(storage.get(1, b"key 2").unwrap(), Some(b"value 2".to_vec()));
+        assert_eq!(storage.get_keys(2).unwrap(), vec![b"key 3".to_vec().into_boxed_slice()]);
+        assert_eq!(storage.get_col(1).unwrap().len(), 1);
+        assert!(storage.get_col(3).unwrap().is_empty());
+
+        storage.destroy().unwrap();
+        assert!(!storage.exists(1, b"key 2"));
+    }
+
+    #[test]
+    pub fn test_get_keys_with_prefix() {
+        fn check_prefix_iteration<S: Storage>(storage: &S) {
+            let mut database_transaction = DatabaseTransaction::new();
+            for key in &[&b"a"[..], b"ab", b"abc", b"abd", b"b", b"ba"] {
+                database_transaction.add(1, key, b"");
+            }
+            storage.batch(database_transaction).unwrap();
+
+            let keys = |prefix: &[u8]| -> Vec<Vec<u8>> {
+                storage
+                    .get_keys_with_prefix(1, prefix)
+                    .unwrap()
+                    .into_iter()
+                    .map(|key| key.to_vec())
+                    .collect()
+            };
+            assert_eq!(keys(b"ab"), vec![b"ab".to_vec(), b"abc".to_vec(), b"abd".to_vec()]);
+            assert_eq!(keys(b"b"), vec![b"b".to_vec(), b"ba".to_vec()]);
+            assert!(keys(b"c").is_empty());
+            assert!(storage.get_keys_with_prefix(2, b"a").unwrap().is_empty());
+        }
+
+        check_prefix_iteration(&MemDb::open(None, None).unwrap());
+
+        let mut path = std::env::temp_dir();
+        path.push(random_storage_path());
+        let storage = RocksDb::open(Some(&path), None).unwrap();
+        check_prefix_iteration(&storage);
+        drop(storage);
+        kill_storage_async::<Tx, CommitmentMerkleParameters>(path);
     }
 
     mod test_invalid {
@@ -123,7 +1299,7 @@ mod test_storage {
 
         #[test]
         pub fn test_invalid_block_addition() {
-            let (blockchain, _): (Arc<Store>, _) = open_test_blockchain();
+            let blockchain: Arc<Store> = open_test_blockchain();
 
             let latest_block = blockchain.get_latest_block().unwrap();
 
@@ -134,7 +1310,7 @@ mod test_storage {
 
         #[test]
         pub fn test_invalid_block_removal() {
-            let (blockchain, _): (Arc<Store>, _) = open_test_blockchain();
+            let blockchain: Arc<Store> = open_test_blockchain();
 
             assert!(blockchain.remove_latest_block().is_err());
             assert!(blockchain.remove_latest_blocks(5).is_err());
@@ -144,7 +1320,7 @@ mod test_storage {
 
         #[test]
         pub fn test_invalid_block_retrieval() {
-            let (blockchain, _): (Arc<Store>, _) = open_test_blockchain();
+            let blockchain: Arc<Store> = open_test_blockchain();
 
             assert!(blockchain.get_block_from_block_number(2).is_err());
             assert!(blockchain.get_block_from_block_number(10).is_err());
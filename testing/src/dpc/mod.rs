@@ -21,12 +21,12 @@ use snarkvm_dpc::{
     traits::{AccountScheme, DPCScheme},
 };
 use snarkvm_parameters::{traits::Parameter, LedgerMerkleTreeParameters};
-use snarkvm_storage::Ledger;
+use snarkvm_storage::{Ledger, MemDb};
 use snarkvm_utilities::bytes::FromBytes;
 
 use rand::Rng;
 
-pub type MerkleTreeLedger = Ledger<Tx, CommitmentMerkleParameters>;
+pub type MerkleTreeLedger = Ledger<Tx, CommitmentMerkleParameters, MemDb>;
 
 pub fn setup_or_load_parameters<R: Rng>(
     verify_only: bool,
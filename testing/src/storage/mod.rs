@@ -17,15 +17,15 @@
 use snarkvm_algorithms::traits::merkle_tree::LoadableMerkleParameters;
 use snarkvm_dpc::base_dpc::instantiated::{CommitmentMerkleParameters, Tx};
 use snarkvm_objects::{
-    traits::{LedgerScheme, Transaction},
+    traits::{LedgerScheme, Storage, Transaction},
     Block,
 };
-pub use snarkvm_storage::Ledger;
+pub use snarkvm_storage::{Ledger, MemDb, RocksDb};
 
 use rand::{thread_rng, Rng};
 use std::{path::PathBuf, sync::Arc};
 
-pub type Store = Ledger<Tx, CommitmentMerkleParameters>;
+pub type Store = Ledger<Tx, CommitmentMerkleParameters, MemDb>;
 
 pub fn random_storage_path() -> String {
     let random_path: usize = thread_rng().gen();
@@ -36,41 +36,23 @@ pub fn random_storage_path() -> String {
 pub fn initialize_test_blockchain<T: Transaction, P: LoadableMerkleParameters>(
     parameters: P,
     genesis_block: Block<T>,
-) -> Ledger<T, P> {
-    let mut path = std::env::temp_dir();
-    path.push(random_storage_path());
-
-    Ledger::<T, P>::destroy_storage(path.clone()).unwrap();
-
-    Ledger::<T, P>::new(Some(&path), parameters, genesis_block).unwrap()
+) -> Ledger<T, P, MemDb> {
+    Ledger::<T, P, MemDb>::new(None, parameters, genesis_block).unwrap()
 }
 
 // Open a test blockchain from stored genesis attributes
-pub fn open_test_blockchain<T: Transaction, P: LoadableMerkleParameters>() -> (Arc<Ledger<T, P>>, PathBuf) {
-    let mut path = std::env::temp_dir();
-    path.push(random_storage_path());
-
-    Ledger::<T, P>::destroy_storage(path.clone()).unwrap();
-
-    let storage = Arc::new(Ledger::<T, P>::open_at_path(path.clone()).unwrap());
-
-    (storage, path)
+pub fn open_test_blockchain<T: Transaction, P: LoadableMerkleParameters>() -> Arc<Ledger<T, P, MemDb>> {
+    Arc::new(Ledger::<T, P, MemDb>::open_in_memory().unwrap())
 }
 
-pub fn kill_storage<T: Transaction, P: LoadableMerkleParameters>(ledger: Ledger<T, P>) {
-    let path = ledger.storage.db.path().to_owned();
-
-    drop(ledger);
-    Ledger::<T, P>::destroy_storage(path).unwrap();
+pub fn kill_storage<T: Transaction, P: LoadableMerkleParameters, S: Storage>(ledger: Ledger<T, P, S>) {
+    ledger.storage.destroy().unwrap();
 }
 
 pub fn kill_storage_async<T: Transaction, P: LoadableMerkleParameters>(path: PathBuf) {
-    Ledger::<T, P>::destroy_storage(path).unwrap();
+    Ledger::<T, P, RocksDb>::destroy_storage(path).unwrap();
 }
 
-pub fn kill_storage_sync<T: Transaction, P: LoadableMerkleParameters>(ledger: Arc<Ledger<T, P>>) {
-    let path = ledger.storage.db.path().to_owned();
-
-    drop(ledger);
-    Ledger::<T, P>::destroy_storage(path).unwrap();
+pub fn kill_storage_sync<T: Transaction, P: LoadableMerkleParameters, S: Storage>(ledger: Arc<Ledger<T, P, S>>) {
+    ledger.storage.destroy().unwrap();
 }